@@ -0,0 +1,339 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Pool factory
+//! Deploys `LiquidityPool` contracts at deterministic addresses and keeps the canonical registry of pools,
+//! one per sorted token pair and fee amount.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    instantiate2_address, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply,
+    Response, StdError, StdResult, SubMsg, WasmMsg,
+};
+use cw_storage_plus::Bound;
+use cw_utils::parse_reply_instantiate_data;
+use liquidity_pool::msg::InstantiateMsg as PoolInstantiateMsg;
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, FeeTier, FeeTiersResponse, InstantiateMsg, PoolAddressResponse,
+    PoolKey, PoolResponse, PoolsResponse, QueryMsg,
+};
+use crate::state::{
+    Config, PendingPool, PoolInfo, CONFIG, FEE_AMOUNT_TICK_SPACING, PENDING_POOL, POOLS,
+};
+
+/// Fees are expressed in hundredths of a bip
+const FEE_DENOMINATOR: u32 = 1_000_000;
+/// Tick spacing is capped to prevent the situation where tick_spacing is so large that
+/// the tick bitmap's next_initialized_tick_within_one_word overflows an i24 container from a valid tick
+const MAX_TICK_SPACING: i32 = 16384;
+/// The fee amounts enabled at instantiation: 0.05%, 0.3% and 1%
+const DEFAULT_FEE_AMOUNTS: [(u32, i32); 3] = [(500, 10), (3000, 60), (10000, 200)];
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub const INSTANTIATE_POOL_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: owner.clone(),
+            pool_code_id: msg.pool_code_id,
+        },
+    )?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", owner);
+    for (fee, tick_spacing) in DEFAULT_FEE_AMOUNTS {
+        FEE_AMOUNT_TICK_SPACING.save(deps.storage, fee, &tick_spacing)?;
+        res = res.add_attribute("fee_amount_enabled", format!("{fee}:{tick_spacing}"));
+    }
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreatePool {
+            token_a,
+            token_b,
+            fee,
+        } => create_pool(deps, info, token_a, token_b, fee),
+        ExecuteMsg::SetOwner { owner } => set_owner(deps, info, owner),
+        ExecuteMsg::EnableFeeAmount { fee, tick_spacing } => {
+            enable_fee_amount(deps, info, fee, tick_spacing)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        INSTANTIATE_POOL_REPLY_ID => register_pool(deps, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::FeeTiers {} => to_binary(&query_fee_tiers(deps)?),
+        QueryMsg::Pool {
+            token_a,
+            token_b,
+            fee,
+        } => to_binary(&query_pool(deps, token_a, token_b, fee)?),
+        QueryMsg::Pools { start_after, limit } => {
+            to_binary(&query_pools(deps, start_after, limit)?)
+        }
+        QueryMsg::PoolAddress {
+            token_a,
+            token_b,
+            fee,
+        } => to_binary(&query_pool_address(deps, env, token_a, token_b, fee)?),
+    }
+}
+
+fn create_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_a: String,
+    token_b: String,
+    fee: u32,
+) -> Result<Response, ContractError> {
+    if token_a == token_b {
+        return Err(ContractError::IdenticalTokens {});
+    }
+    let (token0, token1) = sort_tokens(token_a, token_b);
+    let tick_spacing = FEE_AMOUNT_TICK_SPACING
+        .may_load(deps.storage, fee)?
+        .ok_or(ContractError::FeeNotEnabled { fee })?;
+    if POOLS.has(deps.storage, (&token0, &token1, fee)) {
+        return Err(ContractError::PoolExists {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    PENDING_POOL.save(
+        deps.storage,
+        &PendingPool {
+            token0: token0.clone(),
+            token1: token1.clone(),
+            fee,
+            tick_spacing,
+        },
+    )?;
+
+    let instantiate = WasmMsg::Instantiate2 {
+        admin: None,
+        code_id: config.pool_code_id,
+        label: format!("Xion pool {token0}/{token1} {fee}"),
+        msg: to_binary(&PoolInstantiateMsg {
+            token0: token0.clone(),
+            token1: token1.clone(),
+            fee,
+            tick_spacing,
+        })?,
+        funds: vec![],
+        salt: pool_salt(&token0, &token1, fee),
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            instantiate,
+            INSTANTIATE_POOL_REPLY_ID,
+        ))
+        .add_attribute("action", "create_pool")
+        .add_attribute("token0", token0)
+        .add_attribute("token1", token1)
+        .add_attribute("fee", fee.to_string())
+        .add_attribute("tick_spacing", tick_spacing.to_string())
+        .add_attribute("sender", info.sender))
+}
+
+/// Records the pool instantiated by `create_pool` in the registry
+fn register_pool(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
+    let pending = PENDING_POOL.load(deps.storage)?;
+    PENDING_POOL.remove(deps.storage);
+
+    let res = parse_reply_instantiate_data(msg)?;
+    let address = deps.api.addr_validate(&res.contract_address)?;
+
+    let pool = PoolInfo {
+        token0: pending.token0,
+        token1: pending.token1,
+        fee: pending.fee,
+        tick_spacing: pending.tick_spacing,
+        address,
+    };
+    POOLS.save(deps.storage, (&pool.token0, &pool.token1, pool.fee), &pool)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "pool_created")
+        .add_attribute("token0", pool.token0)
+        .add_attribute("token1", pool.token1)
+        .add_attribute("fee", pool.fee.to_string())
+        .add_attribute("tick_spacing", pool.tick_spacing.to_string())
+        .add_attribute("pool", pool.address))
+}
+
+fn set_owner(deps: DepsMut, info: MessageInfo, owner: String) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    let old_owner = config.owner;
+    config.owner = deps.api.addr_validate(&owner)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_owner")
+        .add_attribute("old_owner", old_owner)
+        .add_attribute("new_owner", config.owner))
+}
+
+fn enable_fee_amount(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee: u32,
+    tick_spacing: i32,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if fee >= FEE_DENOMINATOR {
+        return Err(ContractError::InvalidFee { fee });
+    }
+    if tick_spacing <= 0 || tick_spacing >= MAX_TICK_SPACING {
+        return Err(ContractError::InvalidTickSpacing { tick_spacing });
+    }
+    if FEE_AMOUNT_TICK_SPACING.has(deps.storage, fee) {
+        return Err(ContractError::FeeAlreadyEnabled { fee });
+    }
+    FEE_AMOUNT_TICK_SPACING.save(deps.storage, fee, &tick_spacing)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "enable_fee_amount")
+        .add_attribute("fee", fee.to_string())
+        .add_attribute("tick_spacing", tick_spacing.to_string()))
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        owner: config.owner,
+        pool_code_id: config.pool_code_id,
+    })
+}
+
+fn query_fee_tiers(deps: Deps) -> StdResult<FeeTiersResponse> {
+    let fee_tiers = FEE_AMOUNT_TICK_SPACING
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(fee, tick_spacing)| FeeTier { fee, tick_spacing }))
+        .collect::<StdResult<_>>()?;
+    Ok(FeeTiersResponse { fee_tiers })
+}
+
+fn query_pool(deps: Deps, token_a: String, token_b: String, fee: u32) -> StdResult<PoolResponse> {
+    let (token0, token1) = sort_tokens(token_a, token_b);
+    POOLS
+        .load(deps.storage, (&token0, &token1, fee))
+        .map(pool_response)
+}
+
+fn query_pools(
+    deps: Deps,
+    start_after: Option<PoolKey>,
+    limit: Option<u32>,
+) -> StdResult<PoolsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|key| Bound::exclusive((key.token0.as_str(), key.token1.as_str(), key.fee)));
+
+    let pools = POOLS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, pool)| pool_response(pool)))
+        .collect::<StdResult<_>>()?;
+    Ok(PoolsResponse { pools })
+}
+
+fn query_pool_address(
+    deps: Deps,
+    env: Env,
+    token_a: String,
+    token_b: String,
+    fee: u32,
+) -> StdResult<PoolAddressResponse> {
+    let (token0, token1) = sort_tokens(token_a, token_b);
+    let config = CONFIG.load(deps.storage)?;
+    let checksum = deps
+        .querier
+        .query_wasm_code_info(config.pool_code_id)?
+        .checksum;
+    let creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let address = instantiate2_address(
+        checksum.as_slice(),
+        &creator,
+        &pool_salt(&token0, &token1, fee),
+    )
+    .map_err(|err| StdError::generic_err(err.to_string()))?;
+
+    Ok(PoolAddressResponse {
+        address: deps.api.addr_humanize(&address)?,
+    })
+}
+
+/// The instantiate2 salt of a pool: sha256(token0 || 0x00 || token1 || 0x00 || big-endian fee)
+/// Denoms never contain a NUL byte, so distinct pools always get distinct salts.
+pub fn pool_salt(token0: &str, token1: &str, fee: u32) -> Binary {
+    let hash = Sha256::new()
+        .chain_update(token0.as_bytes())
+        .chain_update([0])
+        .chain_update(token1.as_bytes())
+        .chain_update([0])
+        .chain_update(fee.to_be_bytes())
+        .finalize();
+    Binary::from(hash.as_slice())
+}
+
+/// Returns the two tokens sorted, token0 < token1
+fn sort_tokens(token_a: String, token_b: String) -> (String, String) {
+    if token_a < token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    }
+}
+
+fn pool_response(pool: PoolInfo) -> PoolResponse {
+    PoolResponse {
+        token0: pool.token0,
+        token1: pool.token1,
+        fee: pool.fee,
+        tick_spacing: pool.tick_spacing,
+        address: pool.address,
+    }
+}
@@ -0,0 +1,36 @@
+use cosmwasm_std::StdError;
+use cw_utils::ParseReplyError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("A pool needs two different tokens")]
+    IdenticalTokens {},
+
+    #[error("Fee amount {fee} is not enabled")]
+    FeeNotEnabled { fee: u32 },
+
+    #[error("Fee amount {fee} is already enabled")]
+    FeeAlreadyEnabled { fee: u32 },
+
+    #[error("Invalid fee: {fee}")]
+    InvalidFee { fee: u32 },
+
+    #[error("Invalid tick spacing: {tick_spacing}")]
+    InvalidTickSpacing { tick_spacing: i32 },
+
+    #[error("Pool already exists")]
+    PoolExists {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+}
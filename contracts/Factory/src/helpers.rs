@@ -0,0 +1,35 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, QuerierWrapper};
+use liquidity_pool::msg::QueryMsg as PoolQueryMsg;
+use liquidity_pool::state::Config as PoolConfig;
+
+use crate::msg::{PoolResponse, QueryMsg};
+
+/// A handle on a deployed factory, for contracts that only work with the factory's pools
+#[cw_serde]
+pub struct FactoryContract(pub Addr);
+
+impl FactoryContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    /// Whether `pool` is the pool the factory registered for its tokens and fee
+    /// Contracts that don't answer the pool `Config` query are not pools
+    pub fn is_pool(&self, querier: &QuerierWrapper, pool: &Addr) -> bool {
+        let Ok(config) = querier.query_wasm_smart::<PoolConfig>(pool, &PoolQueryMsg::Config {})
+        else {
+            return false;
+        };
+        querier
+            .query_wasm_smart::<PoolResponse>(
+                &self.0,
+                &QueryMsg::Pool {
+                    token_a: config.token0,
+                    token_b: config.token1,
+                    fee: config.fee,
+                },
+            )
+            .is_ok_and(|registered| registered.address == pool)
+    }
+}
@@ -0,0 +1,12 @@
+pub mod contract;
+pub mod error;
+pub mod helpers;
+pub mod msg;
+pub mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use crate::error::ContractError;
+
+#[cfg(test)]
+mod tests;
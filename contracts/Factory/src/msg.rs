@@ -0,0 +1,100 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Addr;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Code id of the `LiquidityPool` contract instantiated for every new pool
+    pub pool_code_id: u64,
+    /// The factory owner, defaults to the sender
+    pub owner: Option<String>,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a pool for the given two tokens and fee
+    /// The tokens may be passed in either order; the pool is instantiated at a deterministic address
+    CreatePool {
+        token_a: String,
+        token_b: String,
+        fee: u32,
+    },
+    /// Updates the owner of the factory, must be called by the current owner
+    SetOwner { owner: String },
+    /// Enables a fee amount with the given tick spacing, must be called by the owner
+    /// Fee amounts may never be removed once enabled
+    EnableFeeAmount { fee: u32, tick_spacing: i32 },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Every enabled fee amount with its tick spacing
+    #[returns(FeeTiersResponse)]
+    FeeTiers {},
+    /// The pool for the given tokens and fee; the tokens may be passed in either order
+    #[returns(PoolResponse)]
+    Pool {
+        token_a: String,
+        token_b: String,
+        fee: u32,
+    },
+    /// All pools, ordered by sorted token pair and fee
+    #[returns(PoolsResponse)]
+    Pools {
+        start_after: Option<PoolKey>,
+        limit: Option<u32>,
+    },
+    /// The address a pool for the given tokens and fee is (or would be) instantiated at
+    #[returns(PoolAddressResponse)]
+    PoolAddress {
+        token_a: String,
+        token_b: String,
+        fee: u32,
+    },
+}
+
+/// Identifies a pool by its sorted token pair and fee
+#[cw_serde]
+pub struct PoolKey {
+    pub token0: String,
+    pub token1: String,
+    pub fee: u32,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub pool_code_id: u64,
+}
+
+#[cw_serde]
+pub struct FeeTier {
+    pub fee: u32,
+    pub tick_spacing: i32,
+}
+
+#[cw_serde]
+pub struct FeeTiersResponse {
+    pub fee_tiers: Vec<FeeTier>,
+}
+
+#[cw_serde]
+pub struct PoolResponse {
+    pub token0: String,
+    pub token1: String,
+    pub fee: u32,
+    pub tick_spacing: i32,
+    pub address: Addr,
+}
+
+#[cw_serde]
+pub struct PoolsResponse {
+    pub pools: Vec<PoolResponse>,
+}
+
+#[cw_serde]
+pub struct PoolAddressResponse {
+    pub address: Addr,
+}
@@ -0,0 +1,35 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map};
+
+#[cw_serde]
+pub struct Config {
+    pub owner: Addr,
+    pub pool_code_id: u64,
+}
+
+/// A pool registered with the factory
+#[cw_serde]
+pub struct PoolInfo {
+    pub token0: String,
+    pub token1: String,
+    pub fee: u32,
+    pub tick_spacing: i32,
+    pub address: Addr,
+}
+
+/// A pool whose instantiation is in flight, registered once the instantiate reply arrives
+#[cw_serde]
+pub struct PendingPool {
+    pub token0: String,
+    pub token1: String,
+    pub fee: u32,
+    pub tick_spacing: i32,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// Fee amount, in hundredths of a bip, to the tick spacing of pools created with it
+pub const FEE_AMOUNT_TICK_SPACING: Map<u32, i32> = Map::new("fee_amount_tick_spacing");
+/// Pools keyed by (token0, token1, fee), with token0 < token1
+pub const POOLS: Map<(&str, &str, u32), PoolInfo> = Map::new("pools");
+pub const PENDING_POOL: Item<PendingPool> = Item::new("pending_pool");
@@ -0,0 +1,102 @@
+//! A multi-test deployment of the factory and its pools, for the tests of the contracts built on top of them
+//! Enabled by the `testing` feature
+
+use cosmwasm_std::{Addr, Empty, Uint256};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+use crate::msg::{ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg};
+
+/// The sender that deploys the factory and owns it
+pub const OWNER: &str = "owner";
+
+pub fn factory_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply(crate::contract::reply),
+    )
+}
+
+pub fn pool_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            liquidity_pool::contract::execute,
+            liquidity_pool::contract::instantiate,
+            liquidity_pool::contract::query,
+        )
+        .with_reply(liquidity_pool::contract::reply),
+    )
+}
+
+/// Stores the pool and factory code and instantiates a factory owned by `OWNER`
+pub fn deploy_factory(app: &mut App) -> Addr {
+    let pool_code_id = app.store_code(pool_contract());
+    let factory_code_id = app.store_code(factory_contract());
+    app.instantiate_contract(
+        factory_code_id,
+        Addr::unchecked(OWNER),
+        &InstantiateMsg {
+            pool_code_id,
+            owner: None,
+        },
+        &[],
+        "factory",
+        None,
+    )
+    .unwrap()
+}
+
+/// Creates the factory's pool for the tokens and fee and initializes it at `sqrt_price_x96`
+pub fn deploy_pool(
+    app: &mut App,
+    factory: &Addr,
+    token_a: &str,
+    token_b: &str,
+    fee: u32,
+    sqrt_price_x96: Uint256,
+) -> Addr {
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory.clone(),
+        &ExecuteMsg::CreatePool {
+            token_a: token_a.to_string(),
+            token_b: token_b.to_string(),
+            fee,
+        },
+        &[],
+    )
+    .unwrap();
+    let pool: PoolResponse = app
+        .wrap()
+        .query_wasm_smart(
+            factory,
+            &QueryMsg::Pool {
+                token_a: token_a.to_string(),
+                token_b: token_b.to_string(),
+                fee,
+            },
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        pool.address.clone(),
+        &liquidity_pool::msg::ExecuteMsg::Initialize { sqrt_price_x96 },
+        &[],
+    )
+    .unwrap();
+    pool.address
+}
+
+/// The last value of an attribute, so that replies win over the messages that triggered them
+pub fn attribute(res: &AppResponse, key: &str) -> String {
+    res.events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .rfind(|attr| attr.key == key)
+        .unwrap()
+        .value
+        .clone()
+}
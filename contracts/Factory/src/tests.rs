@@ -0,0 +1,462 @@
+use cosmwasm_std::{coin, coins, Addr, Int256, Uint128, Uint256};
+use cw_multi_test::{App, AppResponse, Executor};
+use libraries::fixed_point_96::FixedPoint96;
+use libraries::tick_math::MIN_SQRT_RATIO;
+use liquidity_pool::msg::{ExecuteMsg as PoolExecuteMsg, QueryMsg as PoolQueryMsg};
+use liquidity_pool::state::{ProtocolFees, Slot0};
+use liquidity_pool::ContractError as PoolContractError;
+
+use crate::contract::pool_salt;
+use crate::error::ContractError;
+use crate::msg::{
+    ConfigResponse, ExecuteMsg, FeeTier, FeeTiersResponse, PoolKey, PoolResponse, PoolsResponse,
+    QueryMsg,
+};
+use crate::testing::{deploy_factory, OWNER};
+
+fn setup() -> (App, Addr) {
+    let mut app = App::default();
+    let factory = deploy_factory(&mut app);
+    (app, factory)
+}
+
+fn create_pool(
+    app: &mut App,
+    factory: &Addr,
+    token_a: &str,
+    token_b: &str,
+    fee: u32,
+) -> anyhow::Result<AppResponse> {
+    app.execute_contract(
+        Addr::unchecked("anyone"),
+        factory.clone(),
+        &ExecuteMsg::CreatePool {
+            token_a: token_a.to_string(),
+            token_b: token_b.to_string(),
+            fee,
+        },
+        &[],
+    )
+}
+
+fn contract_error(err: &anyhow::Error) -> &ContractError {
+    err.root_cause().downcast_ref::<ContractError>().unwrap()
+}
+
+fn pool_error(err: &anyhow::Error) -> &PoolContractError {
+    err.root_cause()
+        .downcast_ref::<PoolContractError>()
+        .unwrap()
+}
+
+fn query_pool(app: &App, factory: &Addr, token_a: &str, token_b: &str, fee: u32) -> PoolResponse {
+    app.wrap()
+        .query_wasm_smart(
+            factory,
+            &QueryMsg::Pool {
+                token_a: token_a.to_string(),
+                token_b: token_b.to_string(),
+                fee,
+            },
+        )
+        .unwrap()
+}
+
+#[test]
+fn instantiate_enables_default_fee_tiers() {
+    let (app, factory) = setup();
+
+    let config: ConfigResponse = app
+        .wrap()
+        .query_wasm_smart(&factory, &QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.owner, Addr::unchecked(OWNER));
+
+    let tiers: FeeTiersResponse = app
+        .wrap()
+        .query_wasm_smart(&factory, &QueryMsg::FeeTiers {})
+        .unwrap();
+    assert_eq!(
+        tiers.fee_tiers,
+        vec![
+            FeeTier {
+                fee: 500,
+                tick_spacing: 10
+            },
+            FeeTier {
+                fee: 3000,
+                tick_spacing: 60
+            },
+            FeeTier {
+                fee: 10000,
+                tick_spacing: 200
+            },
+        ]
+    );
+}
+
+#[test]
+fn create_pool_instantiates_and_registers_sorted_pool() {
+    let (mut app, factory) = setup();
+
+    let res = create_pool(&mut app, &factory, "uxion", "uatom", 3000).unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "action" && attr.value == "pool_created")));
+    // the creator is reported, not the factory itself
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "sender" && attr.value == "anyone")));
+
+    let pool = query_pool(&app, &factory, "uatom", "uxion", 3000);
+    assert_eq!(pool.token0, "uatom");
+    assert_eq!(pool.token1, "uxion");
+    assert_eq!(pool.tick_spacing, 60);
+    // either token order resolves to the same pool
+    assert_eq!(query_pool(&app, &factory, "uxion", "uatom", 3000), pool);
+
+    let config: liquidity_pool::state::Config = app
+        .wrap()
+        .query_wasm_smart(&pool.address, &liquidity_pool::msg::QueryMsg::Config {})
+        .unwrap();
+    assert_eq!(config.factory, factory);
+    assert_eq!(config.token0, "uatom");
+    assert_eq!(config.token1, "uxion");
+    assert_eq!(config.fee, 3000);
+    assert_eq!(config.tick_spacing, 60);
+}
+
+#[test]
+fn create_pool_rejects_duplicates_and_bad_input() {
+    let (mut app, factory) = setup();
+    create_pool(&mut app, &factory, "uatom", "uxion", 3000).unwrap();
+
+    let err = create_pool(&mut app, &factory, "uxion", "uatom", 3000).unwrap_err();
+    assert_eq!(*contract_error(&err), ContractError::PoolExists {});
+
+    let err = create_pool(&mut app, &factory, "uatom", "uatom", 3000).unwrap_err();
+    assert_eq!(*contract_error(&err), ContractError::IdenticalTokens {});
+
+    let err = create_pool(&mut app, &factory, "uatom", "uxion", 100).unwrap_err();
+    assert_eq!(
+        *contract_error(&err),
+        ContractError::FeeNotEnabled { fee: 100 }
+    );
+
+    // a different fee tier is a different pool
+    create_pool(&mut app, &factory, "uatom", "uxion", 500).unwrap();
+    let pools: PoolsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory,
+            &QueryMsg::Pools {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(pools.pools.len(), 2);
+}
+
+#[test]
+fn pools_are_paginated() {
+    let (mut app, factory) = setup();
+    create_pool(&mut app, &factory, "uatom", "uxion", 500).unwrap();
+    create_pool(&mut app, &factory, "uatom", "uxion", 3000).unwrap();
+    create_pool(&mut app, &factory, "uosmo", "uxion", 3000).unwrap();
+
+    let page: PoolsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory,
+            &QueryMsg::Pools {
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+    assert_eq!(page.pools.len(), 2);
+    assert_eq!(page.pools[1].fee, 3000);
+
+    let last = &page.pools[1];
+    let page: PoolsResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &factory,
+            &QueryMsg::Pools {
+                start_after: Some(PoolKey {
+                    token0: last.token0.clone(),
+                    token1: last.token1.clone(),
+                    fee: last.fee,
+                }),
+                limit: Some(2),
+            },
+        )
+        .unwrap();
+    assert_eq!(page.pools.len(), 1);
+    assert_eq!(page.pools[0].token0, "uosmo");
+}
+
+#[test]
+fn owner_actions_are_restricted() {
+    let (mut app, factory) = setup();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            factory.clone(),
+            &ExecuteMsg::EnableFeeAmount {
+                fee: 100,
+                tick_spacing: 1,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(*contract_error(&err), ContractError::Unauthorized {});
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            factory.clone(),
+            &ExecuteMsg::EnableFeeAmount {
+                fee: 100,
+                tick_spacing: 16384,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        *contract_error(&err),
+        ContractError::InvalidTickSpacing {
+            tick_spacing: 16384
+        }
+    );
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            factory.clone(),
+            &ExecuteMsg::EnableFeeAmount {
+                fee: 3000,
+                tick_spacing: 1,
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        *contract_error(&err),
+        ContractError::FeeAlreadyEnabled { fee: 3000 }
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory.clone(),
+        &ExecuteMsg::EnableFeeAmount {
+            fee: 100,
+            tick_spacing: 1,
+        },
+        &[],
+    )
+    .unwrap();
+    create_pool(&mut app, &factory, "uatom", "uxion", 100).unwrap();
+    assert_eq!(
+        query_pool(&app, &factory, "uatom", "uxion", 100).tick_spacing,
+        1
+    );
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory.clone(),
+        &ExecuteMsg::SetOwner {
+            owner: "new_owner".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            factory.clone(),
+            &ExecuteMsg::SetOwner {
+                owner: OWNER.to_string(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(*contract_error(&err), ContractError::Unauthorized {});
+}
+
+#[test]
+fn factory_owner_controls_the_protocol_fee() {
+    let mut app = App::new(|router, _, storage| {
+        for user in ["provider", "trader"] {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked(user),
+                    vec![coin(1_000_000_000, "uatom"), coin(1_000_000_000, "uxion")],
+                )
+                .unwrap();
+        }
+    });
+    let factory = deploy_factory(&mut app);
+    create_pool(&mut app, &factory, "uatom", "uxion", 3000).unwrap();
+    let pool = query_pool(&app, &factory, "uatom", "uxion", 3000).address;
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        pool.clone(),
+        &PoolExecuteMsg::Initialize {
+            sqrt_price_x96: FixedPoint96::Q96,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("provider"),
+        pool.clone(),
+        &PoolExecuteMsg::Mint {
+            recipient: "provider".to_string(),
+            tick_lower: -60_000,
+            tick_upper: 60_000,
+            amount: Uint128::new(10_000_000),
+            data: Default::default(),
+        },
+        &[coin(10_000_000, "uatom"), coin(10_000_000, "uxion")],
+    )
+    .unwrap();
+
+    let set_fee_protocol = |fee_protocol0, fee_protocol1| PoolExecuteMsg::SetFeeProtocol {
+        fee_protocol0,
+        fee_protocol1,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            pool.clone(),
+            &set_fee_protocol(4, 4),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(*pool_error(&err), PoolContractError::Unauthorized {});
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            pool.clone(),
+            &set_fee_protocol(4, 3),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        *pool_error(&err),
+        PoolContractError::InvalidFeeProtocol {
+            fee_protocol0: 4,
+            fee_protocol1: 3,
+        }
+    );
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        pool.clone(),
+        &set_fee_protocol(4, 10),
+        &[],
+    )
+    .unwrap();
+    let slot0: Slot0 = app
+        .wrap()
+        .query_wasm_smart(&pool, &PoolQueryMsg::Slot0 {})
+        .unwrap();
+    assert_eq!(slot0.fee_protocol, 4 + (10 << 4));
+
+    // a quarter of the 300 uatom swap fee goes to the protocol
+    app.execute_contract(
+        Addr::unchecked("trader"),
+        pool.clone(),
+        &PoolExecuteMsg::Swap {
+            recipient: "trader".to_string(),
+            zero_for_one: true,
+            amount_specified: Int256::from(100_000i128),
+            sqrt_price_limit_x96: MIN_SQRT_RATIO + Uint256::one(),
+            data: Default::default(),
+        },
+        &coins(100_000, "uatom"),
+    )
+    .unwrap();
+    let protocol_fees: ProtocolFees = app
+        .wrap()
+        .query_wasm_smart(&pool, &PoolQueryMsg::ProtocolFees {})
+        .unwrap();
+    assert_eq!(protocol_fees.token0, Uint128::new(75));
+    assert_eq!(protocol_fees.token1, Uint128::zero());
+
+    let collect_protocol = PoolExecuteMsg::CollectProtocol {
+        recipient: "treasury".to_string(),
+        amount0_requested: Uint128::MAX,
+        amount1_requested: Uint128::MAX,
+    };
+    let err = app
+        .execute_contract(
+            Addr::unchecked("anyone"),
+            pool.clone(),
+            &collect_protocol,
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(*pool_error(&err), PoolContractError::Unauthorized {});
+    app.execute_contract(Addr::unchecked(OWNER), pool.clone(), &collect_protocol, &[])
+        .unwrap();
+    assert_eq!(
+        app.wrap()
+            .query_balance("treasury", "uatom")
+            .unwrap()
+            .amount,
+        Uint128::new(75)
+    );
+    let protocol_fees: ProtocolFees = app
+        .wrap()
+        .query_wasm_smart(&pool, &PoolQueryMsg::ProtocolFees {})
+        .unwrap();
+    assert_eq!(protocol_fees, ProtocolFees::default());
+
+    // the pools follow the factory's ownership
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory,
+        &ExecuteMsg::SetOwner {
+            owner: "new_owner".to_string(),
+        },
+        &[],
+    )
+    .unwrap();
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            pool.clone(),
+            &set_fee_protocol(0, 0),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(*pool_error(&err), PoolContractError::Unauthorized {});
+    app.execute_contract(
+        Addr::unchecked("new_owner"),
+        pool,
+        &set_fee_protocol(0, 0),
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn pool_salt_is_unique_per_pool() {
+    assert_ne!(
+        pool_salt("uatom", "uxion", 500),
+        pool_salt("uatom", "uxion", 3000)
+    );
+    // the separator keeps concatenations from colliding
+    assert_ne!(
+        pool_salt("ua", "tomuxion", 500),
+        pool_salt("uatom", "uxion", 500)
+    );
+    assert!(pool_salt("factory/xion1abc/a", "factory/xion1abc/b", 500).len() <= 64);
+}
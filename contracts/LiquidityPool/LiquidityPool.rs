@@ -1,198 +0,0 @@
-// SPDX-License-Identifier: BUSL-1.1
-#![cfg_attr(not(feature = "std"), no_std)]
-
-use cosmwasm_std::{
-    Addr, Api, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
-};
-use cw_storage_plus::{Item, Map};
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
-// Define your contract state here using storage items
-pub struct OrderBook {
-    pub orders: Map<Storage, String, Order>,
-    // Define other state items as needed
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Order {
-    pub id: String,
-    pub trader: Addr,
-    pub token: String,
-    pub amount: u128,
-    pub price: u128,
-    pub order_type: OrderType,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub enum OrderType {
-    Buy,
-    Sell,
-}
-
-impl OrderBook {
-    pub fn new(storage: &mut dyn Storage) -> Self {
-        Self {
-            orders: Map::new(storage, "orders"),
-            // Initialize other state items as needed
-        }
-    }
-
-    pub fn handle(&mut self, _deps: DepsMut, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Handle different message types (e.g., AddOrder, MatchOrders)
-        // Implement your contract's business logic here
-        unimplemented!()
-    }
-
-    pub fn init(&mut self, _deps: DepsMut, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Initialize the contract state if necessary
-        Ok(Response::default())
-    }
-
-    pub fn query_orders(&self, _deps: Deps, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Query orders from the order book
-        // Return orders as JSON or other format
-        unimplemented!()
-    }
-
-    pub fn query_trades(&self, _deps: Deps, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Query executed trades
-        // Return trades as JSON or other format
-        unimplemented!()
-    }
-}
-
-// Example of how to test the contract's functionality
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-
-    #[test]
-    fn test_init_order_book() {
-        let mut deps = mock_dependencies(&[]);
-        let env = mock_env();
-        let info = mock_info("creator", &[]);
-        
-        // Initialize the contract
-        let mut order_book = OrderBook::new(&mut deps.storage);
-        let res = order_book.init(deps.as_mut(), env.clone(), info.clone());
-        assert!(res.is_ok());
-        
-        // Verify the contract state after initialization if needed
-        // Example: Check if there are no orders in the order book initially
-        // let state = config(deps.as_ref()).load().expect("load state");
-        // assert_eq!(state.orders.len(), 0);
-    }
-
-    #[test]
-    fn test_add_order() {
-        let mut deps = mock_dependencies(&[]);
-        let env = mock_env();
-        let info = mock_info("trader1", &[]); // Mock info of trader1
-        
-        // Initialize the contract (if not already initialized)
-        let mut order_book = OrderBook::new(&mut deps.storage);
-        let _res = order_book.init(deps.as_mut(), env.clone(), info.clone());
-
-        // Add an order to the order book
-        let order_id = "order1".to_string();
-        let trader = "trader1".to_string();
-        let token = "token".to_string();
-        let amount = 100;
-        let price = 10;
-        let order_type = OrderType::Buy; // Example: Buy order
-
-        let res = order_book.handle(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            HandleMsg::AddOrder {
-                order_id: order_id.clone(),
-                trader: trader.clone(),
-                token: token.clone(),
-                amount: amount.clone(),
-                price: price.clone(),
-                order_type: order_type.clone(),
-            }
-        );
-
-        assert!(res.is_ok());
-
-        // Retrieve and verify the order from the order book if needed
-        // Example: Check if the order was added correctly
-        // let orders = query_orders(deps.as_ref()).expect("load orders");
-        // assert_eq!(orders.len(), 1);
-        // assert_eq!(orders[0].id, order_id);
-    }
-
-    #[test]
-    fn test_match_orders() {
-        let mut deps = mock_dependencies(&[]);
-        let env = mock_env();
-        let info = mock_info("matcher", &[]); // Mock info of matcher
-        
-        // Initialize the contract (if not already initialized)
-        let mut order_book = OrderBook::new(&mut deps.storage);
-        let _res = order_book.init(deps.as_mut(), env.clone(), info.clone());
-
-        // Add buy and sell orders to the order book
-        let trader1 = "trader1".to_string();
-        let trader2 = "trader2".to_string();
-        let token = "token".to_string();
-        let amount1 = 100;
-        let amount2 = 80;
-        let price = 10;
-
-        // Add a buy order
-        let res1 = order_book.handle(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            HandleMsg::AddOrder {
-                order_id: "order1".to_string(),
-                trader: trader1.clone(),
-                token: token.clone(),
-                amount: amount1.clone(),
-                price: price.clone(),
-                order_type: OrderType::Buy,
-            }
-        );
-
-        assert!(res1.is_ok());
-
-        // Add a sell order
-        let res2 = order_book.handle(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            HandleMsg::AddOrder {
-                order_id: "order2".to_string(),
-                trader: trader2.clone(),
-                token: token.clone(),
-                amount: amount2.clone(),
-                price: price.clone(),
-                order_type: OrderType::Sell,
-            }
-        );
-
-        assert!(res2.is_ok());
-
-        // Match buy and sell orders
-        let res3 = order_book.handle(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            HandleMsg::MatchOrders {
-                token: token.clone(),
-            }
-        );
-
-        assert!(res3.is_ok());
-
-        // Verify the state after matching orders if needed
-        // Example: Check if the trades were executed correctly
-        // let trades = query_trades(deps.as_ref()).expect("load trades");
-        // assert_eq!(trades.len(), expected_trades_count);
-    }
-}
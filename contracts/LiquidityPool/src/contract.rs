@@ -0,0 +1,1642 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Concentrated liquidity pool
+//! A pool holds two native tokens and lets liquidity providers concentrate their liquidity within a price range
+//! bounded by two ticks. Swaps walk the price across initialized ticks, charging the pool fee on the way.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, Int256, MessageInfo, QuerierWrapper,
+    Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, Uint256, WasmMsg,
+};
+use interfaces::callback::flash::XionFlashCallbackMsg;
+use interfaces::callback::mint::XionMintCallbackMsg;
+use interfaces::callback::swap::XionSwapCallbackMsg;
+use interfaces::factory::query_factory_owner;
+use interfaces::pool::events::{
+    BurnEvent, CollectEvent, CollectProtocolEvent, FlashEvent,
+    IncreaseObservationCardinalityNextEvent, InitializeEvent, MintEvent, SetFeeProtocolEvent,
+    SwapEvent,
+};
+use interfaces::pool::{IXionPoolActions, IXionPoolEvent, IXionPoolOwnerActions};
+use libraries::fixed_point_128::FixedPoint128;
+use libraries::full_math::FullMath;
+use libraries::liquidity_math::add_delta;
+use libraries::oracle;
+use libraries::position::{self, PositionInfo};
+use libraries::safe_cast::{to_i256, to_u256};
+use libraries::sqrt_price_math::{get_amount0_delta_signed, get_amount1_delta_signed};
+use libraries::swap_math::compute_swap_step;
+use libraries::tick::{get_fee_growth_inside, tick_spacing_to_max_liquidity_per_tick};
+use libraries::tick_math::{
+    get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO,
+    MIN_TICK,
+};
+use libraries::transfer_helper::{safe_transfer, AssetInfo};
+
+use crate::error::ContractError;
+use crate::msg::{
+    ArithmeticMeanTickResponse, ExecuteMsg, FeeGrowthGlobalResponse, InstantiateMsg,
+    ObserveResponse, QueryMsg, SnapshotCumulativesInsideResponse,
+};
+use crate::quoter;
+use crate::state::{
+    load_observation, Config, FlashState, PaymentState, ProtocolFees, Slot0, CONFIG,
+    FEE_GROWTH_GLOBAL0_X128, FEE_GROWTH_GLOBAL1_X128, FLASH, LIQUIDITY, OBSERVATIONS, PAYMENT,
+    POSITIONS, PROTOCOL_FEES, SLOT0, TICKS, TICK_BITMAP,
+};
+
+/// Fees are expressed in hundredths of a bip
+const FEE_DENOMINATOR: u32 = 1_000_000;
+/// The largest tick spacing a pool may use, keeping tick_spacing_to_max_liquidity_per_tick meaningful
+const MAX_TICK_SPACING: i32 = 16384;
+
+/// The most tick bitmap words a swap step searches for the next initialized tick
+/// Empty words are skipped in a single storage read, so this only bounds how far one step may move the price
+pub(crate) const MAX_BITMAP_WORDS_PER_STEP: u16 = 16;
+
+/// The most observation slots a single call may pre-write
+const MAX_OBSERVATION_CARDINALITY_GROWTH: u16 = 1000;
+
+pub const FLASH_REPLY_ID: u64 = 1;
+pub const MINT_CALLBACK_REPLY_ID: u64 = 2;
+pub const SWAP_CALLBACK_REPLY_ID: u64 = 3;
+
+/// The pool contract; its `IXionPoolActions` implementation backs the execute messages
+pub struct XionPool;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    if msg.token0 >= msg.token1 || msg.token0.is_empty() {
+        return Err(ContractError::InvalidTokenPair {});
+    }
+    if msg.fee >= FEE_DENOMINATOR {
+        return Err(ContractError::InvalidFee { fee: msg.fee });
+    }
+    if msg.tick_spacing <= 0 || msg.tick_spacing >= MAX_TICK_SPACING {
+        return Err(ContractError::InvalidTickSpacing {
+            tick_spacing: msg.tick_spacing,
+        });
+    }
+
+    let config = Config {
+        factory: info.sender,
+        token0: msg.token0,
+        token1: msg.token1,
+        fee: msg.fee,
+        tick_spacing: msg.tick_spacing,
+        max_liquidity_per_tick: Uint128::new(tick_spacing_to_max_liquidity_per_tick(
+            msg.tick_spacing,
+        )),
+    };
+    CONFIG.save(deps.storage, &config)?;
+    FEE_GROWTH_GLOBAL0_X128.save(deps.storage, &Uint256::zero())?;
+    FEE_GROWTH_GLOBAL1_X128.save(deps.storage, &Uint256::zero())?;
+    PROTOCOL_FEES.save(deps.storage, &ProtocolFees::default())?;
+    LIQUIDITY.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("factory", config.factory)
+        .add_attribute("token0", config.token0)
+        .add_attribute("token1", config.token1)
+        .add_attribute("fee", config.fee.to_string())
+        .add_attribute("tick_spacing", config.tick_spacing.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let pool = XionPool;
+    match msg {
+        ExecuteMsg::Initialize { sqrt_price_x96 } => {
+            pool.initialize(deps, env, info, sqrt_price_x96)
+        }
+        ExecuteMsg::Mint {
+            recipient,
+            tick_lower,
+            tick_upper,
+            amount,
+            data,
+        } => pool.mint(
+            deps, env, info, recipient, tick_lower, tick_upper, amount, data,
+        ),
+        ExecuteMsg::Collect {
+            recipient,
+            tick_lower,
+            tick_upper,
+            amount0_requested,
+            amount1_requested,
+        } => pool.collect(
+            deps,
+            env,
+            info,
+            recipient,
+            tick_lower,
+            tick_upper,
+            amount0_requested,
+            amount1_requested,
+        ),
+        ExecuteMsg::Burn {
+            tick_lower,
+            tick_upper,
+            amount,
+        } => pool.burn(deps, env, info, tick_lower, tick_upper, amount),
+        ExecuteMsg::Swap {
+            recipient,
+            zero_for_one,
+            amount_specified,
+            sqrt_price_limit_x96,
+            data,
+        } => pool.swap(
+            deps,
+            env,
+            info,
+            recipient,
+            zero_for_one,
+            amount_specified,
+            sqrt_price_limit_x96,
+            data,
+        ),
+        ExecuteMsg::Flash {
+            recipient,
+            amount0,
+            amount1,
+            data,
+        } => pool.flash(deps, env, info, recipient, amount0, amount1, data),
+        ExecuteMsg::IncreaseObservationCardinalityNext {
+            observation_cardinality_next,
+        } => pool.increase_observation_cardinality_next(
+            deps,
+            env,
+            info,
+            observation_cardinality_next,
+        ),
+        ExecuteMsg::SetFeeProtocol {
+            fee_protocol0,
+            fee_protocol1,
+        } => pool.set_fee_protocol(deps, env, info, fee_protocol0, fee_protocol1),
+        ExecuteMsg::CollectProtocol {
+            recipient,
+            amount0_requested,
+            amount1_requested,
+        } => pool.collect_protocol(
+            deps,
+            env,
+            info,
+            recipient,
+            amount0_requested,
+            amount1_requested,
+        ),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        FLASH_REPLY_ID => settle_flash(deps, env),
+        MINT_CALLBACK_REPLY_ID | SWAP_CALLBACK_REPLY_ID => settle_payment(deps, env),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Slot0 {} => to_binary(&SLOT0.load(deps.storage)?),
+        QueryMsg::Liquidity {} => to_binary(&LIQUIDITY.load(deps.storage)?),
+        QueryMsg::FeeGrowthGlobal {} => to_binary(&FeeGrowthGlobalResponse {
+            fee_growth_global0_x128: FEE_GROWTH_GLOBAL0_X128.load(deps.storage)?,
+            fee_growth_global1_x128: FEE_GROWTH_GLOBAL1_X128.load(deps.storage)?,
+        }),
+        QueryMsg::ProtocolFees {} => to_binary(&PROTOCOL_FEES.load(deps.storage)?),
+        QueryMsg::Tick { tick } => to_binary(&TICKS.info(deps.storage, tick)?),
+        QueryMsg::TickBitmap { word_position } => {
+            to_binary(&TICK_BITMAP.word(deps.storage, word_position)?)
+        }
+        QueryMsg::Position {
+            owner,
+            tick_lower,
+            tick_upper,
+        } => {
+            let owner = deps.api.addr_validate(&owner)?;
+            to_binary(
+                &POSITIONS
+                    .may_load(deps.storage, (&owner, tick_lower, tick_upper))?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::Observation { index } => to_binary(&load_observation(deps.storage, index)?),
+        QueryMsg::Observe { seconds_agos } => {
+            let (tick_cumulatives, seconds_per_liquidity_cumulative_x128s) =
+                observe(deps, &env, &seconds_agos)?;
+            to_binary(&ObserveResponse {
+                tick_cumulatives,
+                seconds_per_liquidity_cumulative_x128s,
+            })
+        }
+        QueryMsg::ArithmeticMeanTick { window } => {
+            to_binary(&query_arithmetic_mean_tick(deps, &env, window)?)
+        }
+        QueryMsg::SnapshotCumulativesInside {
+            tick_lower,
+            tick_upper,
+        } => to_binary(
+            &snapshot_cumulatives_inside(deps, &env, tick_lower, tick_upper)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+        QueryMsg::QuoteExactInput {
+            zero_for_one,
+            amount_in,
+            sqrt_price_limit_x96,
+        } => to_binary(
+            &quoter::quote(
+                deps.storage,
+                zero_for_one,
+                to_i256(amount_in.into()),
+                sqrt_price_limit_x96,
+            )
+            .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+        QueryMsg::QuoteExactOutput {
+            zero_for_one,
+            amount_out,
+            sqrt_price_limit_x96,
+        } => to_binary(
+            &quoter::quote(
+                deps.storage,
+                zero_for_one,
+                -to_i256(amount_out.into()),
+                sqrt_price_limit_x96,
+            )
+            .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+    }
+}
+
+/// Returns the cumulative tick and liquidity as of each timestamp `seconds_agos` from the current block time
+fn observe(deps: Deps, env: &Env, seconds_agos: &[u32]) -> StdResult<(Vec<i64>, Vec<Uint256>)> {
+    let slot0 = SLOT0.load(deps.storage)?;
+    let liquidity = LIQUIDITY.load(deps.storage)?;
+    oracle::observe(
+        &|index| load_observation(deps.storage, index),
+        block_time(env),
+        seconds_agos,
+        slot0.tick,
+        slot0.observation_index,
+        liquidity.u128(),
+        slot0.observation_cardinality,
+    )
+}
+
+/// Returns the tick cumulative, seconds per liquidity and seconds spent inside [tick_lower, tick_upper)
+/// Both ticks must be initialized, i.e. referenced by some position
+fn snapshot_cumulatives_inside(
+    deps: Deps,
+    env: &Env,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<SnapshotCumulativesInsideResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_ticks(tick_lower, tick_upper, config.tick_spacing)?;
+
+    let lower = TICKS.load_initialized(deps.storage, tick_lower)?;
+    let upper = TICKS.load_initialized(deps.storage, tick_upper)?;
+
+    let slot0 = SLOT0.load(deps.storage)?;
+    let (tick_cumulative_inside, seconds_per_liquidity_inside_x128, seconds_inside) =
+        if slot0.tick < tick_lower {
+            (
+                lower
+                    .tick_cumulative_outside
+                    .wrapping_sub(upper.tick_cumulative_outside),
+                lower
+                    .seconds_per_liquidity_outside_x128
+                    .wrapping_sub(upper.seconds_per_liquidity_outside_x128),
+                lower.seconds_outside.wrapping_sub(upper.seconds_outside),
+            )
+        } else if slot0.tick < tick_upper {
+            let time = block_time(env);
+            let liquidity = LIQUIDITY.load(deps.storage)?.u128();
+            let (tick_cumulative, seconds_per_liquidity_cumulative_x128) =
+                observe_latest(deps.storage, &slot0, time, liquidity)?;
+            (
+                tick_cumulative
+                    .wrapping_sub(lower.tick_cumulative_outside)
+                    .wrapping_sub(upper.tick_cumulative_outside),
+                seconds_per_liquidity_cumulative_x128
+                    .wrapping_sub(lower.seconds_per_liquidity_outside_x128)
+                    .wrapping_sub(upper.seconds_per_liquidity_outside_x128),
+                time.wrapping_sub(lower.seconds_outside)
+                    .wrapping_sub(upper.seconds_outside),
+            )
+        } else {
+            (
+                upper
+                    .tick_cumulative_outside
+                    .wrapping_sub(lower.tick_cumulative_outside),
+                upper
+                    .seconds_per_liquidity_outside_x128
+                    .wrapping_sub(lower.seconds_per_liquidity_outside_x128),
+                upper.seconds_outside.wrapping_sub(lower.seconds_outside),
+            )
+        };
+
+    Ok(SnapshotCumulativesInsideResponse {
+        tick_cumulative_inside,
+        seconds_per_liquidity_inside_x128,
+        seconds_inside,
+    })
+}
+
+/// The time weighted average tick over the last `window` seconds, rounded towards negative infinity
+fn query_arithmetic_mean_tick(
+    deps: Deps,
+    env: &Env,
+    window: u32,
+) -> StdResult<ArithmeticMeanTickResponse> {
+    if window == 0 {
+        return Err(StdError::generic_err("window must be greater than zero"));
+    }
+    let (tick_cumulatives, _) = observe(deps, env, &[window, 0])?;
+    let tick_cumulatives_delta = tick_cumulatives[1] - tick_cumulatives[0];
+
+    let mut arithmetic_mean_tick = tick_cumulatives_delta / window as i64;
+    // always round to negative infinity
+    if tick_cumulatives_delta < 0 && tick_cumulatives_delta % window as i64 != 0 {
+        arithmetic_mean_tick -= 1;
+    }
+
+    Ok(ArithmeticMeanTickResponse {
+        arithmetic_mean_tick: arithmetic_mean_tick as i32,
+    })
+}
+
+impl IXionPoolActions for XionPool {
+    type Error = ContractError;
+
+    fn initialize(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        _info: MessageInfo,
+        sqrt_price_x96: Uint256,
+    ) -> Result<Response, ContractError> {
+        if SLOT0.may_load(deps.storage)?.is_some() {
+            return Err(ContractError::AlreadyInitialized {});
+        }
+        if sqrt_price_x96 < MIN_SQRT_RATIO || sqrt_price_x96 >= MAX_SQRT_RATIO {
+            return Err(ContractError::InvalidSqrtPrice {});
+        }
+
+        let tick = get_tick_at_sqrt_ratio(sqrt_price_x96);
+        let (observation, cardinality, cardinality_next) = oracle::initialize(block_time(&env));
+        OBSERVATIONS.save(deps.storage, 0, &observation)?;
+        SLOT0.save(
+            deps.storage,
+            &Slot0 {
+                sqrt_price_x96,
+                tick,
+                observation_index: 0,
+                observation_cardinality: cardinality,
+                observation_cardinality_next: cardinality_next,
+                fee_protocol: 0,
+                unlocked: true,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "initialize")
+            .add_event(
+                InitializeEvent {
+                    sqrt_price_x96,
+                    tick,
+                }
+                .to_event(),
+            ))
+    }
+
+    fn mint(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: Uint128,
+        data: Binary,
+    ) -> Result<Response, ContractError> {
+        if amount.is_zero() {
+            return Err(ContractError::ZeroAmount {});
+        }
+        load_unlocked_slot0(deps.storage)?;
+        let config = CONFIG.load(deps.storage)?;
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        let (_, amount0, amount1) = modify_position(
+            deps.storage,
+            &env,
+            &config,
+            &recipient,
+            tick_lower,
+            tick_upper,
+            liquidity_delta(amount, false)?,
+        )?;
+        let amount0 = amount_owed(amount0)?;
+        let amount1 = amount_owed(amount1)?;
+        let payment = request_payment(
+            deps.storage,
+            &deps.querier,
+            &env,
+            &config,
+            &info,
+            amount0,
+            amount1,
+            data,
+            |data| {
+                to_binary(&XionMintCallbackMsg::XionMintCallback {
+                    amount0_owed: Uint256::from(amount0),
+                    amount1_owed: Uint256::from(amount1),
+                    data,
+                })
+            },
+            MINT_CALLBACK_REPLY_ID,
+        )?;
+
+        Ok(Response::new()
+            .add_submessages(payment)
+            .add_attribute("action", "mint")
+            .add_event(
+                MintEvent {
+                    sender: info.sender.into(),
+                    owner: recipient.into(),
+                    tick_lower,
+                    tick_upper,
+                    amount,
+                    amount0,
+                    amount1,
+                }
+                .to_event(),
+            ))
+    }
+
+    fn collect(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        recipient: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount0_requested: Uint128,
+        amount1_requested: Uint128,
+    ) -> Result<Response, ContractError> {
+        load_unlocked_slot0(deps.storage)?;
+        let config = CONFIG.load(deps.storage)?;
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        // we don't need to check_ticks here, because invalid positions will never have non-zero tokens_owed
+        let key = (&info.sender, tick_lower, tick_upper);
+        let mut amount0 = Uint128::zero();
+        let mut amount1 = Uint128::zero();
+        if let Some(mut position) = POSITIONS.may_load(deps.storage, key)? {
+            amount0 = amount0_requested.min(position.tokens_owed0);
+            amount1 = amount1_requested.min(position.tokens_owed1);
+            position.tokens_owed0 -= amount0;
+            position.tokens_owed1 -= amount1;
+            POSITIONS.save(deps.storage, key, &position)?;
+        }
+
+        Ok(Response::new()
+            .add_messages(transfer(&recipient, &config.token0, amount0)?)
+            .add_messages(transfer(&recipient, &config.token1, amount1)?)
+            .add_attribute("action", "collect")
+            .add_event(
+                CollectEvent {
+                    owner: info.sender.into(),
+                    recipient: recipient.into(),
+                    tick_lower,
+                    tick_upper,
+                    amount0,
+                    amount1,
+                }
+                .to_event(),
+            ))
+    }
+
+    fn burn(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: Uint128,
+    ) -> Result<Response, ContractError> {
+        load_unlocked_slot0(deps.storage)?;
+        let config = CONFIG.load(deps.storage)?;
+
+        let (mut position, amount0, amount1) = modify_position(
+            deps.storage,
+            &env,
+            &config,
+            &info.sender,
+            tick_lower,
+            tick_upper,
+            liquidity_delta(amount, true)?,
+        )?;
+        let amount0 = amount_paid(amount0)?;
+        let amount1 = amount_paid(amount1)?;
+
+        if !amount0.is_zero() || !amount1.is_zero() {
+            position.tokens_owed0 =
+                Uint128::new(position.tokens_owed0.u128().wrapping_add(amount0.u128()));
+            position.tokens_owed1 =
+                Uint128::new(position.tokens_owed1.u128().wrapping_add(amount1.u128()));
+            POSITIONS.save(
+                deps.storage,
+                (&info.sender, tick_lower, tick_upper),
+                &position,
+            )?;
+        }
+
+        Ok(Response::new().add_attribute("action", "burn").add_event(
+            BurnEvent {
+                owner: info.sender.into(),
+                tick_lower,
+                tick_upper,
+                amount,
+                amount0,
+                amount1,
+            }
+            .to_event(),
+        ))
+    }
+
+    fn swap(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        zero_for_one: bool,
+        amount_specified: Int256,
+        sqrt_price_limit_x96: Uint256,
+        data: Binary,
+    ) -> Result<Response, ContractError> {
+        if amount_specified.is_zero() {
+            return Err(ContractError::AmountSpecifiedZero {});
+        }
+        let slot0_start = load_unlocked_slot0(deps.storage)?;
+        let config = CONFIG.load(deps.storage)?;
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        check_sqrt_price_limit(
+            zero_for_one,
+            slot0_start.sqrt_price_x96,
+            sqrt_price_limit_x96,
+        )?;
+
+        let time = block_time(&env);
+        let liquidity_start = LIQUIDITY.load(deps.storage)?.u128();
+        let fee_growth_global0_x128 = FEE_GROWTH_GLOBAL0_X128.load(deps.storage)?;
+        let fee_growth_global1_x128 = FEE_GROWTH_GLOBAL1_X128.load(deps.storage)?;
+
+        let exact_input = amount_specified > Int256::zero();
+
+        // the top level state of the swap, the results of which are recorded in storage at the end
+        let SwapState {
+            amount_specified_remaining,
+            amount_calculated,
+            sqrt_price_x96,
+            tick,
+            fee_growth_global_x128,
+            protocol_fee,
+            liquidity,
+            crossed_ticks,
+            ..
+        } = compute_swap(
+            deps.storage,
+            &config,
+            &slot0_start,
+            liquidity_start,
+            if zero_for_one {
+                fee_growth_global0_x128
+            } else {
+                fee_growth_global1_x128
+            },
+            zero_for_one,
+            amount_specified,
+            sqrt_price_limit_x96,
+        )?;
+
+        // run the tick transitions, with the oracle values at the start of the swap
+        if !crossed_ticks.is_empty() {
+            let (tick_cumulative, seconds_per_liquidity_x128) =
+                observe_latest(deps.storage, &slot0_start, time, liquidity_start)?;
+            for (tick_crossed, fee_growth_crossed_x128) in crossed_ticks {
+                TICKS.cross(
+                    deps.storage,
+                    tick_crossed,
+                    if zero_for_one {
+                        fee_growth_crossed_x128
+                    } else {
+                        fee_growth_global0_x128
+                    },
+                    if zero_for_one {
+                        fee_growth_global1_x128
+                    } else {
+                        fee_growth_crossed_x128
+                    },
+                    seconds_per_liquidity_x128,
+                    tick_cumulative,
+                    time,
+                )?;
+            }
+        }
+
+        // write an oracle entry for the tick the swap started at, then update the price and tick
+        let mut slot0 = slot0_start.clone();
+        write_observation(deps.storage, &mut slot0, time, liquidity_start)?;
+        slot0.tick = tick;
+        slot0.sqrt_price_x96 = sqrt_price_x96;
+        SLOT0.save(deps.storage, &slot0)?;
+
+        // update liquidity if it changed
+        if liquidity != liquidity_start {
+            LIQUIDITY.save(deps.storage, &Uint128::new(liquidity))?;
+        }
+
+        // update fee growth global and, if necessary, protocol fees
+        // overflow is acceptable, protocol has to withdraw before it hits u128::MAX fees
+        let mut protocol_fees = PROTOCOL_FEES.load(deps.storage)?;
+        if zero_for_one {
+            FEE_GROWTH_GLOBAL0_X128.save(deps.storage, &fee_growth_global_x128)?;
+            protocol_fees.token0 = Uint128::new(
+                protocol_fees
+                    .token0
+                    .u128()
+                    .wrapping_add(protocol_fee.u128()),
+            );
+        } else {
+            FEE_GROWTH_GLOBAL1_X128.save(deps.storage, &fee_growth_global_x128)?;
+            protocol_fees.token1 = Uint128::new(
+                protocol_fees
+                    .token1
+                    .u128()
+                    .wrapping_add(protocol_fee.u128()),
+            );
+        }
+        if !protocol_fee.is_zero() {
+            PROTOCOL_FEES.save(deps.storage, &protocol_fees)?;
+        }
+
+        let (amount0, amount1) = if zero_for_one == exact_input {
+            (
+                amount_specified - amount_specified_remaining,
+                amount_calculated,
+            )
+        } else {
+            (
+                amount_calculated,
+                amount_specified - amount_specified_remaining,
+            )
+        };
+
+        // do the transfers and collect payment
+        let (out_denom, amount_out, amount_in) = if zero_for_one {
+            (&config.token1, amount_paid(amount1)?, amount_owed(amount0)?)
+        } else {
+            (&config.token0, amount_paid(amount0)?, amount_owed(amount1)?)
+        };
+        let (owed0, owed1) = if zero_for_one {
+            (amount_in, Uint128::zero())
+        } else {
+            (Uint128::zero(), amount_in)
+        };
+        let payment = request_payment(
+            deps.storage,
+            &deps.querier,
+            &env,
+            &config,
+            &info,
+            owed0,
+            owed1,
+            data,
+            |data| {
+                to_binary(&XionSwapCallbackMsg::XionSwapCallback {
+                    amount0_delta: amount0,
+                    amount1_delta: amount1,
+                    data,
+                })
+            },
+            SWAP_CALLBACK_REPLY_ID,
+        )?;
+
+        Ok(Response::new()
+            .add_messages(transfer(&recipient, out_denom, amount_out)?)
+            .add_submessages(payment)
+            .add_attribute("action", "swap")
+            .add_event(
+                SwapEvent {
+                    sender: info.sender.into(),
+                    recipient: recipient.into(),
+                    amount0,
+                    amount1,
+                    sqrt_price_x96,
+                    liquidity: Uint128::new(liquidity),
+                    tick,
+                }
+                .to_event(),
+            ))
+    }
+
+    fn flash(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount0: Uint128,
+        amount1: Uint128,
+        data: Binary,
+    ) -> Result<Response, ContractError> {
+        let mut slot0 = load_unlocked_slot0(deps.storage)?;
+        let config = CONFIG.load(deps.storage)?;
+        if LIQUIDITY.load(deps.storage)?.is_zero() {
+            return Err(ContractError::NoLiquidity {});
+        }
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        let fee0 = flash_fee(amount0, config.fee)?;
+        let fee1 = flash_fee(amount1, config.fee)?;
+        let balance0_before = deps
+            .querier
+            .query_balance(&env.contract.address, &config.token0)?
+            .amount;
+        let balance1_before = deps
+            .querier
+            .query_balance(&env.contract.address, &config.token1)?
+            .amount;
+
+        FLASH.save(
+            deps.storage,
+            &FlashState {
+                sender: info.sender.clone(),
+                recipient: recipient.clone(),
+                amount0,
+                amount1,
+                fee0,
+                fee1,
+                balance0_before,
+                balance1_before,
+            },
+        )?;
+        // the pool stays locked until the loan is settled in the reply
+        slot0.unlocked = false;
+        SLOT0.save(deps.storage, &slot0)?;
+
+        let callback = WasmMsg::Execute {
+            contract_addr: recipient.to_string(),
+            msg: to_binary(&XionFlashCallbackMsg::XionFlashCallback {
+                fee0: Uint256::from(fee0),
+                fee1: Uint256::from(fee1),
+                data,
+            })?,
+            funds: vec![],
+        };
+
+        Ok(Response::new()
+            .add_messages(transfer(&recipient, &config.token0, amount0)?)
+            .add_messages(transfer(&recipient, &config.token1, amount1)?)
+            .add_submessage(SubMsg::reply_on_success(callback, FLASH_REPLY_ID))
+            .add_attribute("action", "flash"))
+    }
+
+    fn increase_observation_cardinality_next(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        observation_cardinality_next: u16,
+    ) -> Result<Response, ContractError> {
+        let mut slot0 = load_unlocked_slot0(deps.storage)?;
+        let old = slot0.observation_cardinality_next;
+        if observation_cardinality_next.saturating_sub(old) > MAX_OBSERVATION_CARDINALITY_GROWTH {
+            return Err(ContractError::ObservationCardinalityGrowthTooLarge {
+                max: MAX_OBSERVATION_CARDINALITY_GROWTH,
+            });
+        }
+
+        let new = oracle::grow(old, observation_cardinality_next, |index, observation| {
+            OBSERVATIONS.save(deps.storage, index, observation)
+        })?;
+        if new != old {
+            slot0.observation_cardinality_next = new;
+            SLOT0.save(deps.storage, &slot0)?;
+        }
+
+        let mut response =
+            Response::new().add_attribute("action", "increase_observation_cardinality_next");
+        if new != old {
+            response = response.add_event(
+                IncreaseObservationCardinalityNextEvent {
+                    observation_cardinality_next_old: old,
+                    observation_cardinality_next_new: new,
+                }
+                .to_event(),
+            );
+        }
+        Ok(response)
+    }
+}
+
+impl IXionPoolOwnerActions for XionPool {
+    type Error = ContractError;
+
+    fn set_fee_protocol(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        fee_protocol0: u8,
+        fee_protocol1: u8,
+    ) -> Result<Response, ContractError> {
+        let mut slot0 = load_unlocked_slot0(deps.storage)?;
+        check_factory_owner(deps.as_ref(), &info)?;
+        let valid = |fee_protocol: u8| fee_protocol == 0 || (4..=10).contains(&fee_protocol);
+        if !valid(fee_protocol0) || !valid(fee_protocol1) {
+            return Err(ContractError::InvalidFeeProtocol {
+                fee_protocol0,
+                fee_protocol1,
+            });
+        }
+
+        let fee_protocol_old = slot0.fee_protocol;
+        slot0.fee_protocol = fee_protocol0 + (fee_protocol1 << 4);
+        SLOT0.save(deps.storage, &slot0)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_fee_protocol")
+            .add_event(
+                SetFeeProtocolEvent {
+                    fee_protocol0_old: fee_protocol_old % 16,
+                    fee_protocol1_old: fee_protocol_old >> 4,
+                    fee_protocol0_new: fee_protocol0,
+                    fee_protocol1_new: fee_protocol1,
+                }
+                .to_event(),
+            ))
+    }
+
+    fn collect_protocol(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount0_requested: Uint128,
+        amount1_requested: Uint128,
+    ) -> Result<Response, ContractError> {
+        load_unlocked_slot0(deps.storage)?;
+        check_factory_owner(deps.as_ref(), &info)?;
+        let config = CONFIG.load(deps.storage)?;
+        let recipient = deps.api.addr_validate(&recipient)?;
+
+        let mut protocol_fees = PROTOCOL_FEES.load(deps.storage)?;
+        let amount0 = amount0_requested.min(protocol_fees.token0);
+        let amount1 = amount1_requested.min(protocol_fees.token1);
+        protocol_fees.token0 -= amount0;
+        protocol_fees.token1 -= amount1;
+        PROTOCOL_FEES.save(deps.storage, &protocol_fees)?;
+
+        Ok(Response::new()
+            .add_messages(transfer(&recipient, &config.token0, amount0)?)
+            .add_messages(transfer(&recipient, &config.token1, amount1)?)
+            .add_attribute("action", "collect_protocol")
+            .add_event(
+                CollectProtocolEvent {
+                    sender: info.sender.into(),
+                    recipient: recipient.into(),
+                    amount0,
+                    amount1,
+                }
+                .to_event(),
+            ))
+    }
+}
+
+/// Checks that the borrower of a flash loan paid it back with the fee and credits the fee to liquidity providers
+fn settle_flash(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let flash = FLASH.load(deps.storage)?;
+    FLASH.remove(deps.storage);
+    let config = CONFIG.load(deps.storage)?;
+    let mut slot0 = SLOT0.load(deps.storage)?;
+    let liquidity = LIQUIDITY.load(deps.storage)?;
+
+    let balance0_after = deps
+        .querier
+        .query_balance(&env.contract.address, &config.token0)?
+        .amount;
+    let balance1_after = deps
+        .querier
+        .query_balance(&env.contract.address, &config.token1)?
+        .amount;
+
+    if flash.balance0_before.checked_add(flash.fee0)? > balance0_after {
+        return Err(ContractError::FlashNotRepaid {
+            denom: config.token0,
+        });
+    }
+    if flash.balance1_before.checked_add(flash.fee1)? > balance1_after {
+        return Err(ContractError::FlashNotRepaid {
+            denom: config.token1,
+        });
+    }
+
+    // sub is safe because we know balance_after is gt balance_before by at least fee
+    let paid0 = balance0_after - flash.balance0_before;
+    let paid1 = balance1_after - flash.balance1_before;
+
+    let mut protocol_fees = PROTOCOL_FEES.load(deps.storage)?;
+    if !paid0.is_zero() {
+        let fee_protocol0 = slot0.fee_protocol % 16;
+        let fees0 = if fee_protocol0 == 0 {
+            Uint128::zero()
+        } else {
+            paid0 / Uint128::from(fee_protocol0)
+        };
+        protocol_fees.token0 = Uint128::new(protocol_fees.token0.u128().wrapping_add(fees0.u128()));
+        FEE_GROWTH_GLOBAL0_X128.update(deps.storage, |fee_growth| -> StdResult<_> {
+            Ok(fee_growth.wrapping_add(FullMath::checked_mul_div(
+                Uint256::from(paid0 - fees0),
+                FixedPoint128::Q128,
+                Uint256::from(liquidity),
+            )?))
+        })?;
+    }
+    if !paid1.is_zero() {
+        let fee_protocol1 = slot0.fee_protocol >> 4;
+        let fees1 = if fee_protocol1 == 0 {
+            Uint128::zero()
+        } else {
+            paid1 / Uint128::from(fee_protocol1)
+        };
+        protocol_fees.token1 = Uint128::new(protocol_fees.token1.u128().wrapping_add(fees1.u128()));
+        FEE_GROWTH_GLOBAL1_X128.update(deps.storage, |fee_growth| -> StdResult<_> {
+            Ok(fee_growth.wrapping_add(FullMath::checked_mul_div(
+                Uint256::from(paid1 - fees1),
+                FixedPoint128::Q128,
+                Uint256::from(liquidity),
+            )?))
+        })?;
+    }
+    PROTOCOL_FEES.save(deps.storage, &protocol_fees)?;
+
+    slot0.unlocked = true;
+    SLOT0.save(deps.storage, &slot0)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "flash_settle")
+        .add_event(
+            FlashEvent {
+                sender: flash.sender.into(),
+                recipient: flash.recipient.into(),
+                amount0: flash.amount0,
+                amount1: flash.amount1,
+                paid0,
+                paid1,
+            }
+            .to_event(),
+        ))
+}
+
+/// Checks that the payer of a mint or swap callback paid what it owed and unlocks the pool
+fn settle_payment(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let payment = PAYMENT.load(deps.storage)?;
+    PAYMENT.remove(deps.storage);
+    let config = CONFIG.load(deps.storage)?;
+
+    let paid0 = deps
+        .querier
+        .query_balance(&env.contract.address, &config.token0)?
+        .amount
+        .saturating_sub(payment.balance0_before);
+    let paid1 = deps
+        .querier
+        .query_balance(&env.contract.address, &config.token1)?
+        .amount
+        .saturating_sub(payment.balance1_before);
+
+    if paid0 < payment.owed0 {
+        return Err(ContractError::InsufficientFunds {
+            denom: config.token0,
+            owed: payment.owed0,
+            paid: paid0,
+        });
+    }
+    if paid1 < payment.owed1 {
+        return Err(ContractError::InsufficientFunds {
+            denom: config.token1,
+            owed: payment.owed1,
+            paid: paid1,
+        });
+    }
+
+    SLOT0.update(deps.storage, |mut slot0| -> StdResult<_> {
+        slot0.unlocked = true;
+        Ok(slot0)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "settle_payment")
+        .add_attribute("payer", payment.payer)
+        .add_attribute("paid0", paid0)
+        .add_attribute("paid1", paid1))
+}
+
+/// Effect some changes to a position
+/// Returns the updated position and the amounts of token0 and token1 owed to the pool (negative if the pool
+/// should pay the recipient)
+fn modify_position(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    owner: &Addr,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity_delta: i128,
+) -> Result<(PositionInfo, Int256, Int256), ContractError> {
+    check_ticks(tick_lower, tick_upper, config.tick_spacing)?;
+
+    let mut slot0 = SLOT0.load(storage)?;
+    let position = update_position(
+        storage,
+        env,
+        config,
+        &slot0,
+        owner,
+        tick_lower,
+        tick_upper,
+        liquidity_delta,
+    )?;
+
+    let mut amount0 = Int256::zero();
+    let mut amount1 = Int256::zero();
+    if liquidity_delta != 0 {
+        let liquidity_before = LIQUIDITY.load(storage)?.u128();
+
+        // write an oracle entry
+        write_observation(storage, &mut slot0, block_time(env), liquidity_before)?;
+        SLOT0.save(storage, &slot0)?;
+
+        let sqrt_ratio_lower_x96 = get_sqrt_ratio_at_tick(tick_lower);
+        let sqrt_ratio_upper_x96 = get_sqrt_ratio_at_tick(tick_upper);
+
+        if slot0.tick < tick_lower {
+            // current tick is below the passed range; liquidity can only become in range by crossing from left to
+            // right, when we'll need _more_ token0 (it's becoming more valuable) so user must provide it
+            amount0 = get_amount0_delta_signed(
+                sqrt_ratio_lower_x96,
+                sqrt_ratio_upper_x96,
+                liquidity_delta,
+            );
+        } else if slot0.tick < tick_upper {
+            // current tick is inside the passed range
+            amount0 = get_amount0_delta_signed(
+                slot0.sqrt_price_x96,
+                sqrt_ratio_upper_x96,
+                liquidity_delta,
+            );
+            amount1 = get_amount1_delta_signed(
+                sqrt_ratio_lower_x96,
+                slot0.sqrt_price_x96,
+                liquidity_delta,
+            );
+
+            LIQUIDITY.save(
+                storage,
+                &Uint128::new(add_delta(liquidity_before, liquidity_delta)),
+            )?;
+        } else {
+            // current tick is above the passed range; liquidity can only become in range by crossing from right to
+            // left, when we'll need _more_ token1 (it's becoming more valuable) so user must provide it
+            amount1 = get_amount1_delta_signed(
+                sqrt_ratio_lower_x96,
+                sqrt_ratio_upper_x96,
+                liquidity_delta,
+            );
+        }
+    }
+
+    Ok((position, amount0, amount1))
+}
+
+/// Gets and updates a position with the given liquidity delta, updating the boundary ticks along the way
+#[allow(clippy::too_many_arguments)]
+fn update_position(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    slot0: &Slot0,
+    owner: &Addr,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity_delta: i128,
+) -> Result<PositionInfo, ContractError> {
+    let key = (owner, tick_lower, tick_upper);
+    let mut position = POSITIONS.may_load(storage, key)?.unwrap_or_default();
+    if liquidity_delta == 0 && position.liquidity.is_zero() {
+        // disallow pokes for 0 liquidity positions
+        return Err(ContractError::NoPosition {});
+    }
+    if liquidity_delta < 0 && liquidity_delta.unsigned_abs() > position.liquidity.u128() {
+        return Err(ContractError::InsufficientLiquidity {
+            liquidity: position.liquidity,
+            requested: Uint128::new(liquidity_delta.unsigned_abs()),
+        });
+    }
+
+    let fee_growth_global0_x128 = FEE_GROWTH_GLOBAL0_X128.load(storage)?;
+    let fee_growth_global1_x128 = FEE_GROWTH_GLOBAL1_X128.load(storage)?;
+
+    // if we need to update the ticks, do it
+    let mut flipped_lower = false;
+    let mut flipped_upper = false;
+    if liquidity_delta != 0 {
+        let time = block_time(env);
+        let liquidity = LIQUIDITY.load(storage)?.u128();
+        let (tick_cumulative, seconds_per_liquidity_cumulative_x128) =
+            observe_latest(storage, slot0, time, liquidity)?;
+
+        flipped_lower = TICKS.update(
+            storage,
+            tick_lower,
+            slot0.tick,
+            liquidity_delta,
+            fee_growth_global0_x128,
+            fee_growth_global1_x128,
+            seconds_per_liquidity_cumulative_x128,
+            tick_cumulative,
+            time,
+            false,
+            config.max_liquidity_per_tick.u128(),
+        )?;
+        flipped_upper = TICKS.update(
+            storage,
+            tick_upper,
+            slot0.tick,
+            liquidity_delta,
+            fee_growth_global0_x128,
+            fee_growth_global1_x128,
+            seconds_per_liquidity_cumulative_x128,
+            tick_cumulative,
+            time,
+            true,
+            config.max_liquidity_per_tick.u128(),
+        )?;
+
+        if flipped_lower {
+            TICK_BITMAP.flip_tick(storage, tick_lower, config.tick_spacing)?;
+        }
+        if flipped_upper {
+            TICK_BITMAP.flip_tick(storage, tick_upper, config.tick_spacing)?;
+        }
+    }
+
+    let (fee_growth_inside0_x128, fee_growth_inside1_x128) = get_fee_growth_inside(
+        &TICKS.info(storage, tick_lower)?,
+        &TICKS.info(storage, tick_upper)?,
+        tick_lower,
+        tick_upper,
+        slot0.tick,
+        fee_growth_global0_x128,
+        fee_growth_global1_x128,
+    );
+
+    position::update(
+        &mut position,
+        liquidity_delta,
+        fee_growth_inside0_x128,
+        fee_growth_inside1_x128,
+    )?;
+    POSITIONS.save(storage, key, &position)?;
+
+    // clear any tick data that is no longer needed
+    if liquidity_delta < 0 {
+        if flipped_lower {
+            TICKS.clear(storage, tick_lower);
+        }
+        if flipped_upper {
+            TICKS.clear(storage, tick_upper);
+        }
+    }
+
+    Ok(position)
+}
+
+/// Common checks for valid tick inputs
+fn check_ticks(tick_lower: i32, tick_upper: i32, tick_spacing: i32) -> Result<(), ContractError> {
+    if tick_lower >= tick_upper {
+        return Err(ContractError::TickLowerNotBelowUpper {});
+    }
+    if tick_lower < MIN_TICK {
+        return Err(ContractError::TickLowerTooLow {});
+    }
+    if tick_upper > MAX_TICK {
+        return Err(ContractError::TickUpperTooHigh {});
+    }
+    for tick in [tick_lower, tick_upper] {
+        if tick % tick_spacing != 0 {
+            return Err(ContractError::TickNotSpaced { tick });
+        }
+    }
+    Ok(())
+}
+
+/// The state of a swap after `compute_swap` has walked it through the ticks
+pub(crate) struct SwapState {
+    /// the amount remaining to be swapped in/out of the input/output asset
+    pub amount_specified_remaining: Int256,
+    /// the amount already swapped out/in of the output/input asset
+    pub amount_calculated: Int256,
+    /// current sqrt(price)
+    pub sqrt_price_x96: Uint256,
+    /// the tick associated with the current price
+    pub tick: i32,
+    /// the global fee growth of the input token
+    pub fee_growth_global_x128: Uint256,
+    /// amount of input token paid as protocol fee
+    pub protocol_fee: Uint128,
+    /// the current liquidity in range
+    pub liquidity: u128,
+    /// the initialized ticks crossed, in order, with the input token's global fee growth when each was crossed
+    pub crossed_ticks: Vec<(i32, Uint256)>,
+    /// the number of iterations of the step loop
+    pub steps: u64,
+}
+
+/// Walks a swap from the current price through the initialized ticks without writing anything, so that `swap`
+/// and `quote` share one step loop. Crossed ticks are only recorded: crossing a tick leaves the bitmap and its net
+/// liquidity unchanged, so `swap` crossing them in storage afterwards gives the same result as crossing them in order
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn compute_swap(
+    storage: &dyn Storage,
+    config: &Config,
+    slot0_start: &Slot0,
+    liquidity_start: u128,
+    fee_growth_global_x128: Uint256,
+    zero_for_one: bool,
+    amount_specified: Int256,
+    sqrt_price_limit_x96: Uint256,
+) -> Result<SwapState, ContractError> {
+    let fee_protocol = if zero_for_one {
+        slot0_start.fee_protocol % 16
+    } else {
+        slot0_start.fee_protocol >> 4
+    };
+    let exact_input = amount_specified > Int256::zero();
+
+    let mut state = SwapState {
+        amount_specified_remaining: amount_specified,
+        amount_calculated: Int256::zero(),
+        sqrt_price_x96: slot0_start.sqrt_price_x96,
+        tick: slot0_start.tick,
+        fee_growth_global_x128,
+        protocol_fee: Uint128::zero(),
+        liquidity: liquidity_start,
+        crossed_ticks: vec![],
+        steps: 0,
+    };
+
+    // continue swapping as long as we haven't used the entire input/output and haven't reached the price limit
+    while !state.amount_specified_remaining.is_zero()
+        && state.sqrt_price_x96 != sqrt_price_limit_x96
+    {
+        let sqrt_price_start_x96 = state.sqrt_price_x96;
+        state.steps += 1;
+
+        let (tick_next, initialized) = TICK_BITMAP.next_initialized_tick(
+            storage,
+            state.tick,
+            config.tick_spacing,
+            zero_for_one,
+            MAX_BITMAP_WORDS_PER_STEP,
+        )?;
+        // ensure that we do not overshoot the min/max tick, as the tick bitmap is not aware of these bounds
+        let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+        let sqrt_price_next_x96 = get_sqrt_ratio_at_tick(tick_next);
+
+        let sqrt_price_target_x96 = if (zero_for_one && sqrt_price_next_x96 < sqrt_price_limit_x96)
+            || (!zero_for_one && sqrt_price_next_x96 > sqrt_price_limit_x96)
+        {
+            sqrt_price_limit_x96
+        } else {
+            sqrt_price_next_x96
+        };
+
+        // compute values to swap to the target tick, price limit, or point where input/output amount is exhausted
+        let (sqrt_price_after_x96, amount_in, amount_out, mut fee_amount) = compute_swap_step(
+            state.sqrt_price_x96,
+            sqrt_price_target_x96,
+            state.liquidity,
+            state.amount_specified_remaining,
+            config.fee,
+        );
+        state.sqrt_price_x96 = sqrt_price_after_x96;
+
+        if exact_input {
+            state.amount_specified_remaining -= to_i256(amount_in + fee_amount);
+            state.amount_calculated -= to_i256(amount_out);
+        } else {
+            state.amount_specified_remaining += to_i256(amount_out);
+            state.amount_calculated += to_i256(amount_in + fee_amount);
+        }
+
+        // if the protocol fee is on, calculate how much is owed, decrement fee_amount, and increment protocol_fee
+        if fee_protocol > 0 {
+            let delta = fee_amount / Uint256::from(fee_protocol);
+            fee_amount -= delta;
+            state.protocol_fee += Uint128::try_from(delta)?;
+        }
+
+        // update global fee tracker
+        if state.liquidity > 0 {
+            state.fee_growth_global_x128 =
+                state
+                    .fee_growth_global_x128
+                    .wrapping_add(FullMath::checked_mul_div(
+                        fee_amount,
+                        FixedPoint128::Q128,
+                        Uint256::from(state.liquidity),
+                    )?);
+        }
+
+        // shift tick if we reached the next price
+        if state.sqrt_price_x96 == sqrt_price_next_x96 {
+            // if the tick is initialized, record the transition and apply its net liquidity
+            if initialized {
+                let mut liquidity_net = TICKS
+                    .load_initialized(storage, tick_next)?
+                    .liquidity_net
+                    .i128();
+                state
+                    .crossed_ticks
+                    .push((tick_next, state.fee_growth_global_x128));
+
+                // if we're moving leftward, we interpret liquidity_net as the opposite sign
+                if zero_for_one {
+                    liquidity_net = -liquidity_net;
+                }
+                state.liquidity = add_delta(state.liquidity, liquidity_net);
+            }
+
+            state.tick = if zero_for_one {
+                tick_next - 1
+            } else {
+                tick_next
+            };
+        } else if state.sqrt_price_x96 != sqrt_price_start_x96 {
+            // recompute unless we're on a lower tick boundary (i.e. already transitioned ticks), and haven't moved
+            state.tick = get_tick_at_sqrt_ratio(state.sqrt_price_x96);
+        }
+    }
+
+    Ok(state)
+}
+
+/// Checks a swap's price limit lies strictly between the current price and the price bound in its direction
+pub(crate) fn check_sqrt_price_limit(
+    zero_for_one: bool,
+    sqrt_price_x96: Uint256,
+    sqrt_price_limit_x96: Uint256,
+) -> Result<(), ContractError> {
+    let limit_ok = if zero_for_one {
+        sqrt_price_limit_x96 < sqrt_price_x96 && sqrt_price_limit_x96 > MIN_SQRT_RATIO
+    } else {
+        sqrt_price_limit_x96 > sqrt_price_x96 && sqrt_price_limit_x96 < MAX_SQRT_RATIO
+    };
+    if !limit_ok {
+        return Err(ContractError::InvalidSqrtPriceLimit {});
+    }
+    Ok(())
+}
+
+/// Checks the sender owns the factory that created the pool
+fn check_factory_owner(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != query_factory_owner(&deps.querier, &config.factory)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+/// Loads slot0, failing if the pool is not initialized or is locked by a flash loan or payment callback in flight
+fn load_unlocked_slot0(storage: &dyn Storage) -> Result<Slot0, ContractError> {
+    let slot0 = SLOT0
+        .may_load(storage)?
+        .ok_or(ContractError::NotInitialized {})?;
+    if !slot0.unlocked {
+        return Err(ContractError::Locked {});
+    }
+    Ok(slot0)
+}
+
+/// Returns the tick and seconds per liquidity cumulatives as of `time`
+fn observe_latest(
+    storage: &dyn Storage,
+    slot0: &Slot0,
+    time: u32,
+    liquidity: u128,
+) -> StdResult<(i64, Uint256)> {
+    let mut last = OBSERVATIONS.load(storage, slot0.observation_index)?;
+    if last.block_timestamp != time {
+        last = oracle::transform(&last, time, slot0.tick, liquidity);
+    }
+    Ok((
+        last.tick_cumulative,
+        last.seconds_per_liquidity_cumulative_x128,
+    ))
+}
+
+/// Writes an oracle observation for the current tick, advancing the slot0 observation index and cardinality
+fn write_observation(
+    storage: &mut dyn Storage,
+    slot0: &mut Slot0,
+    time: u32,
+    liquidity: u128,
+) -> StdResult<()> {
+    let last = OBSERVATIONS.load(storage, slot0.observation_index)?;
+    let (index, cardinality, observation) = oracle::write(
+        &last,
+        slot0.observation_index,
+        time,
+        slot0.tick,
+        liquidity,
+        slot0.observation_cardinality,
+        slot0.observation_cardinality_next,
+    );
+    if let Some(observation) = observation {
+        OBSERVATIONS.save(storage, index, &observation)?;
+    }
+    slot0.observation_index = index;
+    slot0.observation_cardinality = cardinality;
+    Ok(())
+}
+
+/// Collects the amounts a mint or swap owes the pool
+/// Without callback data the amounts are taken from the attached funds, refunding any excess. With it, the message
+/// built by `callback` is delivered to the sender as a submessage and the pool stays locked until the reply has
+/// checked that its balances grew by what is owed; failing that check reverts the whole call, state included.
+#[allow(clippy::too_many_arguments)]
+fn request_payment(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    env: &Env,
+    config: &Config,
+    info: &MessageInfo,
+    owed0: Uint128,
+    owed1: Uint128,
+    data: Binary,
+    callback: impl FnOnce(Binary) -> StdResult<Binary>,
+    reply_id: u64,
+) -> Result<Vec<SubMsg>, ContractError> {
+    if data.is_empty() {
+        let refund = take_payment(config, info, owed0, owed1)?;
+        return Ok(refund.into_iter().map(SubMsg::new).collect());
+    }
+    if let Some(coin) = info.funds.first() {
+        return Err(ContractError::UnexpectedFunds {
+            denom: coin.denom.clone(),
+        });
+    }
+
+    PAYMENT.save(
+        storage,
+        &PaymentState {
+            payer: info.sender.clone(),
+            owed0,
+            owed1,
+            balance0_before: querier
+                .query_balance(&env.contract.address, &config.token0)?
+                .amount,
+            balance1_before: querier
+                .query_balance(&env.contract.address, &config.token1)?
+                .amount,
+        },
+    )?;
+    SLOT0.update(storage, |mut slot0| -> StdResult<_> {
+        slot0.unlocked = false;
+        Ok(slot0)
+    })?;
+
+    let callback = WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: callback(data)?,
+        funds: vec![],
+    };
+    Ok(vec![SubMsg::reply_on_success(callback, reply_id)])
+}
+
+/// Checks the funds attached to the call cover the amounts owed to the pool
+/// Returns the refund of anything attached beyond what is owed
+fn take_payment(
+    config: &Config,
+    info: &MessageInfo,
+    owed0: Uint128,
+    owed1: Uint128,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut paid0 = Uint128::zero();
+    let mut paid1 = Uint128::zero();
+    for coin in &info.funds {
+        if coin.denom == config.token0 {
+            paid0 += coin.amount;
+        } else if coin.denom == config.token1 {
+            paid1 += coin.amount;
+        } else {
+            return Err(ContractError::UnexpectedFunds {
+                denom: coin.denom.clone(),
+            });
+        }
+    }
+
+    if paid0 < owed0 {
+        return Err(ContractError::InsufficientFunds {
+            denom: config.token0.clone(),
+            owed: owed0,
+            paid: paid0,
+        });
+    }
+    if paid1 < owed1 {
+        return Err(ContractError::InsufficientFunds {
+            denom: config.token1.clone(),
+            owed: owed1,
+            paid: paid1,
+        });
+    }
+
+    Ok([
+        transfer(&info.sender, &config.token0, paid0 - owed0)?,
+        transfer(&info.sender, &config.token1, paid1 - owed1)?,
+    ]
+    .into_iter()
+    .flatten()
+    .collect())
+}
+
+/// Sends `amount` of `denom` to `to`, skipping empty transfers the bank module would reject
+fn transfer(to: &Addr, denom: &str, amount: Uint128) -> StdResult<Option<CosmosMsg>> {
+    if amount.is_zero() {
+        return Ok(None);
+    }
+    safe_transfer(&AssetInfo::native(denom), to, amount).map(Some)
+}
+
+/// The fee charged on a flash loan of `amount`, rounded up
+fn flash_fee(amount: Uint128, fee: u32) -> Result<Uint128, ContractError> {
+    Ok(Uint128::try_from(FullMath::checked_mul_div_rounding_up(
+        Uint256::from(amount),
+        Uint256::from(fee),
+        Uint256::from(FEE_DENOMINATOR),
+    )?)?)
+}
+
+/// Converts a liquidity amount into a signed delta
+fn liquidity_delta(amount: Uint128, negative: bool) -> StdResult<i128> {
+    let delta = i128::try_from(amount.u128())
+        .map_err(|_| StdError::generic_err("liquidity amount doesn't fit in an int128"))?;
+    Ok(if negative { -delta } else { delta })
+}
+
+/// The positive part of a signed pool delta, i.e. what is owed to the pool
+pub(crate) fn amount_owed(delta: Int256) -> Result<Uint128, ContractError> {
+    if delta <= Int256::zero() {
+        return Ok(Uint128::zero());
+    }
+    Ok(Uint128::try_from(to_u256(delta))?)
+}
+
+/// The negative part of a signed pool delta, i.e. what the pool pays out
+pub(crate) fn amount_paid(delta: Int256) -> Result<Uint128, ContractError> {
+    if delta >= Int256::zero() {
+        return Ok(Uint128::zero());
+    }
+    Ok(Uint128::try_from(to_u256(-delta))?)
+}
+
+/// The block timestamp, truncated to 32 bits like the oracle expects
+fn block_time(env: &Env) -> u32 {
+    env.block.time.seconds() as u32
+}
@@ -0,0 +1,100 @@
+use cosmwasm_std::{ConversionOverflowError, OverflowError, StdError, Uint128};
+use libraries::tick::TickError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("{0}")]
+    Tick(#[from] TickError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid protocol fee: {fee_protocol0}, {fee_protocol1}")]
+    InvalidFeeProtocol {
+        fee_protocol0: u8,
+        fee_protocol1: u8,
+    },
+
+    #[error("Pool is locked")]
+    Locked {},
+
+    #[error("Pool is already initialized")]
+    AlreadyInitialized {},
+
+    #[error("Pool is not initialized")]
+    NotInitialized {},
+
+    #[error("token0 must sort before token1 and differ from it")]
+    InvalidTokenPair {},
+
+    #[error("Invalid tick spacing: {tick_spacing}")]
+    InvalidTickSpacing { tick_spacing: i32 },
+
+    #[error("Invalid fee: {fee}")]
+    InvalidFee { fee: u32 },
+
+    #[error("Lower tick must be below upper tick")]
+    TickLowerNotBelowUpper {},
+
+    #[error("Lower tick is below the minimum tick")]
+    TickLowerTooLow {},
+
+    #[error("Upper tick is above the maximum tick")]
+    TickUpperTooHigh {},
+
+    #[error("Tick {tick} is not a multiple of the tick spacing")]
+    TickNotSpaced { tick: i32 },
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Position has no liquidity")]
+    NoPosition {},
+
+    #[error("Position holds {liquidity} liquidity, cannot remove {requested}")]
+    InsufficientLiquidity {
+        liquidity: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("Amount specified must not be zero")]
+    AmountSpecifiedZero {},
+
+    #[error("Sqrt price is outside the supported range")]
+    InvalidSqrtPrice {},
+
+    #[error("Invalid sqrt price limit")]
+    InvalidSqrtPriceLimit {},
+
+    #[error("Insufficient {denom} paid: owed {owed}, received {paid}")]
+    InsufficientFunds {
+        denom: String,
+        owed: Uint128,
+        paid: Uint128,
+    },
+
+    #[error("Unexpected funds sent in {denom}")]
+    UnexpectedFunds { denom: String },
+
+    #[error("Pool has no liquidity")]
+    NoLiquidity {},
+
+    #[error("Flash loan of {denom} was not repaid with its fee")]
+    FlashNotRepaid { denom: String },
+
+    #[error("Observation cardinality can grow by at most {max} per call")]
+    ObservationCardinalityGrowthTooLarge { max: u16 },
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+}
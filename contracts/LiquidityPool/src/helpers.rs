@@ -0,0 +1,119 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Coin, ConversionOverflowError, CosmosMsg, QuerierWrapper, StdError, StdResult, Uint128,
+    Uint256,
+};
+use interfaces::pool::IXionPoolDerivedState;
+use libraries::transfer_helper::{safe_transfer, AssetInfo};
+use thiserror::Error;
+
+use crate::msg::{ObserveResponse, QueryMsg, SnapshotCumulativesInsideResponse};
+use crate::state::Config;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MintPaymentError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("Insufficient {denom} attached: owed {owed}, received {paid}")]
+    InsufficientFunds {
+        denom: String,
+        owed: Uint128,
+        paid: Uint128,
+    },
+}
+
+/// A handle on a deployed pool, for contracts that read its oracle and range snapshots
+#[cw_serde]
+pub struct XionPoolContract(pub Addr);
+
+impl XionPoolContract {
+    pub fn addr(&self) -> Addr {
+        self.0.clone()
+    }
+
+    /// Pays the amounts the pool asked for in a mint callback out of the funds `payer` attached to the mint,
+    /// refunding whatever is left to the payer
+    /// Returns the transfers to the pool followed by the refunds
+    pub fn pay_from_funds(
+        &self,
+        querier: &QuerierWrapper,
+        funds: Vec<Coin>,
+        payer: &Addr,
+        amount0_owed: Uint256,
+        amount1_owed: Uint256,
+    ) -> Result<Vec<CosmosMsg>, MintPaymentError> {
+        let config: Config = querier.query_wasm_smart(&self.0, &QueryMsg::Config {})?;
+        let mut refund = funds;
+        let mut msgs = vec![];
+        for (denom, owed) in [(config.token0, amount0_owed), (config.token1, amount1_owed)] {
+            let owed = Uint128::try_from(owed)?;
+            if owed.is_zero() {
+                continue;
+            }
+            match refund.iter_mut().find(|coin| coin.denom == denom) {
+                Some(paid) if paid.amount >= owed => paid.amount -= owed,
+                paid => {
+                    return Err(MintPaymentError::InsufficientFunds {
+                        owed,
+                        paid: paid.map_or(Uint128::zero(), |paid| paid.amount),
+                        denom,
+                    })
+                }
+            }
+            msgs.push(safe_transfer(&AssetInfo::native(denom), &self.0, owed)?);
+        }
+        for coin in refund {
+            if !coin.amount.is_zero() {
+                msgs.push(safe_transfer(
+                    &AssetInfo::native(coin.denom),
+                    payer,
+                    coin.amount,
+                )?);
+            }
+        }
+        Ok(msgs)
+    }
+}
+
+impl IXionPoolDerivedState for XionPoolContract {
+    fn observe(
+        &self,
+        querier: &QuerierWrapper,
+        seconds_ago: Vec<u32>,
+    ) -> StdResult<(Vec<i64>, Vec<Uint256>)> {
+        let res: ObserveResponse = querier.query_wasm_smart(
+            &self.0,
+            &QueryMsg::Observe {
+                seconds_agos: seconds_ago,
+            },
+        )?;
+        Ok((
+            res.tick_cumulatives,
+            res.seconds_per_liquidity_cumulative_x128s,
+        ))
+    }
+
+    fn snapshot_cumulatives_inside(
+        &self,
+        querier: &QuerierWrapper,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> StdResult<(i64, Uint256, u32)> {
+        let res: SnapshotCumulativesInsideResponse = querier.query_wasm_smart(
+            &self.0,
+            &QueryMsg::SnapshotCumulativesInside {
+                tick_lower,
+                tick_upper,
+            },
+        )?;
+        Ok((
+            res.tick_cumulative_inside,
+            res.seconds_per_liquidity_inside_x128,
+            res.seconds_inside,
+        ))
+    }
+}
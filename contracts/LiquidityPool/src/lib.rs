@@ -1,17 +1,11 @@
-// src/lib.rs
+pub mod contract;
+pub mod error;
+pub mod helpers;
+pub mod msg;
+pub mod quoter;
+pub mod state;
 
-// Bu alana gerekli modül ve kodlarınızı ekleyin.
-// Örneğin:
-// mod my_module;
-// use crate::my_module::MyStruct;
+pub use crate::error::ContractError;
 
-// Bu dosya bir kütüphane olarak derlensinse genellikle burada bir trait yada struct tanımlamanız yapılır.
-
-// Bu alanı güvende bırakın!
 #[cfg(test)]
-mod tests {
-    #[test]
-    fn it_works() {
-        assert_eq!(2 + 2, 4);
-    }
-}
+mod tests;
@@ -0,0 +1,193 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Int256, Uint128, Uint256};
+use libraries::oracle::Observation;
+use libraries::position::PositionInfo;
+use libraries::tick::TickInfo;
+
+use crate::state::{Config, ProtocolFees, Slot0};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// Denom of the first token of the pool, must sort before `token1`
+    pub token0: String,
+    /// Denom of the second token of the pool
+    pub token1: String,
+    /// The pool's fee in hundredths of a bip, i.e. 1e-6
+    pub fee: u32,
+    /// The minimum number of ticks between initialized ticks
+    pub tick_spacing: i32,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Sets the initial price for the pool, as a sqrt(token1/token0) Q64.96 value
+    Initialize { sqrt_price_x96: Uint256 },
+    /// Adds liquidity for the recipient over [tick_lower, tick_upper), paid with the attached funds, or in an
+    /// `XionMintCallback` on the sender when `data` is not empty
+    Mint {
+        recipient: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: Uint128,
+        #[serde(default)]
+        data: Binary,
+    },
+    /// Collects up to the requested amounts of tokens owed to the sender's position
+    Collect {
+        recipient: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount0_requested: Uint128,
+        amount1_requested: Uint128,
+    },
+    /// Burns liquidity from the sender's position, crediting the tokens owed to it
+    Burn {
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: Uint128,
+    },
+    /// Swaps token0 for token1, or token1 for token0; a positive `amount_specified` is exact input and a
+    /// negative one exact output. The input is paid with the attached funds, or in an `XionSwapCallback` on the
+    /// sender when `data` is not empty
+    Swap {
+        recipient: String,
+        zero_for_one: bool,
+        amount_specified: Int256,
+        sqrt_price_limit_x96: Uint256,
+        #[serde(default)]
+        data: Binary,
+    },
+    /// Lends token0 and/or token1 to the recipient, to be paid back plus a fee in the flash callback
+    Flash {
+        recipient: String,
+        amount0: Uint128,
+        amount1: Uint128,
+        #[serde(default)]
+        data: Binary,
+    },
+    /// Increases the maximum number of price and liquidity observations the pool will store, up to 65535
+    /// The new slots are written up front, so the caller pays for them instead of later swappers. A single call may
+    /// add at most 1000 slots to stay within the block gas limit.
+    IncreaseObservationCardinalityNext { observation_cardinality_next: u16 },
+    /// Sets the protocol's share of the swap fees in each token to 1/`fee_protocol`, or turns it off with 0
+    /// Only callable by the factory owner; each value must be 0 or between 4 and 10
+    SetFeeProtocol {
+        fee_protocol0: u8,
+        fee_protocol1: u8,
+    },
+    /// Sends up to the requested amounts of the accrued protocol fees to the recipient
+    /// Only callable by the factory owner
+    CollectProtocol {
+        recipient: String,
+        amount0_requested: Uint128,
+        amount1_requested: Uint128,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// The pool's immutable parameters
+    #[returns(Config)]
+    Config {},
+    /// The current price, tick, oracle indices, protocol fee and lock
+    #[returns(Slot0)]
+    Slot0 {},
+    /// The currently in range liquidity available to the pool
+    #[returns(Uint128)]
+    Liquidity {},
+    #[returns(FeeGrowthGlobalResponse)]
+    FeeGrowthGlobal {},
+    /// The amounts of token0 and token1 that are owed to the protocol
+    #[returns(ProtocolFees)]
+    ProtocolFees {},
+    /// Information about a specific tick in the pool; uninitialized ticks are returned zeroed
+    #[returns(TickInfo)]
+    Tick { tick: i32 },
+    /// A packed word of 256 tick initialized booleans
+    #[returns(Uint256)]
+    TickBitmap { word_position: i16 },
+    /// Information about a position, looked up by its owner and tick range
+    #[returns(PositionInfo)]
+    Position {
+        owner: String,
+        tick_lower: i32,
+        tick_upper: i32,
+    },
+    /// An observation from the oracle ring buffer
+    #[returns(Observation)]
+    Observation { index: u16 },
+    /// The cumulative tick and seconds per liquidity as of each `seconds_agos` from the current block time
+    /// Fails if any of them is older than the oldest stored observation
+    #[returns(ObserveResponse)]
+    Observe { seconds_agos: Vec<u32> },
+    /// The time weighted average tick over the last `window` seconds
+    #[returns(ArithmeticMeanTickResponse)]
+    ArithmeticMeanTick { window: u32 },
+    /// A snapshot of the tick cumulative, seconds per liquidity and seconds inside a tick range
+    /// Snapshots must only be compared to other snapshots taken over a period for which a position existed
+    #[returns(SnapshotCumulativesInsideResponse)]
+    SnapshotCumulativesInside { tick_lower: i32, tick_upper: i32 },
+    /// Simulates swapping exactly `amount_in` without changing any state
+    /// The price limit defaults to the furthest price in the swap's direction, in which case the whole amount may
+    /// not be used
+    #[returns(QuoteResponse)]
+    QuoteExactInput {
+        zero_for_one: bool,
+        amount_in: Uint128,
+        sqrt_price_limit_x96: Option<Uint256>,
+    },
+    /// Simulates swapping for exactly `amount_out` without changing any state
+    /// The price limit defaults to the furthest price in the swap's direction, in which case less may be received
+    #[returns(QuoteResponse)]
+    QuoteExactOutput {
+        zero_for_one: bool,
+        amount_out: Uint128,
+        sqrt_price_limit_x96: Option<Uint256>,
+    },
+}
+
+/// The all-time fee growth per unit of liquidity, in each token
+#[cw_serde]
+pub struct FeeGrowthGlobalResponse {
+    pub fee_growth_global0_x128: Uint256,
+    pub fee_growth_global1_x128: Uint256,
+}
+
+#[cw_serde]
+pub struct ObserveResponse {
+    /// Cumulative tick values as of each `seconds_agos`
+    pub tick_cumulatives: Vec<i64>,
+    /// Cumulative seconds per in range liquidity as of each `seconds_agos`
+    pub seconds_per_liquidity_cumulative_x128s: Vec<Uint256>,
+}
+
+#[cw_serde]
+pub struct ArithmeticMeanTickResponse {
+    /// The average tick over the window, rounded towards negative infinity
+    pub arithmetic_mean_tick: i32,
+}
+
+#[cw_serde]
+pub struct SnapshotCumulativesInsideResponse {
+    /// The snapshot of the tick accumulator for the range
+    pub tick_cumulative_inside: i64,
+    /// The snapshot of seconds per liquidity for the range
+    pub seconds_per_liquidity_inside_x128: Uint256,
+    /// The snapshot of the number of seconds during which the price was in the range
+    pub seconds_inside: u32,
+}
+
+#[cw_serde]
+pub struct QuoteResponse {
+    /// The amount the swap would take, fee included
+    pub amount_in: Uint128,
+    /// The amount the swap would pay out
+    pub amount_out: Uint128,
+    /// The pool's price after the swap
+    pub sqrt_price_x96_after: Uint256,
+    /// The number of initialized ticks the swap would cross
+    pub initialized_ticks_crossed: u32,
+    /// A rough heuristic of the swap's cost for comparing quotes, not a measured gas figure
+    pub gas_estimate: u64,
+}
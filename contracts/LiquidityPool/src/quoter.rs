@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Swap quoter
+//! Simulates a swap against the pool's current state without writing anything, so that the expected amounts can be
+//! queried before a swap is submitted. The simulation runs the swap's own step loop, `compute_swap`, and only skips
+//! persisting the crossed ticks and fee growth, so a quote matches the swap executed against the same state exactly.
+
+use cosmwasm_std::{Int256, Storage, Uint128, Uint256};
+use libraries::tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO};
+
+use crate::contract::{amount_owed, amount_paid, check_sqrt_price_limit, compute_swap, SwapState};
+use crate::error::ContractError;
+use crate::msg::QuoteResponse;
+use crate::state::{CONFIG, FEE_GROWTH_GLOBAL0_X128, FEE_GROWTH_GLOBAL1_X128, LIQUIDITY, SLOT0};
+
+// The weights below are a rough heuristic, not measured gas: they only scale with the work a swap does, so that
+// quotes can be compared with each other. Callers should simulate the transaction for an actual gas limit.
+
+/// The weight of a swap that finishes in a single step, including the payment and transfer messages
+pub const GAS_BASE: u64 = 120_000;
+/// The additional weight of each step of the swap loop, i.e. each initialized tick or bitmap search bound reached
+pub const GAS_PER_STEP: u64 = 15_000;
+/// The additional weight of crossing an initialized tick, which loads and saves it
+pub const GAS_PER_INITIALIZED_TICK: u64 = 30_000;
+
+/// Simulates a swap of `amount_specified`, exact input if positive and exact output if negative
+/// The price limit defaults to the furthest price in the swap's direction
+pub fn quote(
+    storage: &dyn Storage,
+    zero_for_one: bool,
+    amount_specified: Int256,
+    sqrt_price_limit_x96: Option<Uint256>,
+) -> Result<QuoteResponse, ContractError> {
+    if amount_specified.is_zero() {
+        return Err(ContractError::AmountSpecifiedZero {});
+    }
+    let slot0 = SLOT0
+        .may_load(storage)?
+        .ok_or(ContractError::NotInitialized {})?;
+    let config = CONFIG.load(storage)?;
+
+    let sqrt_price_limit_x96 = sqrt_price_limit_x96.unwrap_or(if zero_for_one {
+        MIN_SQRT_RATIO + Uint256::one()
+    } else {
+        MAX_SQRT_RATIO - Uint256::one()
+    });
+    check_sqrt_price_limit(zero_for_one, slot0.sqrt_price_x96, sqrt_price_limit_x96)?;
+
+    let exact_input = amount_specified > Int256::zero();
+    let fee_growth_global_x128 = if zero_for_one {
+        FEE_GROWTH_GLOBAL0_X128.load(storage)?
+    } else {
+        FEE_GROWTH_GLOBAL1_X128.load(storage)?
+    };
+
+    let SwapState {
+        amount_specified_remaining,
+        amount_calculated,
+        sqrt_price_x96,
+        crossed_ticks,
+        steps,
+        ..
+    } = compute_swap(
+        storage,
+        &config,
+        &slot0,
+        LIQUIDITY.load(storage)?.u128(),
+        fee_growth_global_x128,
+        zero_for_one,
+        amount_specified,
+        sqrt_price_limit_x96,
+    )?;
+    let initialized_ticks_crossed = crossed_ticks.len() as u32;
+
+    let (amount0, amount1) = if zero_for_one == exact_input {
+        (
+            amount_specified - amount_specified_remaining,
+            amount_calculated,
+        )
+    } else {
+        (
+            amount_calculated,
+            amount_specified - amount_specified_remaining,
+        )
+    };
+    let (amount_in, amount_out): (Uint128, Uint128) = if zero_for_one {
+        (amount_owed(amount0)?, amount_paid(amount1)?)
+    } else {
+        (amount_owed(amount1)?, amount_paid(amount0)?)
+    };
+
+    Ok(QuoteResponse {
+        amount_in,
+        amount_out,
+        sqrt_price_x96_after: sqrt_price_x96,
+        initialized_ticks_crossed,
+        gas_estimate: GAS_BASE
+            + GAS_PER_STEP * steps
+            + GAS_PER_INITIALIZED_TICK * initialized_ticks_crossed as u64,
+    })
+}
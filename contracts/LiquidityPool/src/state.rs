@@ -0,0 +1,89 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use libraries::oracle::Observation;
+use libraries::position::PositionInfo;
+use libraries::tick::Ticks;
+use libraries::tick_bitmap::TickBitmap;
+use libraries::{i24, u160, u256};
+
+#[cw_serde]
+pub struct Config {
+    /// The contract that deployed the pool
+    pub factory: Addr,
+    pub token0: String,
+    pub token1: String,
+    /// The pool's fee in hundredths of a bip, i.e. 1e-6
+    pub fee: u32,
+    pub tick_spacing: i24,
+    /// The maximum amount of position liquidity that can use any tick in the range
+    pub max_liquidity_per_tick: Uint128,
+}
+
+#[cw_serde]
+pub struct Slot0 {
+    /// The current price
+    pub sqrt_price_x96: u160,
+    /// The current tick
+    pub tick: i24,
+    /// The most-recently updated index of the observations array
+    pub observation_index: u16,
+    /// The current maximum number of observations that are being stored
+    pub observation_cardinality: u16,
+    /// The next maximum number of observations to store, triggered in observations.write
+    pub observation_cardinality_next: u16,
+    /// The current protocol fee as a percentage of the swap fee taken on withdrawal
+    /// represented as an integer denominator (1/x)%
+    pub fee_protocol: u8,
+    /// Whether the pool is locked
+    pub unlocked: bool,
+}
+
+/// Accumulated protocol fees in token0/token1 units
+#[cw_serde]
+#[derive(Default)]
+pub struct ProtocolFees {
+    pub token0: Uint128,
+    pub token1: Uint128,
+}
+
+/// A flash loan in flight, kept between `flash` and its reply
+#[cw_serde]
+pub struct FlashState {
+    pub sender: Addr,
+    pub recipient: Addr,
+    pub amount0: Uint128,
+    pub amount1: Uint128,
+    pub fee0: Uint128,
+    pub fee1: Uint128,
+    pub balance0_before: Uint128,
+    pub balance1_before: Uint128,
+}
+
+/// A mint or swap paid through a callback, kept until its reply checks the pool was paid
+#[cw_serde]
+pub struct PaymentState {
+    pub payer: Addr,
+    pub owed0: Uint128,
+    pub owed1: Uint128,
+    pub balance0_before: Uint128,
+    pub balance1_before: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const SLOT0: Item<Slot0> = Item::new("slot0");
+pub const FEE_GROWTH_GLOBAL0_X128: Item<u256> = Item::new("fee_growth_global0_x128");
+pub const FEE_GROWTH_GLOBAL1_X128: Item<u256> = Item::new("fee_growth_global1_x128");
+pub const PROTOCOL_FEES: Item<ProtocolFees> = Item::new("protocol_fees");
+pub const LIQUIDITY: Item<Uint128> = Item::new("liquidity");
+pub const TICKS: Ticks = Ticks::new("ticks");
+pub const TICK_BITMAP: TickBitmap = TickBitmap::new("tick_bitmap");
+pub const POSITIONS: Map<(&Addr, i24, i24), PositionInfo> = Map::new("positions");
+pub const OBSERVATIONS: Map<u16, Observation> = Map::new("observations");
+pub const FLASH: Item<FlashState> = Item::new("flash");
+pub const PAYMENT: Item<PaymentState> = Item::new("payment");
+
+/// Loads an observation, treating a slot that was never written as uninitialized
+pub fn load_observation(storage: &dyn Storage, index: u16) -> StdResult<Observation> {
+    Ok(OBSERVATIONS.may_load(storage, index)?.unwrap_or_default())
+}
@@ -0,0 +1,1351 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier,
+    MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coin, coins, from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, DepsMut, Empty,
+    Env, Int256, MessageInfo, OwnedDeps, Reply, Response, StdResult, SubMsgResponse, SubMsgResult,
+    Uint128, Uint256, WasmMsg,
+};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use interfaces::callback::flash::XionFlashCallbackMsg;
+use interfaces::callback::mint::XionMintCallbackMsg;
+use interfaces::callback::swap::XionSwapCallbackMsg;
+use interfaces::pool::events::SwapEvent;
+use interfaces::pool::IXionPoolEvent;
+use libraries::fixed_point_96::FixedPoint96;
+use libraries::oracle::Observation;
+use libraries::position::PositionInfo;
+use libraries::sqrt_price_math::{get_amount0_delta, get_amount1_delta};
+use libraries::tick::{tick_spacing_to_max_liquidity_per_tick, TickError, TickInfo};
+use libraries::tick_math::{get_sqrt_ratio_at_tick, MAX_SQRT_RATIO, MIN_SQRT_RATIO};
+
+use crate::contract::{
+    execute, instantiate, query, reply, FLASH_REPLY_ID, MINT_CALLBACK_REPLY_ID,
+    SWAP_CALLBACK_REPLY_ID,
+};
+use crate::error::ContractError;
+use crate::msg::{
+    ArithmeticMeanTickResponse, ExecuteMsg, FeeGrowthGlobalResponse, InstantiateMsg,
+    ObserveResponse, QueryMsg, QuoteResponse, SnapshotCumulativesInsideResponse,
+};
+use crate::state::{ProtocolFees, Slot0, SLOT0};
+
+const TOKEN0: &str = "uatom";
+const TOKEN1: &str = "uxion";
+const FEE: u32 = 3000;
+const TICK_SPACING: i32 = 60;
+
+fn instantiate_pool(deps: DepsMut) {
+    instantiate(
+        deps,
+        mock_env(),
+        mock_info("factory", &[]),
+        InstantiateMsg {
+            token0: TOKEN0.to_string(),
+            token1: TOKEN1.to_string(),
+            fee: FEE,
+            tick_spacing: TICK_SPACING,
+        },
+    )
+    .unwrap();
+}
+
+/// Instantiates a pool and initializes it at a price of 1
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    instantiate_pool(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Initialize {
+            sqrt_price_x96: FixedPoint96::Q96,
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn mint_msg(tick_lower: i32, tick_upper: i32, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Mint {
+        recipient: "alice".to_string(),
+        tick_lower,
+        tick_upper,
+        amount: Uint128::new(amount),
+        data: Binary::default(),
+    }
+}
+
+fn swap_msg(zero_for_one: bool, amount_specified: i128) -> ExecuteMsg {
+    ExecuteMsg::Swap {
+        recipient: "bob".to_string(),
+        zero_for_one,
+        amount_specified: Int256::from(amount_specified),
+        sqrt_price_limit_x96: if zero_for_one {
+            MIN_SQRT_RATIO + Uint256::one()
+        } else {
+            MAX_SQRT_RATIO - Uint256::one()
+        },
+        data: Binary::default(),
+    }
+}
+
+fn query_slot0(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Slot0 {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Slot0 {}).unwrap()).unwrap()
+}
+
+fn query_liquidity(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Uint128 {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Liquidity {}).unwrap()).unwrap()
+}
+
+fn query_tick(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, tick: i32) -> TickInfo {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Tick { tick }).unwrap()).unwrap()
+}
+
+fn query_position(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> PositionInfo {
+    let msg = QueryMsg::Position {
+        owner: "alice".to_string(),
+        tick_lower,
+        tick_upper,
+    };
+    from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+/// The value of an attribute of the response or of its pool event
+fn attribute<'a>(res: &'a cosmwasm_std::Response, key: &str) -> &'a str {
+    &res.attributes
+        .iter()
+        .chain(res.events.iter().flat_map(|event| &event.attributes))
+        .find(|a| a.key == key)
+        .unwrap()
+        .value
+}
+
+#[test]
+fn instantiate_rejects_unsorted_tokens() {
+    let mut deps = mock_dependencies();
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("factory", &[]),
+        InstantiateMsg {
+            token0: TOKEN1.to_string(),
+            token1: TOKEN0.to_string(),
+            fee: FEE,
+            tick_spacing: TICK_SPACING,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTokenPair {});
+}
+
+#[test]
+fn initialize_sets_price_and_first_observation() {
+    let mut deps = setup();
+
+    let slot0 = query_slot0(&deps);
+    assert_eq!(slot0.sqrt_price_x96, FixedPoint96::Q96);
+    assert_eq!(slot0.tick, 0);
+    assert_eq!(slot0.observation_cardinality, 1);
+    assert_eq!(slot0.observation_cardinality_next, 1);
+    assert!(slot0.unlocked);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Initialize {
+            sqrt_price_x96: FixedPoint96::Q96,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyInitialized {});
+}
+
+#[test]
+fn mint_requires_initialized_pool() {
+    let mut deps = mock_dependencies();
+    instantiate_pool(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(1000, TOKEN0), coin(1000, TOKEN1)]),
+        mint_msg(-60, 60, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotInitialized {});
+}
+
+#[test]
+fn mint_validates_ticks() {
+    let mut deps = setup();
+    let info = mock_info("alice", &[]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        mint_msg(60, -60, 1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TickLowerNotBelowUpper {});
+
+    let err = execute(deps.as_mut(), mock_env(), info, mint_msg(-60, 61, 1)).unwrap_err();
+    assert_eq!(err, ContractError::TickNotSpaced { tick: 61 });
+}
+
+#[test]
+fn mint_above_max_liquidity_per_tick_fails() {
+    let mut deps = setup();
+    let max_liquidity = tick_spacing_to_max_liquidity_per_tick(TICK_SPACING);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        mint_msg(-60, 60, max_liquidity + 1),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Tick(TickError::LiquidityOverflow {
+            tick: -60,
+            max_liquidity: Uint128::new(max_liquidity),
+        })
+    );
+    assert_eq!(query_tick(&deps, -60), TickInfo::default());
+}
+
+#[test]
+fn mint_in_range_takes_both_tokens_and_refunds_excess() {
+    let mut deps = setup();
+    let liquidity = 1_000_000u128;
+    let expected0 = get_amount0_delta(
+        FixedPoint96::Q96,
+        get_sqrt_ratio_at_tick(600),
+        liquidity,
+        true,
+    );
+    let expected1 = get_amount1_delta(
+        get_sqrt_ratio_at_tick(-600),
+        FixedPoint96::Q96,
+        liquidity,
+        true,
+    );
+    assert_eq!(expected0, expected1);
+    let owed = Uint128::try_from(expected0).unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)]),
+        mint_msg(-600, 600, liquidity),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "amount0"), owed.to_string());
+    assert_eq!(attribute(&res, "amount1"), owed.to_string());
+    let refund = 100_000 - owed.u128();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(refund, TOKEN0),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(refund, TOKEN1),
+            }),
+        ]
+    );
+
+    assert_eq!(query_liquidity(&deps), Uint128::new(liquidity));
+    assert_eq!(
+        query_tick(&deps, -600).liquidity_net.i128(),
+        liquidity as i128
+    );
+    assert_eq!(
+        query_tick(&deps, 600).liquidity_net.i128(),
+        -(liquidity as i128)
+    );
+    assert_eq!(
+        query_position(&deps, -600, 600).liquidity,
+        Uint128::new(liquidity)
+    );
+}
+
+#[test]
+fn mint_out_of_range_takes_one_token() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(100_000, TOKEN0)),
+        mint_msg(600, 1200, 1_000_000),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "amount1"), "0");
+    // position above the current price does not add to the active liquidity
+    assert_eq!(query_liquidity(&deps), Uint128::zero());
+}
+
+#[test]
+fn mint_with_insufficient_funds_fails() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(1, TOKEN0), coin(100_000, TOKEN1)]),
+        mint_msg(-600, 600, 1_000_000),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFunds { denom, .. } if denom == TOKEN0));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(100_000, TOKEN0), coin(100_000, "uother")]),
+        mint_msg(-600, 600, 1_000_000),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnexpectedFunds {
+            denom: "uother".to_string()
+        }
+    );
+}
+
+#[test]
+fn swap_exact_input_zero_for_one() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)]),
+        mint_msg(-600, 600, 10_000_000),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(1000, TOKEN0)),
+        swap_msg(true, 1000),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "amount0"), "1000");
+    let amount1: i128 = attribute(&res, "amount1").parse().unwrap();
+    // the output is the input less the 0.3% fee and a little price impact
+    assert!(amount1 < -990 && amount1 > -997, "amount1 {amount1}");
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins((-amount1) as u128, TOKEN1),
+        })
+    );
+
+    let slot0 = query_slot0(&deps);
+    assert!(slot0.sqrt_price_x96 < FixedPoint96::Q96);
+    assert_eq!(slot0.tick, -2);
+    // the swap event carries the new pool state for indexers
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(
+        SwapEvent::from_event(&res.events[0]).unwrap(),
+        SwapEvent {
+            sender: "bob".to_string(),
+            recipient: "bob".to_string(),
+            amount0: Int256::from(1000i128),
+            amount1: Int256::from(amount1),
+            sqrt_price_x96: slot0.sqrt_price_x96,
+            liquidity: Uint128::new(10_000_000),
+            tick: -2,
+        }
+    );
+
+    let fee_growth: FeeGrowthGlobalResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::FeeGrowthGlobal {}).unwrap())
+            .unwrap();
+    assert!(!fee_growth.fee_growth_global0_x128.is_zero());
+    assert!(fee_growth.fee_growth_global1_x128.is_zero());
+}
+
+#[test]
+fn swap_exact_output_one_for_zero() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)]),
+        mint_msg(-600, 600, 10_000_000),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        swap_msg(false, -100),
+    )
+    .unwrap_err();
+    assert!(matches!(err, ContractError::InsufficientFunds { denom, .. } if denom == TOKEN1));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(1000, TOKEN1)),
+        swap_msg(false, -100),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "amount0"), "-100");
+    let amount1: u128 = attribute(&res, "amount1").parse().unwrap();
+    assert!(amount1 > 100 && amount1 < 110, "amount1 {amount1}");
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins(100, TOKEN0),
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins(1000 - amount1, TOKEN1),
+        })
+    );
+}
+
+#[test]
+fn swap_crosses_out_of_position_range() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)]),
+        mint_msg(-60, 60, 1_000_000),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(1_000_000, TOKEN0)),
+        swap_msg(true, 1_000_000),
+    )
+    .unwrap();
+
+    // all of the token1 in the range was bought and the price ran to the limit
+    let amount0: u128 = attribute(&res, "amount0").parse().unwrap();
+    assert!(amount0 < 1_000_000);
+    assert_eq!(query_liquidity(&deps), Uint128::zero());
+    let slot0 = query_slot0(&deps);
+    assert_eq!(slot0.sqrt_price_x96, MIN_SQRT_RATIO + Uint256::one());
+    // the lower tick was crossed, flipping its outside accumulators
+    assert!(!query_tick(&deps, -60).fee_growth_outside0_x128.is_zero());
+}
+
+fn query_quote(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    msg: QueryMsg,
+) -> StdResult<QuoteResponse> {
+    from_binary(&query(deps.as_ref(), mock_env(), msg)?)
+}
+
+#[test]
+fn quotes_match_executed_swaps() {
+    let mut deps = setup();
+    for (tick_lower, tick_upper) in [(-600, 600), (-120, 120)] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)]),
+            mint_msg(tick_lower, tick_upper, 10_000_000),
+        )
+        .unwrap();
+    }
+    let slot0_before = query_slot0(&deps);
+
+    // enough to run through the inner position's lower tick, but not the outer one's
+    let quote = query_quote(
+        &deps,
+        QueryMsg::QuoteExactInput {
+            zero_for_one: true,
+            amount_in: Uint128::new(200_000),
+            sqrt_price_limit_x96: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(quote.amount_in, Uint128::new(200_000));
+    assert_eq!(quote.initialized_ticks_crossed, 1);
+    assert!(quote.gas_estimate > crate::quoter::GAS_BASE);
+    // quoting leaves the pool untouched
+    assert_eq!(query_slot0(&deps), slot0_before);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(200_000, TOKEN0)),
+        swap_msg(true, 200_000),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "amount1"), format!("-{}", quote.amount_out));
+    assert_eq!(
+        query_slot0(&deps).sqrt_price_x96,
+        quote.sqrt_price_x96_after
+    );
+    assert_eq!(query_liquidity(&deps), Uint128::new(10_000_000));
+
+    let quote = query_quote(
+        &deps,
+        QueryMsg::QuoteExactOutput {
+            zero_for_one: false,
+            amount_out: Uint128::new(50_000),
+            sqrt_price_limit_x96: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(quote.amount_out, Uint128::new(50_000));
+    assert_eq!(quote.initialized_ticks_crossed, 0);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(100_000, TOKEN1)),
+        swap_msg(false, -50_000),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "amount1"), quote.amount_in.to_string());
+    assert_eq!(
+        query_slot0(&deps).sqrt_price_x96,
+        quote.sqrt_price_x96_after
+    );
+
+    // a limit on the wrong side of the price is rejected like in a swap
+    let err = query_quote(
+        &deps,
+        QueryMsg::QuoteExactInput {
+            zero_for_one: true,
+            amount_in: Uint128::new(1_000),
+            sqrt_price_limit_x96: Some(MAX_SQRT_RATIO - Uint256::one()),
+        },
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::InvalidSqrtPriceLimit {}.to_string()));
+}
+
+#[test]
+fn burn_rejects_missing_and_excess_liquidity() {
+    let mut deps = setup();
+    let burn = |amount: u128| ExecuteMsg::Burn {
+        tick_lower: -600,
+        tick_upper: 600,
+        amount: Uint128::new(amount),
+    };
+
+    // poking a position that holds no liquidity
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn(0)).unwrap_err();
+    assert_eq!(err, ContractError::NoPosition {});
+    let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn(1)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientLiquidity {
+            liquidity: Uint128::zero(),
+            requested: Uint128::new(1),
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)]),
+        mint_msg(-600, 600, 1_000_000),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        burn(1_000_001),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientLiquidity {
+            liquidity: Uint128::new(1_000_000),
+            requested: Uint128::new(1_000_001),
+        }
+    );
+    assert_eq!(query_position(&deps, -600, 600).liquidity.u128(), 1_000_000);
+
+    // a poke of a position with liquidity succeeds
+    execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), burn(0)).unwrap();
+}
+
+#[test]
+fn burn_and_collect_return_liquidity() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)]),
+        mint_msg(-600, 600, 1_000_000),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Burn {
+            tick_lower: -600,
+            tick_upper: 600,
+            amount: Uint128::new(1_000_000),
+        },
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+    let owed0: u128 = attribute(&res, "amount0").parse().unwrap();
+    let owed1: u128 = attribute(&res, "amount1").parse().unwrap();
+
+    // the ticks are cleared once no position references them
+    assert_eq!(query_liquidity(&deps), Uint128::zero());
+    assert_eq!(query_tick(&deps, -600), TickInfo::default());
+    assert_eq!(query_tick(&deps, 600), TickInfo::default());
+    let word: Uint256 = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TickBitmap { word_position: -1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(word.is_zero());
+
+    let position = query_position(&deps, -600, 600);
+    assert_eq!(position.tokens_owed0.u128(), owed0);
+    assert_eq!(position.tokens_owed1.u128(), owed1);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Collect {
+            recipient: "carol".to_string(),
+            tick_lower: -600,
+            tick_upper: 600,
+            amount0_requested: Uint128::MAX,
+            amount1_requested: Uint128::new(10),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .iter()
+            .map(|m| m.msg.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "carol".to_string(),
+                amount: coins(owed0, TOKEN0),
+            }),
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "carol".to_string(),
+                amount: coins(10, TOKEN1),
+            }),
+        ]
+    );
+    let position = query_position(&deps, -600, 600);
+    assert!(position.tokens_owed0.is_zero());
+    assert_eq!(position.tokens_owed1.u128(), owed1 - 10);
+}
+
+/// Sets up a pool holding 1_000_000 of each token with liquidity in range and lends 10_000 token0 out of it
+fn setup_flash() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps =
+        mock_dependencies_with_balance(&[coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)]);
+    instantiate_pool(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Initialize {
+            sqrt_price_x96: FixedPoint96::Q96,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)]),
+        mint_msg(-600, 600, 1_000_000),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("borrower", &[]),
+        ExecuteMsg::Flash {
+            recipient: "borrower".to_string(),
+            amount0: Uint128::new(10_000),
+            amount1: Uint128::zero(),
+            data: Binary::default(),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "borrower".to_string(),
+            amount: coins(10_000, TOKEN0),
+        })
+    );
+    assert_eq!(res.messages[1].id, FLASH_REPLY_ID);
+    deps
+}
+
+fn flash_reply() -> Reply {
+    success_reply(FLASH_REPLY_ID)
+}
+
+fn success_reply(id: u64) -> Reply {
+    Reply {
+        id,
+        result: SubMsgResult::Ok(SubMsgResponse {
+            events: vec![],
+            data: None,
+        }),
+    }
+}
+
+#[test]
+fn flash_is_settled_in_reply() {
+    let mut deps = setup_flash();
+
+    // the pool stays locked while the loan is out
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(1000, TOKEN0)),
+        swap_msg(true, 1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Locked {});
+
+    // 0.3% of 10_000
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1_000_030, TOKEN0), coin(1_000_000, TOKEN1)],
+    );
+    let res = reply(deps.as_mut(), mock_env(), flash_reply()).unwrap();
+    assert_eq!(attribute(&res, "paid0"), "30");
+    assert!(query_slot0(&deps).unlocked);
+
+    let fee_growth: FeeGrowthGlobalResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::FeeGrowthGlobal {}).unwrap())
+            .unwrap();
+    assert!(!fee_growth.fee_growth_global0_x128.is_zero());
+    let protocol_fees: ProtocolFees =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ProtocolFees {}).unwrap()).unwrap();
+    assert_eq!(protocol_fees, ProtocolFees::default());
+
+    // the position earned the whole fee, less rounding
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Burn {
+            tick_lower: -600,
+            tick_upper: 600,
+            amount: Uint128::zero(),
+        },
+    )
+    .unwrap();
+    assert_eq!(query_position(&deps, -600, 600).tokens_owed0.u128(), 29);
+}
+
+#[test]
+fn flash_without_fee_fails() {
+    let mut deps = setup_flash();
+
+    // the principal came back but the fee did not
+    let err = reply(deps.as_mut(), mock_env(), flash_reply()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FlashNotRepaid {
+            denom: TOKEN0.to_string()
+        }
+    );
+}
+
+#[test]
+fn flash_requires_liquidity() {
+    let mut deps = setup();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("borrower", &[]),
+        ExecuteMsg::Flash {
+            recipient: "borrower".to_string(),
+            amount0: Uint128::new(1),
+            amount1: Uint128::zero(),
+            data: Binary::default(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoLiquidity {});
+}
+
+#[test]
+fn mint_with_data_is_paid_in_callback() {
+    let mut deps = mock_dependencies_with_balance(&[coin(1_000, TOKEN0), coin(1_000, TOKEN1)]);
+    instantiate_pool(deps.as_mut());
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::Initialize {
+            sqrt_price_x96: FixedPoint96::Q96,
+        },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        ExecuteMsg::Mint {
+            recipient: "alice".to_string(),
+            tick_lower: -60,
+            tick_upper: 60,
+            amount: Uint128::new(1_000_000),
+            data: Binary::from(b"position"),
+        },
+    )
+    .unwrap();
+    let owed0: u128 = attribute(&res, "amount0").parse().unwrap();
+    let owed1: u128 = attribute(&res, "amount1").parse().unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, MINT_CALLBACK_REPLY_ID);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "manager".to_string(),
+            msg: to_binary(&XionMintCallbackMsg::XionMintCallback {
+                amount0_owed: Uint256::from(owed0),
+                amount1_owed: Uint256::from(owed1),
+                data: Binary::from(b"position"),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert!(!query_slot0(&deps).unlocked);
+
+    // token1 came up one short
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1_000 + owed0, TOKEN0), coin(999 + owed1, TOKEN1)],
+    );
+    let err = reply(
+        deps.as_mut(),
+        mock_env(),
+        success_reply(MINT_CALLBACK_REPLY_ID),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InsufficientFunds {
+            denom: TOKEN1.to_string(),
+            owed: Uint128::new(owed1),
+            paid: Uint128::new(owed1 - 1),
+        }
+    );
+}
+
+#[test]
+fn swap_with_data_is_paid_in_callback() {
+    let mut deps = setup_flash();
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1_000_030, TOKEN0), coin(1_000_000, TOKEN1)],
+    );
+    reply(deps.as_mut(), mock_env(), flash_reply()).unwrap();
+
+    // funds can't be attached when paying in the callback
+    let mut msg = swap_msg(true, 1000);
+    if let ExecuteMsg::Swap { data, .. } = &mut msg {
+        *data = Binary::from(b"route");
+    }
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("router", &coins(1000, TOKEN0)),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::UnexpectedFunds {
+            denom: TOKEN0.to_string()
+        }
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("router", &[]), msg).unwrap();
+    assert_eq!(attribute(&res, "amount0"), "1000");
+    let amount1: i128 = attribute(&res, "amount1").parse().unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "bob".to_string(),
+            amount: coins((-amount1) as u128, TOKEN1),
+        })
+    );
+    assert_eq!(res.messages[1].id, SWAP_CALLBACK_REPLY_ID);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router".to_string(),
+            msg: to_binary(&XionSwapCallbackMsg::XionSwapCallback {
+                amount0_delta: Int256::from(1000),
+                amount1_delta: Int256::from(amount1),
+                data: Binary::from(b"route"),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    // nothing else can touch the pool until the payment is checked
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Burn {
+            tick_lower: -600,
+            tick_upper: 600,
+            amount: Uint128::zero(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Locked {});
+
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1_001_030, TOKEN0), coin(1_000_000, TOKEN1)],
+    );
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        success_reply(SWAP_CALLBACK_REPLY_ID),
+    )
+    .unwrap();
+    assert_eq!(attribute(&res, "paid0"), "1000");
+    assert!(query_slot0(&deps).unlocked);
+}
+
+#[test]
+fn flash_fees_are_shared_with_protocol() {
+    let mut deps = setup_flash();
+    // a 1/4 protocol fee on token0, 1/10 on token1
+    SLOT0
+        .update(deps.as_mut().storage, |mut slot0| -> StdResult<_> {
+            slot0.fee_protocol = 4 + (10 << 4);
+            Ok(slot0)
+        })
+        .unwrap();
+
+    deps.querier.update_balance(
+        MOCK_CONTRACT_ADDR,
+        vec![coin(1_000_030, TOKEN0), coin(1_000_000, TOKEN1)],
+    );
+    reply(deps.as_mut(), mock_env(), flash_reply()).unwrap();
+
+    let protocol_fees: ProtocolFees =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ProtocolFees {}).unwrap()).unwrap();
+    assert_eq!(
+        protocol_fees,
+        ProtocolFees {
+            token0: Uint128::new(7),
+            token1: Uint128::zero(),
+        }
+    );
+}
+
+/// A borrower that pays the pool back whatever coins it was told to in the callback data
+fn borrower_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(
+        |_, _, info: MessageInfo, msg: XionFlashCallbackMsg| -> StdResult<Response> {
+            let XionFlashCallbackMsg::XionFlashCallback { data, .. } = msg;
+            let repay: Vec<Coin> = from_binary(&data)?;
+            Ok(Response::new().add_message(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: repay,
+            }))
+        },
+        |_, _, _, _: Empty| -> StdResult<Response> { Ok(Response::new()) },
+        |_, _, _: Empty| -> StdResult<Binary> { Ok(Binary::default()) },
+    ))
+}
+
+fn pool_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+}
+
+#[test]
+fn flash_loan_round_trip() {
+    let mut app = App::new(|router, _, storage| {
+        router
+            .bank
+            .init_balance(
+                storage,
+                &Addr::unchecked("alice"),
+                vec![coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)],
+            )
+            .unwrap();
+    });
+    let pool_code_id = app.store_code(pool_contract());
+    let borrower_code_id = app.store_code(borrower_contract());
+    let pool = app
+        .instantiate_contract(
+            pool_code_id,
+            Addr::unchecked("factory"),
+            &InstantiateMsg {
+                token0: TOKEN0.to_string(),
+                token1: TOKEN1.to_string(),
+                fee: FEE,
+                tick_spacing: TICK_SPACING,
+            },
+            &[],
+            "pool",
+            None,
+        )
+        .unwrap();
+    let borrower = app
+        .instantiate_contract(
+            borrower_code_id,
+            Addr::unchecked("arb"),
+            &Empty {},
+            &[],
+            "borrower",
+            None,
+        )
+        .unwrap();
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        pool.clone(),
+        &ExecuteMsg::Initialize {
+            sqrt_price_x96: FixedPoint96::Q96,
+        },
+        &[],
+    )
+    .unwrap();
+    app.execute_contract(
+        Addr::unchecked("alice"),
+        pool.clone(),
+        &mint_msg(-600, 600, 1_000_000),
+        &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)],
+    )
+    .unwrap();
+    // the fee has to come from the borrower's own pocket
+    app.send_tokens(
+        Addr::unchecked("alice"),
+        borrower.clone(),
+        &[coin(100, TOKEN1)],
+    )
+    .unwrap();
+
+    let flash = |repay: Vec<Coin>| ExecuteMsg::Flash {
+        recipient: borrower.to_string(),
+        amount0: Uint128::zero(),
+        amount1: Uint128::new(10_000),
+        data: to_binary(&repay).unwrap(),
+    };
+
+    // paying back the principal alone reverts the loan
+    let err = app
+        .execute_contract(
+            Addr::unchecked("arb"),
+            pool.clone(),
+            &flash(coins(10_000, TOKEN1)),
+            &[],
+        )
+        .unwrap_err();
+    // errors raised in a reply reach the caller as text
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::FlashNotRepaid {
+            denom: TOKEN1.to_string()
+        }
+        .to_string()
+    );
+    assert_eq!(
+        app.wrap().query_balance(&borrower, TOKEN1).unwrap().amount,
+        Uint128::new(100)
+    );
+
+    app.execute_contract(
+        Addr::unchecked("arb"),
+        pool.clone(),
+        &flash(coins(10_030, TOKEN1)),
+        &[],
+    )
+    .unwrap();
+    assert_eq!(
+        app.wrap().query_balance(&borrower, TOKEN1).unwrap().amount,
+        Uint128::new(70)
+    );
+    let slot0: Slot0 = app
+        .wrap()
+        .query_wasm_smart(&pool, &QueryMsg::Slot0 {})
+        .unwrap();
+    assert!(slot0.unlocked);
+    let fee_growth: FeeGrowthGlobalResponse = app
+        .wrap()
+        .query_wasm_smart(&pool, &QueryMsg::FeeGrowthGlobal {})
+        .unwrap();
+    assert!(fee_growth.fee_growth_global0_x128.is_zero());
+    assert!(!fee_growth.fee_growth_global1_x128.is_zero());
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+#[test]
+fn observe_returns_time_weighted_ticks() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)]),
+        mint_msg(-600, 600, 10_000_000),
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::IncreaseObservationCardinalityNext {
+            observation_cardinality_next: 10,
+        },
+    )
+    .unwrap();
+
+    // the price sits at tick 0 for 100 seconds, then at the tick the swap moves it to
+    execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("bob", &coins(200_000, TOKEN0)),
+        swap_msg(true, 200_000),
+    )
+    .unwrap();
+    let tick = query_slot0(&deps).tick;
+    assert!(tick < 0);
+
+    let observe: ObserveResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(300),
+            QueryMsg::Observe {
+                seconds_agos: vec![300, 250, 200, 0],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(observe.tick_cumulatives, vec![0, 0, 0, tick as i64 * 200]);
+    assert!(observe.seconds_per_liquidity_cumulative_x128s[0].is_zero());
+    // the interpolated value sits halfway between the first two observations
+    assert_eq!(
+        observe.seconds_per_liquidity_cumulative_x128s[1] * Uint256::from(2u8),
+        observe.seconds_per_liquidity_cumulative_x128s[2]
+    );
+
+    let mean_tick = |window| -> ArithmeticMeanTickResponse {
+        from_binary(
+            &query(
+                deps.as_ref(),
+                env_at(300),
+                QueryMsg::ArithmeticMeanTick { window },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+    assert_eq!(mean_tick(200).arithmetic_mean_tick, tick);
+    assert_eq!(mean_tick(100).arithmetic_mean_tick, tick);
+    // rounded towards negative infinity
+    assert_eq!(
+        mean_tick(300).arithmetic_mean_tick,
+        (tick as i64 * 200).div_euclid(300) as i32
+    );
+
+    // nothing is known from before the pool was initialized
+    query(
+        deps.as_ref(),
+        env_at(300),
+        QueryMsg::Observe {
+            seconds_agos: vec![301],
+        },
+    )
+    .unwrap_err();
+    query(
+        deps.as_ref(),
+        env_at(300),
+        QueryMsg::ArithmeticMeanTick { window: 0 },
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn increase_observation_cardinality_prewrites_slots() {
+    let mut deps = setup();
+    let increase = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, next| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("anyone", &[]),
+            ExecuteMsg::IncreaseObservationCardinalityNext {
+                observation_cardinality_next: next,
+            },
+        )
+    };
+
+    let res = increase(&mut deps, 5).unwrap();
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].ty, "increase_observation_cardinality_next");
+    assert_eq!(res.events[0].attributes[0].value, "1");
+    assert_eq!(res.events[0].attributes[1].value, "5");
+    let slot0 = query_slot0(&deps);
+    assert_eq!(slot0.observation_cardinality, 1);
+    assert_eq!(slot0.observation_cardinality_next, 5);
+
+    // the new slots hold placeholders that are never read as observations
+    for index in 1..5 {
+        let observation: Observation = from_binary(
+            &query(deps.as_ref(), mock_env(), QueryMsg::Observation { index }).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(observation.block_timestamp, 1);
+        assert!(!observation.initialized);
+    }
+
+    // shrinking is a no-op without an event
+    let res = increase(&mut deps, 3).unwrap();
+    assert!(res.events.is_empty());
+    assert_eq!(query_slot0(&deps).observation_cardinality_next, 5);
+
+    let err = increase(&mut deps, 1006).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ObservationCardinalityGrowthTooLarge { max: 1000 }
+    );
+    increase(&mut deps, 1005).unwrap();
+    assert_eq!(query_slot0(&deps).observation_cardinality_next, 1005);
+
+    // observations fill the pre-written slots as time passes, and the oldest one stays observable
+    for seconds in 1..=5 {
+        execute(
+            deps.as_mut(),
+            env_at(seconds * 10),
+            mock_info("alice", &[coin(1_000, TOKEN0), coin(1_000, TOKEN1)]),
+            mint_msg(-60, 60, 1_000),
+        )
+        .unwrap();
+    }
+    let slot0 = query_slot0(&deps);
+    assert_eq!(slot0.observation_index, 5);
+    assert_eq!(slot0.observation_cardinality, 1005);
+    let observe: ObserveResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(50),
+            QueryMsg::Observe {
+                seconds_agos: vec![50, 25],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(observe.tick_cumulatives, vec![0, 0]);
+}
+
+#[test]
+fn snapshot_cumulatives_inside_tracks_time_in_range() {
+    let mut deps = setup();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)]),
+        mint_msg(-60, 60, 1_000_000),
+    )
+    .unwrap();
+    let snapshot = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, seconds| {
+        query(
+            deps.as_ref(),
+            env_at(seconds),
+            QueryMsg::SnapshotCumulativesInside {
+                tick_lower: -60,
+                tick_upper: 60,
+            },
+        )
+        .map(|res| from_binary::<SnapshotCumulativesInsideResponse>(&res).unwrap())
+    };
+
+    let start = snapshot(&deps, 0).unwrap();
+    let in_range = snapshot(&deps, 100).unwrap();
+    assert_eq!(in_range.seconds_inside - start.seconds_inside, 100);
+    assert_eq!(
+        in_range.tick_cumulative_inside,
+        start.tick_cumulative_inside
+    );
+    assert!(in_range.seconds_per_liquidity_inside_x128 > start.seconds_per_liquidity_inside_x128);
+
+    // push the price below the range; time stops accruing inside it
+    execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("bob", &coins(1_000_000, TOKEN0)),
+        swap_msg(true, 1_000_000),
+    )
+    .unwrap();
+    assert!(query_slot0(&deps).tick < -60);
+    let below = snapshot(&deps, 150).unwrap();
+    assert_eq!(below.seconds_inside - start.seconds_inside, 100);
+    assert_eq!(
+        below.seconds_per_liquidity_inside_x128,
+        in_range.seconds_per_liquidity_inside_x128
+    );
+
+    // both ends of the range have to be referenced by a position
+    let err = query(
+        deps.as_ref(),
+        env_at(150),
+        QueryMsg::SnapshotCumulativesInside {
+            tick_lower: -120,
+            tick_upper: 60,
+        },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Tick -120 is not initialized"));
+}
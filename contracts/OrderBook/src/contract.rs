@@ -0,0 +1,858 @@
+// SPDX-License-Identifier: MIT
+
+//! Central limit order book
+//! Limit orders for any token are priced in a single quote denom and matched in price-time priority:
+//! the best price fills first and, within a price level, the oldest order. Fills execute at the resting (maker) order's price.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Api, Binary, CosmosMsg, Deps, DepsMut, Env, Event, MessageInfo,
+    Order as SortOrder, Response, StdResult, Storage, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::{must_pay, nonpayable};
+use libraries::transfer_helper::safe_transfer;
+
+use crate::error::ContractError;
+use crate::msg::{
+    AssetInfo, BookCursor, ConfigResponse, DepthResponse, ExecuteMsg, InstantiateMsg,
+    LockedBalance, LockedBalancesResponse, OrderResponse, OrderType, OrdersResponse, PriceLevel,
+    QueryMsg, ReceiveMsg, TimeInForce, TradeResponse, TradesResponse,
+};
+use crate::state::{
+    lock, next_id, orders, price_key, trades, unlock, Config, Order, Trade, CONFIG, LOCKED,
+    ORDER_COUNT, TRADE_COUNT,
+};
+
+/// The most fills and expiries a single `MatchOrders` crank or order placement processes
+pub(crate) const MAX_CRANK_STEPS: usize = 50;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+/// Price levels per side returned by `Depth` by default, and at most
+const DEFAULT_DEPTH: u32 = 10;
+const MAX_DEPTH: u32 = 50;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    if msg.quote_denom.is_empty() {
+        return Err(ContractError::InvalidToken {});
+    }
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            quote_denom: msg.quote_denom.clone(),
+        },
+    )?;
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("quote_denom", msg.quote_denom))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::AddOrder {
+            token,
+            amount,
+            price,
+            order_type,
+            time_in_force,
+        } => add_order(
+            deps,
+            env,
+            info,
+            token,
+            amount,
+            price,
+            order_type,
+            time_in_force,
+        ),
+        ExecuteMsg::CancelOrder { order_id } => cancel_order(deps, info, order_id),
+        ExecuteMsg::AmendOrder {
+            order_id,
+            price,
+            amount,
+        } => amend_order(deps, env, info, order_id, price, amount),
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+        ExecuteMsg::MatchOrders { token } => match_orders(deps, env, token),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Order { order_id } => to_binary(&query_order(deps, order_id)?),
+        QueryMsg::Depth { token, limit } => to_binary(&query_depth(deps, env, token, limit)?),
+        QueryMsg::Orders {
+            token,
+            order_type,
+            start_after,
+            limit,
+        } => to_binary(&query_orders(
+            deps,
+            env,
+            token,
+            order_type,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::OrdersByTrader {
+            trader,
+            start_after,
+            limit,
+        } => to_binary(&query_orders_by_trader(deps, trader, start_after, limit)?),
+        QueryMsg::Trades {
+            token,
+            start_after,
+            limit,
+        } => to_binary(&query_trades(deps, token, start_after, limit)?),
+        QueryMsg::LockedBalances { trader } => to_binary(&query_locked_balances(deps, trader)?),
+    }
+}
+
+/// Places an order backed by the native funds attached to the message
+#[allow(clippy::too_many_arguments)]
+fn add_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: AssetInfo,
+    amount: Uint128,
+    price: Uint128,
+    order_type: OrderType,
+    time_in_force: TimeInForce,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let token = validate_order(
+        deps.api,
+        &env,
+        &config,
+        token,
+        amount,
+        price,
+        &time_in_force,
+    )?;
+    let order = new_order(
+        deps.storage,
+        info.sender.clone(),
+        token,
+        order_type,
+        price,
+        amount,
+        time_in_force,
+    )?;
+    check_escrow_funds(&info, &config, &order, order.locked)?;
+
+    let mut payouts = Payouts::default();
+    let (order, filled, events) = place_order(deps.storage, &env, &config, order, &mut payouts)?;
+    Ok(order_response_attributes(
+        Response::new()
+            .add_messages(payouts.into_msgs()?)
+            .add_events(events)
+            .add_attribute("action", "add_order")
+            .add_attribute("order_id", order.id.to_string()),
+        &order,
+        filled,
+    ))
+}
+
+/// Places a sell order for CW20 tokens sent to the order book
+fn receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let trader = deps.api.addr_validate(&msg.sender)?;
+    let token = AssetInfo::Cw20 {
+        contract_addr: info.sender.into_string(),
+    };
+
+    match from_binary(&msg.msg)? {
+        ReceiveMsg::AddOrder {
+            price,
+            time_in_force,
+        } => {
+            let token = validate_order(
+                deps.api,
+                &env,
+                &config,
+                token,
+                msg.amount,
+                price,
+                &time_in_force,
+            )?;
+            let order = new_order(
+                deps.storage,
+                trader,
+                token,
+                OrderType::Sell,
+                price,
+                msg.amount,
+                time_in_force,
+            )?;
+
+            let mut payouts = Payouts::default();
+            let (order, filled, events) =
+                place_order(deps.storage, &env, &config, order, &mut payouts)?;
+            Ok(order_response_attributes(
+                Response::new()
+                    .add_messages(payouts.into_msgs()?)
+                    .add_events(events)
+                    .add_attribute("action", "add_order")
+                    .add_attribute("order_id", order.id.to_string()),
+                &order,
+                filled,
+            ))
+        }
+    }
+}
+
+fn cancel_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut order = load_own_order(deps.storage, &info, order_id)?;
+
+    let mut payouts = Payouts::default();
+    order.remaining = Uint128::zero();
+    let refund = order.locked;
+    save_order(deps.storage, &config, &mut order, &mut payouts)?;
+
+    Ok(Response::new()
+        .add_messages(payouts.into_msgs()?)
+        .add_attribute("action", "cancel_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("trader", order.trader)
+        .add_attribute("refund", refund))
+}
+
+fn amend_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: u64,
+    price: Option<Uint128>,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut order = load_own_order(deps.storage, &info, order_id)?;
+    if order.time_in_force.is_expired(&env.block) {
+        return Err(ContractError::OrderExpired {});
+    }
+    let price = price.unwrap_or(order.price);
+    let amount = amount.unwrap_or(order.remaining);
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    if price.is_zero() {
+        return Err(ContractError::ZeroPrice {});
+    }
+
+    let mut payouts = Payouts::default();
+    let res = Response::new()
+        .add_attribute("action", "amend_order")
+        .add_attribute("order_id", order_id.to_string());
+
+    // shrinking in place keeps the order's place in the queue, the excess escrow is refunded
+    if price == order.price && amount <= order.remaining {
+        nonpayable(&info)?;
+        order.remaining = amount;
+        save_order(deps.storage, &config, &mut order, &mut payouts)?;
+        return Ok(order_response_attributes(
+            res.add_messages(payouts.into_msgs()?),
+            &order,
+            Uint128::zero(),
+        ));
+    }
+
+    // anything else goes to the back of the queue as a new order, funded by the old escrow plus any top up
+    let replacement = new_order(
+        deps.storage,
+        order.trader.clone(),
+        order.token.clone(),
+        order.order_type,
+        price,
+        amount,
+        order.time_in_force.clone(),
+    )?;
+    check_escrow_funds(
+        &info,
+        &config,
+        &order,
+        replacement.locked.saturating_sub(order.locked),
+    )?;
+    let escrow = escrow_token(&config, &order);
+    unlock(
+        deps.storage,
+        &order.trader,
+        &escrow.to_string(),
+        order.locked,
+    )?;
+    payouts.add(
+        &order.trader,
+        &escrow,
+        order.locked.saturating_sub(replacement.locked),
+    );
+    orders().remove(deps.storage, order.id)?;
+
+    let (replacement, filled, events) =
+        place_order(deps.storage, &env, &config, replacement, &mut payouts)?;
+    Ok(order_response_attributes(
+        res.add_messages(payouts.into_msgs()?)
+            .add_events(events)
+            .add_attribute("new_order_id", replacement.id.to_string()),
+        &replacement,
+        filled,
+    ))
+}
+
+/// Loads an order of the message sender
+fn load_own_order(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+    order_id: u64,
+) -> Result<Order, ContractError> {
+    let order = orders()
+        .may_load(storage, order_id)?
+        .ok_or(ContractError::OrderNotFound { order_id })?;
+    if order.trader != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(order)
+}
+
+/// Checks an order's parameters, returning the token with its address validated
+fn validate_order(
+    api: &dyn Api,
+    env: &Env,
+    config: &Config,
+    token: AssetInfo,
+    amount: Uint128,
+    price: Uint128,
+    time_in_force: &TimeInForce,
+) -> Result<AssetInfo, ContractError> {
+    let token = match token {
+        AssetInfo::Native { denom } if denom.is_empty() || denom == config.quote_denom => {
+            return Err(ContractError::InvalidToken {})
+        }
+        AssetInfo::Native { denom } => AssetInfo::Native { denom },
+        AssetInfo::Cw20 { contract_addr } => AssetInfo::Cw20 {
+            contract_addr: api.addr_validate(&contract_addr)?.into_string(),
+        },
+    };
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    if price.is_zero() {
+        return Err(ContractError::ZeroPrice {});
+    }
+    if time_in_force.is_expired(&env.block) {
+        return Err(ContractError::OrderExpired {});
+    }
+    Ok(token)
+}
+
+/// Builds a new order under the next id, holding the escrow its amount needs
+fn new_order(
+    storage: &mut dyn Storage,
+    trader: Addr,
+    token: AssetInfo,
+    order_type: OrderType,
+    price: Uint128,
+    amount: Uint128,
+    time_in_force: TimeInForce,
+) -> Result<Order, ContractError> {
+    let mut order = Order {
+        id: next_id(storage, &ORDER_COUNT)?,
+        trader,
+        token,
+        order_type,
+        price,
+        amount,
+        remaining: amount,
+        locked: Uint128::zero(),
+        time_in_force,
+    };
+    order.locked = order.required_escrow()?;
+    Ok(order)
+}
+
+/// Checks the attached funds add exactly `required` to an order's escrow
+fn check_escrow_funds(
+    info: &MessageInfo,
+    config: &Config,
+    order: &Order,
+    required: Uint128,
+) -> Result<(), ContractError> {
+    if required.is_zero() {
+        nonpayable(info)?;
+        return Ok(());
+    }
+    let AssetInfo::Native { denom } = escrow_token(config, order) else {
+        return Err(ContractError::Cw20NotReceived {});
+    };
+    let received = must_pay(info, &denom)?;
+    if received != required {
+        return Err(ContractError::FundsMismatch {
+            expected: required,
+            received,
+        });
+    }
+    Ok(())
+}
+
+/// Locks a new order's escrow, matches it against the book under its time in force and rests whatever is left
+/// Matching stops after `MAX_CRANK_STEPS` fills and expiries, so that dust or expired orders can't exhaust the gas
+/// of a large order; a remainder that still crosses the book then rests for the `MatchOrders` crank
+/// Returns the order, the amount it filled and the fill and expiry events
+fn place_order(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    mut order: Order,
+    payouts: &mut Payouts,
+) -> Result<(Order, Uint128, Vec<Event>), ContractError> {
+    let escrow = escrow_token(config, &order).to_string();
+    lock(storage, &order.trader, &escrow, order.locked)?;
+
+    let token = order.token.to_string();
+    let mut events = vec![];
+    while !order.remaining.is_zero() && events.len() < MAX_CRANK_STEPS {
+        let Some(mut maker) = best_order(storage, &token, order.order_type.opposite())? else {
+            break;
+        };
+        if maker.time_in_force.is_expired(&env.block) {
+            events.push(expire_order(storage, config, &mut maker, payouts)?);
+            continue;
+        }
+        if !crosses(&order, &maker) {
+            break;
+        }
+        events.push(fill(storage, env, config, &mut maker, &mut order, payouts)?);
+        save_order(storage, config, &mut maker, payouts)?;
+    }
+
+    let filled = order.amount - order.remaining;
+    match order.time_in_force {
+        TimeInForce::FillOrKill if !order.remaining.is_zero() => {
+            return Err(ContractError::FillOrKillNotFilled {})
+        }
+        // whatever didn't fill right away is cancelled
+        TimeInForce::ImmediateOrCancel => order.remaining = Uint128::zero(),
+        _ => {}
+    }
+    save_order(storage, config, &mut order, payouts)?;
+    Ok((order, filled, events))
+}
+
+fn match_orders(deps: DepsMut, env: Env, token: String) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut payouts = Payouts::default();
+    let mut events = vec![];
+    let mut fills = 0;
+    while events.len() < MAX_CRANK_STEPS {
+        let (Some(mut bid), Some(mut ask)) = (
+            best_order(deps.storage, &token, OrderType::Buy)?,
+            best_order(deps.storage, &token, OrderType::Sell)?,
+        ) else {
+            break;
+        };
+        if bid.time_in_force.is_expired(&env.block) {
+            events.push(expire_order(deps.storage, &config, &mut bid, &mut payouts)?);
+            continue;
+        }
+        if ask.time_in_force.is_expired(&env.block) {
+            events.push(expire_order(deps.storage, &config, &mut ask, &mut payouts)?);
+            continue;
+        }
+        if bid.price < ask.price {
+            break;
+        }
+        // the order that rested first provides the liquidity
+        let (mut maker, mut taker) = if bid.id < ask.id {
+            (bid, ask)
+        } else {
+            (ask, bid)
+        };
+        events.push(fill(
+            deps.storage,
+            &env,
+            &config,
+            &mut maker,
+            &mut taker,
+            &mut payouts,
+        )?);
+        save_order(deps.storage, &config, &mut maker, &mut payouts)?;
+        save_order(deps.storage, &config, &mut taker, &mut payouts)?;
+        fills += 1;
+    }
+
+    Ok(Response::new()
+        .add_messages(payouts.into_msgs()?)
+        .add_attribute("action", "match_orders")
+        .add_attribute("token", token)
+        .add_attribute("fills", fills.to_string())
+        .add_events(events))
+}
+
+/// Removes an order whose time in force has run out and refunds its escrow
+fn expire_order(
+    storage: &mut dyn Storage,
+    config: &Config,
+    order: &mut Order,
+    payouts: &mut Payouts,
+) -> StdResult<Event> {
+    let refund = order.locked;
+    order.remaining = Uint128::zero();
+    save_order(storage, config, order, payouts)?;
+    Ok(Event::new("order_expired")
+        .add_attribute("order_id", order.id.to_string())
+        .add_attribute("trader", order.trader.as_str())
+        .add_attribute("refund", refund))
+}
+
+/// The order at the front of one side of `token`'s book
+fn best_order(
+    storage: &dyn Storage,
+    token: &str,
+    order_type: OrderType,
+) -> StdResult<Option<Order>> {
+    orders()
+        .idx
+        .book
+        .sub_prefix((token.to_string(), order_type.side()))
+        .range(storage, None, None, SortOrder::Ascending)
+        .next()
+        .transpose()
+        .map(|order| order.map(|(_, order)| order))
+}
+
+/// Whether the taker's limit price reaches the maker's price
+fn crosses(taker: &Order, maker: &Order) -> bool {
+    match taker.order_type {
+        OrderType::Buy => taker.price >= maker.price,
+        OrderType::Sell => taker.price <= maker.price,
+    }
+}
+
+/// Fills as much of both orders as possible at the maker's price, releasing the exchanged escrow to the
+/// counterparties, records the trade and returns its fill event
+fn fill(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    maker: &mut Order,
+    taker: &mut Order,
+    payouts: &mut Payouts,
+) -> Result<Event, ContractError> {
+    let amount = maker.remaining.min(taker.remaining);
+    let price = maker.price;
+    let quote = amount.checked_mul(price)?;
+    let event = Event::new("fill")
+        .add_attribute("price", price)
+        .add_attribute("amount", amount)
+        .add_attribute("maker_order_id", maker.id.to_string())
+        .add_attribute("taker_order_id", taker.id.to_string())
+        .add_attribute("maker", maker.trader.as_str())
+        .add_attribute("taker", taker.trader.as_str())
+        .add_attribute("taker_side", side_name(taker.order_type));
+    let maker_order_id = maker.id;
+
+    let (buy, sell) = match taker.order_type {
+        OrderType::Buy => (taker, maker),
+        OrderType::Sell => (maker, taker),
+    };
+    buy.remaining -= amount;
+    buy.locked = buy.locked.checked_sub(quote)?;
+    unlock(storage, &buy.trader, &config.quote_denom, quote)?;
+    payouts.add(&buy.trader, &sell.token, amount);
+
+    sell.remaining -= amount;
+    sell.locked = sell.locked.checked_sub(amount)?;
+    unlock(storage, &sell.trader, &sell.token.to_string(), amount)?;
+    payouts.add(&sell.trader, &quote_asset(config), quote);
+
+    let trade = Trade {
+        id: next_id(storage, &TRADE_COUNT)?,
+        token: sell.token.clone(),
+        buy_order_id: buy.id,
+        sell_order_id: sell.id,
+        maker_order_id,
+        buyer: buy.trader.clone(),
+        seller: sell.trader.clone(),
+        price,
+        amount,
+        height: env.block.height,
+    };
+    trades().save(storage, trade.id, &trade)?;
+
+    Ok(event
+        .add_attribute("trade_id", trade.id.to_string())
+        .add_attribute("token", trade.token.to_string()))
+}
+
+/// Persists an order, refunding any escrow beyond what its open amount needs, such as a buyer's price
+/// improvement; an order with nothing left open is dropped from the book
+fn save_order(
+    storage: &mut dyn Storage,
+    config: &Config,
+    order: &mut Order,
+    payouts: &mut Payouts,
+) -> StdResult<()> {
+    let excess = order.locked.checked_sub(order.required_escrow()?)?;
+    if !excess.is_zero() {
+        let token = escrow_token(config, order);
+        unlock(storage, &order.trader, &token.to_string(), excess)?;
+        payouts.add(&order.trader, &token, excess);
+        order.locked -= excess;
+    }
+    if order.remaining.is_zero() {
+        orders().remove(storage, order.id)
+    } else {
+        orders().save(storage, order.id, order)
+    }
+}
+
+/// The token an order escrows: quote for buys, the traded token for sells
+fn escrow_token(config: &Config, order: &Order) -> AssetInfo {
+    match order.order_type {
+        OrderType::Buy => quote_asset(config),
+        OrderType::Sell => order.token.clone(),
+    }
+}
+
+fn quote_asset(config: &Config) -> AssetInfo {
+    AssetInfo::Native {
+        denom: config.quote_denom.clone(),
+    }
+}
+
+/// Transfers owed by an execution, merged per recipient and token
+#[derive(Default)]
+struct Payouts(Vec<(Addr, AssetInfo, Uint128)>);
+
+impl Payouts {
+    fn add(&mut self, recipient: &Addr, token: &AssetInfo, amount: Uint128) {
+        if amount.is_zero() {
+            return;
+        }
+        match self
+            .0
+            .iter_mut()
+            .find(|(to, asset, _)| to == recipient && asset == token)
+        {
+            Some((_, _, total)) => *total += amount,
+            None => self.0.push((recipient.clone(), token.clone(), amount)),
+        }
+    }
+
+    fn into_msgs(self) -> StdResult<Vec<CosmosMsg>> {
+        self.0
+            .into_iter()
+            .map(|(recipient, token, amount)| safe_transfer(&token, &recipient, amount))
+            .collect()
+    }
+}
+
+/// Describes the order an execution placed or amended
+fn order_response_attributes(res: Response, order: &Order, filled: Uint128) -> Response {
+    res.add_attribute("trader", order.trader.as_str())
+        .add_attribute("token", order.token.to_string())
+        .add_attribute("side", side_name(order.order_type))
+        .add_attribute("price", order.price)
+        .add_attribute("amount", order.amount)
+        .add_attribute("filled", filled)
+        .add_attribute("remaining", order.remaining)
+}
+
+fn side_name(order_type: OrderType) -> &'static str {
+    match order_type {
+        OrderType::Buy => "buy",
+        OrderType::Sell => "sell",
+    }
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        quote_denom: config.quote_denom,
+    })
+}
+
+fn query_order(deps: Deps, order_id: u64) -> StdResult<OrderResponse> {
+    orders().load(deps.storage, order_id).map(order_response)
+}
+
+/// The resting orders on one side of `token`'s book in priority order, expired orders skipped
+fn book_orders<'a>(
+    deps: Deps<'a>,
+    env: &'a Env,
+    token: &str,
+    order_type: OrderType,
+    start_after: Option<BookCursor>,
+) -> impl Iterator<Item = StdResult<Order>> + 'a {
+    let start = start_after
+        .map(|cursor| Bound::exclusive((price_key(order_type, cursor.price), cursor.order_id)));
+    orders()
+        .idx
+        .book
+        .sub_prefix((token.to_string(), order_type.side()))
+        .range(deps.storage, start, None, SortOrder::Ascending)
+        .map(|item| item.map(|(_, order)| order))
+        .filter(move |item| {
+            item.as_ref()
+                .map_or(true, |order| !order.time_in_force.is_expired(&env.block))
+        })
+}
+
+fn query_depth(
+    deps: Deps,
+    env: Env,
+    token: String,
+    limit: Option<u32>,
+) -> StdResult<DepthResponse> {
+    let limit = limit.unwrap_or(DEFAULT_DEPTH).min(MAX_DEPTH) as usize;
+    let depth = |order_type| -> StdResult<Vec<PriceLevel>> {
+        let mut levels: Vec<PriceLevel> = vec![];
+        for order in book_orders(deps, &env, &token, order_type, None) {
+            let order = order?;
+            if let Some(level) = levels.last_mut().filter(|level| level.price == order.price) {
+                level.amount += order.remaining;
+                level.orders += 1;
+                continue;
+            }
+            if levels.len() == limit {
+                break;
+            }
+            levels.push(PriceLevel {
+                price: order.price,
+                amount: order.remaining,
+                orders: 1,
+            });
+        }
+        Ok(levels)
+    };
+    Ok(DepthResponse {
+        bids: depth(OrderType::Buy)?,
+        asks: depth(OrderType::Sell)?,
+    })
+}
+
+fn query_orders(
+    deps: Deps,
+    env: Env,
+    token: String,
+    order_type: OrderType,
+    start_after: Option<BookCursor>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let orders = book_orders(deps, &env, &token, order_type, start_after)
+        .take(limit)
+        .map(|item| item.map(order_response))
+        .collect::<StdResult<_>>()?;
+    Ok(OrdersResponse { orders })
+}
+
+fn query_orders_by_trader(
+    deps: Deps,
+    trader: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let trader = deps.api.addr_validate(&trader)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let orders = orders()
+        .idx
+        .trader
+        .prefix(trader)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            SortOrder::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, order)| order_response(order)))
+        .collect::<StdResult<_>>()?;
+    Ok(OrdersResponse { orders })
+}
+
+fn query_locked_balances(deps: Deps, trader: String) -> StdResult<LockedBalancesResponse> {
+    let trader = deps.api.addr_validate(&trader)?;
+    let balances = LOCKED
+        .prefix(&trader)
+        .range(deps.storage, None, None, SortOrder::Ascending)
+        .map(|item| item.map(|(token, amount)| LockedBalance { token, amount }))
+        .collect::<StdResult<_>>()?;
+    Ok(LockedBalancesResponse { trader, balances })
+}
+
+fn query_trades(
+    deps: Deps,
+    token: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<TradesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let trades = trades()
+        .idx
+        .token
+        .prefix(token)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            SortOrder::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, trade)| trade_response(trade)))
+        .collect::<StdResult<_>>()?;
+    Ok(TradesResponse { trades })
+}
+
+fn order_response(order: Order) -> OrderResponse {
+    OrderResponse {
+        order_id: order.id,
+        trader: order.trader,
+        token: order.token,
+        amount: order.remaining,
+        price: order.price,
+        order_type: order.order_type,
+        time_in_force: order.time_in_force,
+        locked: order.locked,
+    }
+}
+
+fn trade_response(trade: Trade) -> TradeResponse {
+    TradeResponse {
+        trade_id: trade.id,
+        token: trade.token,
+        buy_order_id: trade.buy_order_id,
+        sell_order_id: trade.sell_order_id,
+        maker_order_id: trade.maker_order_id,
+        buyer: trade.buyer,
+        seller: trade.seller,
+        price: trade.price,
+        amount: trade.amount,
+        height: trade.height,
+    }
+}
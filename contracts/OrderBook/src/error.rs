@@ -0,0 +1,45 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Orders must be for a token other than the quote denom")]
+    InvalidToken {},
+
+    #[error("Order amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("Order price must be greater than zero")]
+    ZeroPrice {},
+
+    #[error("Order needs exactly {expected} in escrow, received {received}")]
+    FundsMismatch {
+        expected: Uint128,
+        received: Uint128,
+    },
+
+    #[error("CW20 tokens are sold by sending them to the order book")]
+    Cw20NotReceived {},
+
+    #[error("Order has expired")]
+    OrderExpired {},
+
+    #[error("Fill-or-kill order could not be filled completely")]
+    FillOrKillNotFilled {},
+
+    #[error("Order {order_id} not found")]
+    OrderNotFound { order_id: u64 },
+}
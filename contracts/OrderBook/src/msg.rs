@@ -0,0 +1,230 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, BlockInfo, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
+/// A token traded against the quote denom
+pub use libraries::transfer_helper::AssetInfo;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The denom every order is priced in
+    pub quote_denom: String,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum OrderType {
+    Buy,
+    Sell,
+}
+
+impl OrderType {
+    /// The side's storage discriminant
+    pub fn side(&self) -> u8 {
+        match self {
+            OrderType::Buy => 0,
+            OrderType::Sell => 1,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            OrderType::Buy => OrderType::Sell,
+            OrderType::Sell => OrderType::Buy,
+        }
+    }
+}
+
+/// How long an order stays on the book
+#[cw_serde]
+#[derive(Default)]
+pub enum TimeInForce {
+    /// Rests until filled or cancelled
+    #[default]
+    GoodTillCancelled,
+    /// Fills what it can on placement, the rest is refunded
+    ImmediateOrCancel,
+    /// Fills completely on placement or fails
+    FillOrKill,
+    /// Rests up to and including block `height`
+    GoodTillBlock { height: u64 },
+    /// Rests up to and including block time `time`
+    GoodTillTime { time: Timestamp },
+}
+
+impl TimeInForce {
+    /// Whether an order with this time in force may no longer trade in `block`
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        match self {
+            TimeInForce::GoodTillBlock { height } => block.height > *height,
+            TimeInForce::GoodTillTime { time } => block.time > *time,
+            _ => false,
+        }
+    }
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Places a limit order for `amount` of `token` at `price` quote units per token
+    /// The order first takes liquidity from the opposite side of the book at prices no worse than `price`;
+    /// whatever is left rests on the book. Matching stops after a fixed number of fills and expiries
+    /// Buy orders must attach exactly `amount * price` of the quote denom, sell orders exactly `amount` of a
+    /// native `token`; CW20 tokens are sold through `Receive`
+    AddOrder {
+        token: AssetInfo,
+        amount: Uint128,
+        price: Uint128,
+        order_type: OrderType,
+        #[serde(default)]
+        time_in_force: TimeInForce,
+    },
+    /// Cancels a resting order of the sender and refunds its escrow
+    CancelOrder { order_id: u64 },
+    /// Changes the price and/or open amount of a resting order of the sender
+    /// Reducing the amount at the same price keeps the order's time priority; any other change replaces it with
+    /// a new order, under a new id, that is matched against the book like a fresh one
+    /// Escrow is topped up by the attached funds or refunded to match the amended order
+    AmendOrder {
+        order_id: u64,
+        price: Option<Uint128>,
+        amount: Option<Uint128>,
+    },
+    /// Entry point for CW20 transfers, carrying a `ReceiveMsg`
+    Receive(Cw20ReceiveMsg),
+    /// Matches crossing resting buy and sell orders for `token`, the older order of each pair being the maker
+    /// Orders are matched when placed, so this is a permissionless crank that only finds work if the book was left
+    /// crossed, e.g. by a placement that reached its step limit
+    MatchOrders { token: String },
+}
+
+/// Messages sent through a CW20 `Send`
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Places a sell order for the received tokens at `price` quote units per token
+    AddOrder {
+        price: Uint128,
+        #[serde(default)]
+        time_in_force: TimeInForce,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// A single resting order
+    #[returns(OrderResponse)]
+    Order { order_id: u64 },
+    /// Aggregated open amount per price level for the best `limit` levels on each side of `token`'s book
+    /// Expired orders are left out
+    #[returns(DepthResponse)]
+    Depth { token: String, limit: Option<u32> },
+    /// One side of `token`'s book in price-time priority, expired orders left out
+    #[returns(OrdersResponse)]
+    Orders {
+        token: String,
+        order_type: OrderType,
+        start_after: Option<BookCursor>,
+        limit: Option<u32>,
+    },
+    /// The resting orders of `trader` by id, expired ones included so they can be cancelled
+    #[returns(OrdersResponse)]
+    OrdersByTrader {
+        trader: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The fills recorded for `token`, oldest first
+    #[returns(TradesResponse)]
+    Trades {
+        token: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The funds `trader` has locked in resting orders, per token
+    #[returns(LockedBalancesResponse)]
+    LockedBalances { trader: String },
+}
+
+/// Position within one side of a book: the price and id of the last order of the previous page
+#[cw_serde]
+pub struct BookCursor {
+    pub price: Uint128,
+    pub order_id: u64,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub quote_denom: String,
+}
+
+#[cw_serde]
+pub struct OrderResponse {
+    pub order_id: u64,
+    pub trader: Addr,
+    pub token: AssetInfo,
+    /// The amount of `token` still open on the order
+    pub amount: Uint128,
+    pub price: Uint128,
+    pub order_type: OrderType,
+    pub time_in_force: TimeInForce,
+    /// The funds still held for the order: quote for buys, `token` for sells
+    pub locked: Uint128,
+}
+
+#[cw_serde]
+pub struct OrdersResponse {
+    pub orders: Vec<OrderResponse>,
+}
+
+/// The open amount resting at one price
+#[cw_serde]
+pub struct PriceLevel {
+    pub price: Uint128,
+    pub amount: Uint128,
+    /// The number of orders at this price
+    pub orders: u32,
+}
+
+#[cw_serde]
+pub struct DepthResponse {
+    /// Best (highest) price first
+    pub bids: Vec<PriceLevel>,
+    /// Best (lowest) price first
+    pub asks: Vec<PriceLevel>,
+}
+
+#[cw_serde]
+pub struct TradeResponse {
+    pub trade_id: u64,
+    pub token: AssetInfo,
+    pub buy_order_id: u64,
+    pub sell_order_id: u64,
+    /// The resting order that provided the liquidity
+    pub maker_order_id: u64,
+    pub buyer: Addr,
+    pub seller: Addr,
+    /// Execution price, the maker's limit price
+    pub price: Uint128,
+    pub amount: Uint128,
+    /// Block height of the fill
+    pub height: u64,
+}
+
+#[cw_serde]
+pub struct TradesResponse {
+    pub trades: Vec<TradeResponse>,
+}
+
+#[cw_serde]
+pub struct LockedBalance {
+    /// A native denom, the quote denom included, or a CW20 contract address
+    pub token: String,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct LockedBalancesResponse {
+    pub trader: Addr,
+    pub balances: Vec<LockedBalance>,
+}
@@ -0,0 +1,164 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+
+use crate::msg::{AssetInfo, OrderType, TimeInForce};
+
+#[cw_serde]
+pub struct Config {
+    /// The denom every order is priced in
+    pub quote_denom: String,
+}
+
+/// A resting limit order
+#[cw_serde]
+pub struct Order {
+    /// Assigned sequentially, so it doubles as the order's time priority
+    pub id: u64,
+    pub trader: Addr,
+    pub token: AssetInfo,
+    pub order_type: OrderType,
+    /// Limit price in quote units per token
+    pub price: Uint128,
+    /// The amount of `token` the order was placed for
+    pub amount: Uint128,
+    /// The amount of `token` still open
+    pub remaining: Uint128,
+    /// Escrow still held for the order: quote for buys, `token` for sells
+    pub locked: Uint128,
+    pub time_in_force: TimeInForce,
+}
+
+impl Order {
+    /// The escrow the open amount needs: quote at the limit price for buys, `token` for sells
+    pub fn required_escrow(&self) -> StdResult<Uint128> {
+        match self.order_type {
+            OrderType::Buy => Ok(self.remaining.checked_mul(self.price)?),
+            OrderType::Sell => Ok(self.remaining),
+        }
+    }
+
+    /// Key of the order within its side of the book; ascending iteration yields the best price first,
+    /// so bids are stored with their price inverted
+    pub fn book_key(&self) -> (String, u8, u128) {
+        (
+            self.token.to_string(),
+            self.order_type.side(),
+            price_key(self.order_type, self.price),
+        )
+    }
+}
+
+/// The book index component of a price; bids invert it so the best price sorts first on both sides
+pub fn price_key(order_type: OrderType, price: Uint128) -> u128 {
+    match order_type {
+        OrderType::Buy => u128::MAX - price.u128(),
+        OrderType::Sell => price.u128(),
+    }
+}
+
+/// A fill between a resting (maker) order and the order that crossed it (taker)
+#[cw_serde]
+pub struct Trade {
+    pub id: u64,
+    pub token: AssetInfo,
+    pub buy_order_id: u64,
+    pub sell_order_id: u64,
+    pub maker_order_id: u64,
+    pub buyer: Addr,
+    pub seller: Addr,
+    /// Execution price, always the maker's limit price
+    pub price: Uint128,
+    pub amount: Uint128,
+    pub height: u64,
+}
+
+pub struct OrderIndexes<'a> {
+    /// Orders by (token, side, price key), then id: price-time priority within each side
+    pub book: MultiIndex<'a, (String, u8, u128), Order, u64>,
+    pub trader: MultiIndex<'a, Addr, Order, u64>,
+}
+
+impl<'a> IndexList<Order> for OrderIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Order>> + '_> {
+        let v: Vec<&dyn Index<Order>> = vec![&self.book, &self.trader];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Resting orders by id; filled orders are removed
+pub fn orders<'a>() -> IndexedMap<'a, u64, Order, OrderIndexes<'a>> {
+    let indexes = OrderIndexes {
+        book: MultiIndex::new(|_, order| order.book_key(), "orders", "orders__book"),
+        trader: MultiIndex::new(|_, order| order.trader.clone(), "orders", "orders__trader"),
+    };
+    IndexedMap::new("orders", indexes)
+}
+
+pub struct TradeIndexes<'a> {
+    pub token: MultiIndex<'a, String, Trade, u64>,
+}
+
+impl<'a> IndexList<Trade> for TradeIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Trade>> + '_> {
+        let v: Vec<&dyn Index<Trade>> = vec![&self.token];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Every fill by id
+pub fn trades<'a>() -> IndexedMap<'a, u64, Trade, TradeIndexes<'a>> {
+    let indexes = TradeIndexes {
+        token: MultiIndex::new(
+            |_, trade| trade.token.to_string(),
+            "trades",
+            "trades__token",
+        ),
+    };
+    IndexedMap::new("trades", indexes)
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const ORDER_COUNT: Item<u64> = Item::new("order_count");
+pub const TRADE_COUNT: Item<u64> = Item::new("trade_count");
+/// Funds escrowed in resting orders by (trader, token key), the quote denom included
+pub const LOCKED: Map<(&Addr, &str), Uint128> = Map::new("locked");
+
+/// Returns the next id from a sequence, starting at 1
+pub fn next_id(storage: &mut dyn Storage, seq: &Item<u64>) -> StdResult<u64> {
+    let id = seq.may_load(storage)?.unwrap_or_default() + 1;
+    seq.save(storage, &id)?;
+    Ok(id)
+}
+
+/// Adds `amount` to the funds `trader` has locked in `token`
+pub fn lock(
+    storage: &mut dyn Storage,
+    trader: &Addr,
+    token: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    LOCKED.update(storage, (trader, token), |locked| -> StdResult<_> {
+        Ok(locked.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+/// Releases `amount` of the funds `trader` has locked in `token`
+pub fn unlock(
+    storage: &mut dyn Storage,
+    trader: &Addr,
+    token: &str,
+    amount: Uint128,
+) -> StdResult<()> {
+    let locked = LOCKED
+        .may_load(storage, (trader, token))?
+        .unwrap_or_default()
+        .checked_sub(amount)?;
+    if locked.is_zero() {
+        LOCKED.remove(storage, (trader, token));
+    } else {
+        LOCKED.save(storage, (trader, token), &locked)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,860 @@
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Env, Event, OwnedDeps, Response,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_utils::PaymentError;
+use serde::de::DeserializeOwned;
+
+use crate::contract::{self, execute, instantiate, MAX_CRANK_STEPS};
+use crate::error::ContractError;
+use crate::msg::{
+    AssetInfo, BookCursor, DepthResponse, ExecuteMsg, InstantiateMsg, LockedBalance,
+    LockedBalancesResponse, OrderResponse, OrderType, OrdersResponse, PriceLevel, QueryMsg,
+    ReceiveMsg, TimeInForce, TradesResponse,
+};
+
+const QUOTE: &str = "uusdc";
+const TOKEN: &str = "uxion";
+const CW20: &str = "cw20token";
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        InstantiateMsg {
+            quote_denom: QUOTE.to_string(),
+        },
+    )
+    .unwrap();
+    deps
+}
+
+fn add_order(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    trader: &str,
+    order_type: OrderType,
+    amount: u128,
+    price: u128,
+) -> Result<Response, ContractError> {
+    add_order_with(
+        deps,
+        mock_env(),
+        trader,
+        order_type,
+        amount,
+        price,
+        TimeInForce::GoodTillCancelled,
+    )
+}
+
+fn add_order_with(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    env: Env,
+    trader: &str,
+    order_type: OrderType,
+    amount: u128,
+    price: u128,
+    time_in_force: TimeInForce,
+) -> Result<Response, ContractError> {
+    // attach exactly the escrow the order needs
+    let funds = match order_type {
+        OrderType::Buy => coins(amount * price, QUOTE),
+        OrderType::Sell => coins(amount, TOKEN),
+    };
+    execute(
+        deps.as_mut(),
+        env,
+        mock_info(trader, &funds),
+        ExecuteMsg::AddOrder {
+            token: native(TOKEN),
+            amount: Uint128::new(amount),
+            price: Uint128::new(price),
+            order_type,
+            time_in_force,
+        },
+    )
+}
+
+fn native(denom: &str) -> AssetInfo {
+    AssetInfo::Native {
+        denom: denom.to_string(),
+    }
+}
+
+fn bank_send(to: &str, amount: u128, denom: &str) -> CosmosMsg {
+    BankMsg::Send {
+        to_address: to.to_string(),
+        amount: coins(amount, denom),
+    }
+    .into()
+}
+
+fn cw20_transfer(to: &str, amount: u128) -> CosmosMsg {
+    WasmMsg::Execute {
+        contract_addr: CW20.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: to.to_string(),
+            amount: Uint128::new(amount),
+        })
+        .unwrap(),
+        funds: vec![],
+    }
+    .into()
+}
+
+fn messages(res: &Response) -> Vec<CosmosMsg> {
+    res.messages.iter().map(|msg| msg.msg.clone()).collect()
+}
+
+fn locked_balances(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    trader: &str,
+) -> Vec<(String, u128)> {
+    let res: LockedBalancesResponse = query(
+        deps,
+        QueryMsg::LockedBalances {
+            trader: trader.to_string(),
+        },
+    );
+    res.balances
+        .into_iter()
+        .map(|LockedBalance { token, amount }| (token, amount.u128()))
+        .collect()
+}
+
+fn query_orders(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    token: &str,
+) -> Vec<OrderResponse> {
+    [OrderType::Buy, OrderType::Sell]
+        .into_iter()
+        .flat_map(|order_type| {
+            query::<OrdersResponse>(
+                deps,
+                QueryMsg::Orders {
+                    token: token.to_string(),
+                    order_type,
+                    start_after: None,
+                    limit: Some(30),
+                },
+            )
+            .orders
+        })
+        .collect()
+}
+
+fn query_trades(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> TradesResponse {
+    query(
+        deps,
+        QueryMsg::Trades {
+            token: TOKEN.to_string(),
+            start_after: None,
+            limit: Some(30),
+        },
+    )
+}
+
+fn query<T: DeserializeOwned>(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    msg: QueryMsg,
+) -> T {
+    from_binary(&contract::query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap()
+}
+
+fn attribute_of<'a>(res: &'a Response, key: &str) -> &'a str {
+    &res.attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .unwrap()
+        .value
+}
+
+fn attribute<'a>(event: &'a Event, key: &str) -> &'a str {
+    &event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .unwrap()
+        .value
+}
+
+/// (order id, remaining amount, price) of every resting order, bids then asks
+fn book(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Vec<(u64, u128, u128)> {
+    query_orders(deps, TOKEN)
+        .into_iter()
+        .map(|order| (order.order_id, order.amount.u128(), order.price.u128()))
+        .collect()
+}
+
+#[test]
+fn instantiated_book_is_empty() {
+    let deps = setup();
+    assert!(book(&deps).is_empty());
+    assert!(query_trades(&deps).trades.is_empty());
+}
+
+#[test]
+fn added_order_rests_on_the_book() {
+    let mut deps = setup();
+    let res = add_order(&mut deps, "trader1", OrderType::Buy, 100, 10).unwrap();
+    assert!(res.messages.is_empty());
+
+    assert_eq!(book(&deps), vec![(1, 100, 10)]);
+    let order: OrderResponse = query(&deps, QueryMsg::Order { order_id: 1 });
+    assert_eq!(order.trader, Addr::unchecked("trader1"));
+    assert_eq!(
+        locked_balances(&deps, "trader1"),
+        vec![(QUOTE.to_string(), 1000)]
+    );
+}
+
+#[test]
+fn buy_and_sell_at_the_same_price_match() {
+    let mut deps = setup();
+    add_order(&mut deps, "trader1", OrderType::Buy, 100, 10).unwrap();
+    add_order(&mut deps, "trader2", OrderType::Sell, 80, 10).unwrap();
+
+    // the sell fills against the resting buy as it is added, leaving nothing for the crank
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("matcher", &[]),
+        ExecuteMsg::MatchOrders {
+            token: TOKEN.to_string(),
+        },
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+
+    assert_eq!(book(&deps), vec![(1, 20, 10)]);
+    let trades = query_trades(&deps).trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].buyer, Addr::unchecked("trader1"));
+    assert_eq!(trades[0].seller, Addr::unchecked("trader2"));
+    assert_eq!(trades[0].amount, Uint128::new(80));
+}
+
+#[test]
+fn orders_rest_in_price_time_priority() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Buy, 10, 9).unwrap();
+    add_order(&mut deps, "bob", OrderType::Buy, 10, 10).unwrap();
+    add_order(&mut deps, "carol", OrderType::Buy, 10, 10).unwrap();
+    add_order(&mut deps, "alice", OrderType::Sell, 10, 12).unwrap();
+    add_order(&mut deps, "bob", OrderType::Sell, 10, 11).unwrap();
+
+    // bids best (highest) first, asks best (lowest) first, ties by age
+    assert_eq!(
+        book(&deps),
+        vec![
+            (2, 10, 10),
+            (3, 10, 10),
+            (1, 10, 9),
+            (5, 10, 11),
+            (4, 10, 12)
+        ]
+    );
+    assert!(query_trades(&deps).trades.is_empty());
+
+    let order: OrderResponse = query(&deps, QueryMsg::Order { order_id: 1 });
+    assert_eq!(order.trader, Addr::unchecked("alice"));
+    assert_eq!(order.order_type, OrderType::Buy);
+}
+
+#[test]
+fn crossing_order_partially_fills_at_maker_price() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Sell, 100, 10).unwrap();
+
+    let res = add_order(&mut deps, "bob", OrderType::Buy, 60, 12).unwrap();
+    assert_eq!(res.events.len(), 1);
+    let fill = &res.events[0];
+    assert_eq!(fill.ty, "fill");
+    assert_eq!(attribute(fill, "price"), "10");
+    assert_eq!(attribute(fill, "amount"), "60");
+    assert_eq!(attribute(fill, "maker_order_id"), "1");
+    assert_eq!(attribute(fill, "taker_order_id"), "2");
+    assert_eq!(attribute(fill, "maker"), "alice");
+    assert_eq!(attribute(fill, "taker"), "bob");
+    assert_eq!(attribute(fill, "taker_side"), "buy");
+
+    // the taker was filled completely, the maker keeps resting
+    assert_eq!(book(&deps), vec![(1, 40, 10)]);
+
+    // bob receives the tokens and the quote his better limit price had escrowed, alice is paid
+    assert_eq!(
+        messages(&res),
+        vec![
+            bank_send("bob", 60, TOKEN),
+            bank_send("alice", 600, QUOTE),
+            bank_send("bob", 120, QUOTE),
+        ]
+    );
+    assert_eq!(
+        locked_balances(&deps, "alice"),
+        vec![(TOKEN.to_string(), 40)]
+    );
+    assert!(locked_balances(&deps, "bob").is_empty());
+
+    let trades = query_trades(&deps).trades;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].buy_order_id, 2);
+    assert_eq!(trades[0].sell_order_id, 1);
+    assert_eq!(trades[0].maker_order_id, 1);
+    assert_eq!(trades[0].buyer, Addr::unchecked("bob"));
+    assert_eq!(trades[0].seller, Addr::unchecked("alice"));
+    assert_eq!(trades[0].amount, Uint128::new(60));
+}
+
+#[test]
+fn taker_sweeps_levels_in_priority_and_rests_remainder() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Buy, 50, 10).unwrap();
+    add_order(&mut deps, "bob", OrderType::Buy, 50, 10).unwrap();
+    add_order(&mut deps, "carol", OrderType::Buy, 50, 11).unwrap();
+    add_order(&mut deps, "dave", OrderType::Buy, 50, 8).unwrap();
+
+    let res = add_order(&mut deps, "erin", OrderType::Sell, 180, 9).unwrap();
+    let fills: Vec<_> = res
+        .events
+        .iter()
+        .map(|event| {
+            (
+                attribute(event, "maker_order_id"),
+                attribute(event, "price"),
+                attribute(event, "amount"),
+            )
+        })
+        .collect();
+    assert_eq!(
+        fills,
+        vec![("3", "11", "50"), ("1", "10", "50"), ("2", "10", "50")]
+    );
+
+    // the bid at 8 doesn't cross the sell at 9, so the remainder rests
+    assert_eq!(book(&deps), vec![(4, 50, 8), (5, 30, 9)]);
+    assert_eq!(query_trades(&deps).trades.len(), 3);
+}
+
+#[test]
+fn placement_matches_a_bounded_number_of_makers() {
+    let mut deps = setup();
+    let makers = MAX_CRANK_STEPS as u128 + 5;
+    for _ in 0..makers {
+        add_order(&mut deps, "alice", OrderType::Sell, 1, 10).unwrap();
+    }
+
+    // the buy stops after the step limit and rests the remainder, crossing the asks left behind
+    let res = add_order(&mut deps, "bob", OrderType::Buy, 100, 10).unwrap();
+    assert_eq!(res.events.len(), MAX_CRANK_STEPS);
+    let bob = makers as u64 + 1;
+    let remaining = 100 - MAX_CRANK_STEPS as u128;
+    assert_eq!(book(&deps)[0], (bob, remaining, 10));
+    assert_eq!(book(&deps).len(), 6);
+
+    // the crank matches what placement left crossed
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("matcher", &[]),
+        ExecuteMsg::MatchOrders {
+            token: TOKEN.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute_of(&res, "fills"), "5");
+    assert_eq!(book(&deps), vec![(bob, remaining - 5, 10)]);
+
+    // a fill or kill order that can't complete within the limit fails, even though the book could fill it
+    for _ in 0..makers {
+        add_order(&mut deps, "alice", OrderType::Buy, 1, 10).unwrap();
+    }
+    let err = add_order_with(
+        &mut deps,
+        mock_env(),
+        "carol",
+        OrderType::Sell,
+        remaining - 5 + makers,
+        10,
+        TimeInForce::FillOrKill,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FillOrKillNotFilled {});
+}
+
+#[test]
+fn filled_orders_leave_the_book() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Sell, 30, 10).unwrap();
+    add_order(&mut deps, "bob", OrderType::Buy, 30, 10).unwrap();
+
+    assert!(book(&deps).is_empty());
+    assert!(contract::query(deps.as_ref(), mock_env(), QueryMsg::Order { order_id: 1 }).is_err());
+
+    // nothing crosses, so the crank has no work
+    add_order(&mut deps, "alice", OrderType::Sell, 30, 11).unwrap();
+    add_order(&mut deps, "bob", OrderType::Buy, 30, 10).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::MatchOrders {
+            token: TOKEN.to_string(),
+        },
+    )
+    .unwrap();
+    assert!(res.events.is_empty());
+    assert_eq!(book(&deps).len(), 2);
+}
+
+#[test]
+fn invalid_orders_are_rejected() {
+    let mut deps = setup();
+    assert_eq!(
+        add_order(&mut deps, "alice", OrderType::Buy, 0, 10).unwrap_err(),
+        ContractError::ZeroAmount {}
+    );
+    assert_eq!(
+        add_order(&mut deps, "alice", OrderType::Buy, 10, 0).unwrap_err(),
+        ContractError::ZeroPrice {}
+    );
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::AddOrder {
+            token: native(QUOTE),
+            amount: Uint128::new(10),
+            price: Uint128::new(10),
+            order_type: OrderType::Sell,
+            time_in_force: TimeInForce::default(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidToken {});
+}
+
+#[test]
+fn orders_must_attach_their_escrow() {
+    let mut deps = setup();
+    let add =
+        |amount: u128, price: u128, order_type: OrderType, token: AssetInfo| ExecuteMsg::AddOrder {
+            token,
+            amount: Uint128::new(amount),
+            price: Uint128::new(price),
+            order_type,
+            time_in_force: TimeInForce::default(),
+        };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(99, QUOTE)),
+        add(10, 10, OrderType::Buy, native(TOKEN)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::FundsMismatch {
+            expected: Uint128::new(100),
+            received: Uint128::new(99)
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(10, QUOTE)),
+        add(10, 10, OrderType::Sell, native(TOKEN)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Payment(PaymentError::MissingDenom(TOKEN.to_string()))
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        add(
+            10,
+            10,
+            OrderType::Sell,
+            AssetInfo::Cw20 {
+                contract_addr: CW20.to_string(),
+            },
+        ),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Cw20NotReceived {});
+}
+
+#[test]
+fn cw20_sell_settles_and_cancel_refunds() {
+    let mut deps = setup();
+    let token = AssetInfo::Cw20 {
+        contract_addr: CW20.to_string(),
+    };
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(CW20, &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "alice".to_string(),
+            amount: Uint128::new(50),
+            msg: to_binary(&ReceiveMsg::AddOrder {
+                price: Uint128::new(3),
+                time_in_force: TimeInForce::default(),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        locked_balances(&deps, "alice"),
+        vec![(CW20.to_string(), 50)]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(60, QUOTE)),
+        ExecuteMsg::AddOrder {
+            token,
+            amount: Uint128::new(20),
+            price: Uint128::new(3),
+            order_type: OrderType::Buy,
+            time_in_force: TimeInForce::default(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        messages(&res),
+        vec![cw20_transfer("bob", 20), bank_send("alice", 60, QUOTE)]
+    );
+
+    let cancel = ExecuteMsg::CancelOrder { order_id: 1 };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        cancel.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), cancel).unwrap();
+    assert_eq!(messages(&res), vec![cw20_transfer("alice", 30)]);
+    assert!(locked_balances(&deps, "alice").is_empty());
+    assert!(query_orders(&deps, CW20).is_empty());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderNotFound { order_id: 1 });
+}
+
+#[test]
+fn immediate_or_cancel_refunds_what_does_not_fill() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Sell, 30, 10).unwrap();
+
+    let res = add_order_with(
+        &mut deps,
+        mock_env(),
+        "bob",
+        OrderType::Buy,
+        50,
+        10,
+        TimeInForce::ImmediateOrCancel,
+    )
+    .unwrap();
+    assert_eq!(
+        messages(&res),
+        vec![
+            bank_send("bob", 30, TOKEN),
+            bank_send("alice", 300, QUOTE),
+            bank_send("bob", 200, QUOTE),
+        ]
+    );
+    assert!(book(&deps).is_empty());
+    assert!(locked_balances(&deps, "bob").is_empty());
+}
+
+#[test]
+fn fill_or_kill_fails_unless_completely_filled() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Sell, 30, 10).unwrap();
+
+    let err = add_order_with(
+        &mut deps,
+        mock_env(),
+        "bob",
+        OrderType::Buy,
+        50,
+        10,
+        TimeInForce::FillOrKill,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FillOrKillNotFilled {});
+
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Sell, 30, 10).unwrap();
+    add_order_with(
+        &mut deps,
+        mock_env(),
+        "bob",
+        OrderType::Buy,
+        30,
+        10,
+        TimeInForce::FillOrKill,
+    )
+    .unwrap();
+    assert!(book(&deps).is_empty());
+}
+
+#[test]
+fn expired_orders_are_purged_when_reached() {
+    let mut deps = setup();
+    let env = mock_env();
+    add_order_with(
+        &mut deps,
+        env.clone(),
+        "alice",
+        OrderType::Sell,
+        30,
+        10,
+        TimeInForce::GoodTillBlock {
+            height: env.block.height,
+        },
+    )
+    .unwrap();
+    add_order_with(
+        &mut deps,
+        env.clone(),
+        "carol",
+        OrderType::Sell,
+        30,
+        10,
+        TimeInForce::GoodTillTime {
+            time: env.block.time.plus_seconds(60),
+        },
+    )
+    .unwrap();
+
+    let mut later = env.clone();
+    later.block.height += 1;
+    later.block.time = later.block.time.plus_seconds(5);
+    let res = add_order_with(
+        &mut deps,
+        later.clone(),
+        "bob",
+        OrderType::Buy,
+        10,
+        10,
+        TimeInForce::GoodTillCancelled,
+    )
+    .unwrap();
+
+    // alice's order expired before bob reached it, so carol's order filled
+    assert_eq!(res.events[0].ty, "order_expired");
+    assert_eq!(attribute(&res.events[0], "order_id"), "1");
+    assert_eq!(attribute(&res.events[1], "maker_order_id"), "2");
+    assert_eq!(
+        messages(&res),
+        vec![
+            bank_send("alice", 30, TOKEN),
+            bank_send("bob", 10, TOKEN),
+            bank_send("carol", 100, QUOTE),
+        ]
+    );
+    assert_eq!(book(&deps), vec![(2, 20, 10)]);
+
+    // orders can't be placed already expired
+    let err = add_order_with(
+        &mut deps,
+        later,
+        "alice",
+        OrderType::Sell,
+        30,
+        10,
+        TimeInForce::GoodTillBlock {
+            height: env.block.height,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OrderExpired {});
+}
+
+#[test]
+fn amending_keeps_priority_only_when_reducing() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Sell, 30, 10).unwrap();
+    add_order(&mut deps, "carol", OrderType::Sell, 30, 10).unwrap();
+    let amend = |amount: Option<u128>, price: Option<u128>| ExecuteMsg::AmendOrder {
+        order_id: 1,
+        price: price.map(Uint128::new),
+        amount: amount.map(Uint128::new),
+    };
+
+    // reducing in place refunds the difference and keeps the queue position
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        amend(Some(20), None),
+    )
+    .unwrap();
+    assert_eq!(messages(&res), vec![bank_send("alice", 10, TOKEN)]);
+    assert_eq!(book(&deps), vec![(1, 20, 10), (2, 30, 10)]);
+
+    // growing needs the extra escrow and sends the order to the back of the queue
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(20, TOKEN)),
+        amend(Some(40), None),
+    )
+    .unwrap();
+    assert_eq!(attribute_of(&res, "new_order_id"), "3");
+    assert_eq!(book(&deps), vec![(2, 30, 10), (3, 40, 10)]);
+    assert_eq!(
+        locked_balances(&deps, "alice"),
+        vec![(TOKEN.to_string(), 40)]
+    );
+
+    // a repriced order is matched like a new one
+    add_order(&mut deps, "bob", OrderType::Buy, 10, 8).unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("carol", &[]),
+        ExecuteMsg::AmendOrder {
+            order_id: 2,
+            price: Some(Uint128::new(8)),
+            amount: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(attribute_of(&res, "new_order_id"), "5");
+    assert_eq!(attribute_of(&res, "filled"), "10");
+    assert_eq!(
+        messages(&res),
+        vec![bank_send("bob", 10, TOKEN), bank_send("carol", 80, QUOTE)]
+    );
+    assert_eq!(book(&deps), vec![(5, 20, 8), (3, 40, 10)]);
+
+    // without the top up the amendment fails
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::AmendOrder {
+            order_id: 3,
+            price: None,
+            amount: Some(Uint128::new(50)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Payment(PaymentError::NoFunds {}));
+}
+
+#[test]
+fn depth_aggregates_price_levels() {
+    let mut deps = setup();
+    add_order(&mut deps, "alice", OrderType::Buy, 10, 9).unwrap();
+    add_order(&mut deps, "bob", OrderType::Buy, 15, 9).unwrap();
+    add_order(&mut deps, "carol", OrderType::Buy, 5, 8).unwrap();
+    add_order(&mut deps, "dave", OrderType::Buy, 5, 7).unwrap();
+    add_order(&mut deps, "alice", OrderType::Sell, 20, 11).unwrap();
+    add_order(&mut deps, "bob", OrderType::Sell, 20, 12).unwrap();
+    add_order(&mut deps, "carol", OrderType::Sell, 1, 11).unwrap();
+
+    let level = |price: u128, amount: u128, orders: u32| PriceLevel {
+        price: Uint128::new(price),
+        amount: Uint128::new(amount),
+        orders,
+    };
+    let depth: DepthResponse = query(
+        &deps,
+        QueryMsg::Depth {
+            token: TOKEN.to_string(),
+            limit: Some(2),
+        },
+    );
+    assert_eq!(depth.bids, vec![level(9, 25, 2), level(8, 5, 1)]);
+    assert_eq!(depth.asks, vec![level(11, 21, 2), level(12, 20, 1)]);
+}
+
+#[test]
+fn orders_and_trades_are_paginated() {
+    let mut deps = setup();
+    for price in [10, 12, 11, 11] {
+        add_order(&mut deps, "alice", OrderType::Sell, 10, price).unwrap();
+    }
+    add_order(&mut deps, "bob", OrderType::Buy, 5, 9).unwrap();
+
+    let page = |start_after: Option<BookCursor>| -> Vec<u64> {
+        let res: OrdersResponse = query(
+            &deps,
+            QueryMsg::Orders {
+                token: TOKEN.to_string(),
+                order_type: OrderType::Sell,
+                start_after,
+                limit: Some(2),
+            },
+        );
+        res.orders.iter().map(|order| order.order_id).collect()
+    };
+    assert_eq!(page(None), vec![1, 3]);
+    let cursor = BookCursor {
+        price: Uint128::new(11),
+        order_id: 3,
+    };
+    assert_eq!(page(Some(cursor)), vec![4, 2]);
+
+    let by_trader = |start_after: Option<u64>| -> Vec<u64> {
+        let res: OrdersResponse = query(
+            &deps,
+            QueryMsg::OrdersByTrader {
+                trader: "alice".to_string(),
+                start_after,
+                limit: Some(3),
+            },
+        );
+        res.orders.iter().map(|order| order.order_id).collect()
+    };
+    assert_eq!(by_trader(None), vec![1, 2, 3]);
+    assert_eq!(by_trader(Some(3)), vec![4]);
+
+    // sweep the asks in three fills
+    add_order(&mut deps, "bob", OrderType::Buy, 25, 11).unwrap();
+    let trades = |start_after: Option<u64>| -> Vec<(u64, u64)> {
+        let res: TradesResponse = query(
+            &deps,
+            QueryMsg::Trades {
+                token: TOKEN.to_string(),
+                start_after,
+                limit: Some(2),
+            },
+        );
+        res.trades
+            .iter()
+            .map(|trade| (trade.trade_id, trade.sell_order_id))
+            .collect()
+    };
+    assert_eq!(trades(None), vec![(1, 1), (2, 3)]);
+    assert_eq!(trades(Some(2)), vec![(3, 4)]);
+}
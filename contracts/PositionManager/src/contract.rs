@@ -0,0 +1,680 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Non-fungible position manager
+//! Holds liquidity in the factory's pools on behalf of its users and issues a CW721 token for each position, so
+//! positions can be transferred and approved like any other NFT. The position itself is the token's metadata.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, Event, MessageInfo, Reply, Response, StdError,
+    StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+};
+use cw721_base::state::TokenInfo;
+use cw721_base::{ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg};
+use factory::helpers::FactoryContract;
+use interfaces::pool::events::BurnEvent;
+use interfaces::pool::IXionPoolEvent;
+use libraries::position::{fees_earned, PositionInfo};
+use liquidity_pool::helpers::XionPoolContract;
+use liquidity_pool::msg::{ExecuteMsg as PoolExecuteMsg, QueryMsg as PoolQueryMsg};
+
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::{
+    next_token_id, position_nft, Config, PendingCollect, PendingDecrease, PendingMint, Position,
+    CONFIG, PENDING_COLLECT, PENDING_DECREASE, PENDING_MINT,
+};
+
+pub const DECREASE_REPLY_ID: u64 = 1;
+pub const COLLECT_REPLY_ID: u64 = 2;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = Config {
+        factory: deps.api.addr_validate(&msg.factory)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    // tokens are only ever minted by the manager itself, when it opens a position
+    position_nft().instantiate(
+        deps.branch(),
+        env.clone(),
+        info,
+        Cw721InstantiateMsg {
+            name: msg.name,
+            symbol: msg.symbol,
+            minter: env.contract.address.to_string(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("factory", config.factory))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let cw721_msg = match msg {
+        ExecuteMsg::Mint {
+            pool,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            recipient,
+        } => {
+            return mint(
+                deps, env, info, pool, tick_lower, tick_upper, liquidity, recipient,
+            )
+        }
+        ExecuteMsg::IncreaseLiquidity {
+            token_id,
+            liquidity,
+        } => return increase_liquidity(deps, env, info, token_id, liquidity),
+        ExecuteMsg::DecreaseLiquidity {
+            token_id,
+            liquidity,
+            amount0_min,
+            amount1_min,
+        } => {
+            return decrease_liquidity(
+                deps,
+                env,
+                info,
+                token_id,
+                liquidity,
+                amount0_min,
+                amount1_min,
+            )
+        }
+        ExecuteMsg::Collect {
+            token_id,
+            recipient,
+            amount0_max,
+            amount1_max,
+        } => {
+            return collect(
+                deps,
+                env,
+                info,
+                token_id,
+                recipient,
+                amount0_max,
+                amount1_max,
+            )
+        }
+        ExecuteMsg::Burn { token_id } => return burn(deps, env, info, token_id),
+        ExecuteMsg::XionMintCallback {
+            amount0_owed,
+            amount1_owed,
+            ..
+        } => return mint_callback(deps, env, info, amount0_owed, amount1_owed),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => Cw721ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        },
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => Cw721ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        },
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => Cw721ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        },
+        ExecuteMsg::Revoke { spender, token_id } => Cw721ExecuteMsg::Revoke { spender, token_id },
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            Cw721ExecuteMsg::ApproveAll { operator, expires }
+        }
+        ExecuteMsg::RevokeAll { operator } => Cw721ExecuteMsg::RevokeAll { operator },
+    };
+    Ok(position_nft().execute(deps, env, info, cw721_msg)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        DECREASE_REPLY_ID => settle_decrease(deps, env, msg),
+        COLLECT_REPLY_ID => {
+            let pending = PENDING_COLLECT.load(deps.storage)?;
+            PENDING_COLLECT.remove(deps.storage);
+            finish_collect(deps, env, pending)
+        }
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    let cw721_msg = match msg {
+        QueryMsg::Config {} => return to_binary(&query_config(deps)?),
+        QueryMsg::Position { token_id } => {
+            return to_binary(
+                &position_nft()
+                    .tokens
+                    .load(deps.storage, &token_id)?
+                    .extension,
+            )
+        }
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => cw721_base::QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        },
+        QueryMsg::Approval {
+            token_id,
+            spender,
+            include_expired,
+        } => cw721_base::QueryMsg::Approval {
+            token_id,
+            spender,
+            include_expired,
+        },
+        QueryMsg::Approvals {
+            token_id,
+            include_expired,
+        } => cw721_base::QueryMsg::Approvals {
+            token_id,
+            include_expired,
+        },
+        QueryMsg::Operator {
+            owner,
+            operator,
+            include_expired,
+        } => cw721_base::QueryMsg::Operator {
+            owner,
+            operator,
+            include_expired,
+        },
+        QueryMsg::AllOperators {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => cw721_base::QueryMsg::AllOperators {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        },
+        QueryMsg::NumTokens {} => cw721_base::QueryMsg::NumTokens {},
+        QueryMsg::ContractInfo {} => cw721_base::QueryMsg::ContractInfo {},
+        QueryMsg::NftInfo { token_id } => cw721_base::QueryMsg::NftInfo { token_id },
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => cw721_base::QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        },
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => cw721_base::QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        },
+        QueryMsg::AllTokens { start_after, limit } => {
+            cw721_base::QueryMsg::AllTokens { start_after, limit }
+        }
+    };
+    position_nft().query(deps, env, cw721_msg)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn mint(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool: String,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: Uint128,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = deps.api.addr_validate(&pool)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    if !FactoryContract(config.factory.clone()).is_pool(&deps.querier, &pool) {
+        return Err(ContractError::InvalidPool {
+            pool: pool.to_string(),
+        });
+    }
+
+    let token_id = next_token_id(deps.storage)?;
+    add_liquidity(
+        deps,
+        env,
+        PendingMint {
+            token_id,
+            recipient: Some(recipient),
+            pool,
+            tick_lower,
+            tick_upper,
+            liquidity,
+            payer: info.sender,
+            funds: info.funds,
+        },
+    )
+}
+
+fn increase_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    liquidity: Uint128,
+) -> Result<Response, ContractError> {
+    let position = position_nft()
+        .tokens
+        .load(deps.storage, &token_id)?
+        .extension;
+    add_liquidity(
+        deps,
+        env,
+        PendingMint {
+            token_id,
+            recipient: None,
+            pool: position.pool,
+            tick_lower: position.tick_lower,
+            tick_upper: position.tick_upper,
+            liquidity,
+            payer: info.sender,
+            funds: info.funds,
+        },
+    )
+}
+
+/// Mints liquidity in the pool for the manager, to be paid for by the sender in `mint_callback`
+fn add_liquidity(deps: DepsMut, env: Env, pending: PendingMint) -> Result<Response, ContractError> {
+    if pending.liquidity.is_zero() {
+        return Err(ContractError::ZeroLiquidity {});
+    }
+    PENDING_MINT.save(deps.storage, &pending)?;
+
+    let mint = WasmMsg::Execute {
+        contract_addr: pending.pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Mint {
+            recipient: env.contract.address.to_string(),
+            tick_lower: pending.tick_lower,
+            tick_upper: pending.tick_upper,
+            amount: pending.liquidity,
+            data: to_binary(&pending.token_id)?,
+        })?,
+        funds: vec![],
+    };
+
+    let action = if pending.recipient.is_some() {
+        "mint"
+    } else {
+        "increase_liquidity"
+    };
+    Ok(Response::new()
+        .add_message(mint)
+        .add_attribute("action", action)
+        .add_attribute("token_id", pending.token_id)
+        .add_attribute("pool", pending.pool)
+        .add_attribute("tick_lower", pending.tick_lower.to_string())
+        .add_attribute("tick_upper", pending.tick_upper.to_string())
+        .add_attribute("liquidity", pending.liquidity))
+}
+
+/// Pays the pool for pending liquidity out of the funds the payer attached, refunding the rest, and records the
+/// liquidity in its token
+fn mint_callback(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount0_owed: Uint256,
+    amount1_owed: Uint256,
+) -> Result<Response, ContractError> {
+    let Some(pending) = PENDING_MINT.may_load(deps.storage)? else {
+        return Err(ContractError::UnexpectedCallback {});
+    };
+    if info.sender != pending.pool {
+        return Err(ContractError::UnexpectedCallback {});
+    }
+    PENDING_MINT.remove(deps.storage);
+
+    let payment = XionPoolContract(pending.pool.clone()).pay_from_funds(
+        &deps.querier,
+        pending.funds,
+        &pending.payer,
+        amount0_owed,
+        amount1_owed,
+    )?;
+
+    let pool_position = query_pool_position(
+        deps.as_ref(),
+        &env,
+        &pending.pool,
+        pending.tick_lower,
+        pending.tick_upper,
+    )?;
+    let position = match pending.recipient {
+        Some(recipient) => {
+            // a new token only earns the fees the shared pool position collects from now on
+            let position = Position {
+                pool: pending.pool.clone(),
+                tick_lower: pending.tick_lower,
+                tick_upper: pending.tick_upper,
+                liquidity: pending.liquidity,
+                fee_growth_inside0_last_x128: pool_position.fee_growth_inside0_last_x128,
+                fee_growth_inside1_last_x128: pool_position.fee_growth_inside1_last_x128,
+                tokens_owed0: Uint128::zero(),
+                tokens_owed1: Uint128::zero(),
+            };
+            let minter = MessageInfo {
+                sender: env.contract.address.clone(),
+                funds: vec![],
+            };
+            position_nft().mint(
+                deps.branch(),
+                minter,
+                pending.token_id.clone(),
+                recipient.to_string(),
+                None,
+                position.clone(),
+            )?;
+            position
+        }
+        None => {
+            let mut token = position_nft()
+                .tokens
+                .load(deps.storage, &pending.token_id)?;
+            accrue_fees(&mut token.extension, &pool_position)?;
+            token.extension.liquidity = token.extension.liquidity.checked_add(pending.liquidity)?;
+            position_nft()
+                .tokens
+                .save(deps.storage, &pending.token_id, &token)?;
+            token.extension
+        }
+    };
+
+    Ok(Response::new().add_messages(payment).add_event(
+        Event::new("increase_liquidity")
+            .add_attribute("token_id", pending.token_id)
+            .add_attribute("liquidity", position.liquidity)
+            .add_attribute("amount0", amount0_owed)
+            .add_attribute("amount1", amount1_owed),
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn decrease_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    liquidity: Uint128,
+    amount0_min: Uint128,
+    amount1_min: Uint128,
+) -> Result<Response, ContractError> {
+    if liquidity.is_zero() {
+        return Err(ContractError::ZeroLiquidity {});
+    }
+    let position = load_authorized(deps.as_ref(), &env, &info, &token_id)?.extension;
+    if position.liquidity < liquidity {
+        return Err(ContractError::InsufficientLiquidity {
+            token_id,
+            liquidity: position.liquidity,
+        });
+    }
+
+    PENDING_DECREASE.save(
+        deps.storage,
+        &PendingDecrease {
+            token_id: token_id.clone(),
+            liquidity,
+            amount0_min,
+            amount1_min,
+        },
+    )?;
+    let burn = WasmMsg::Execute {
+        contract_addr: position.pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Burn {
+            tick_lower: position.tick_lower,
+            tick_upper: position.tick_upper,
+            amount: liquidity,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(burn, DECREASE_REPLY_ID))
+        .add_attribute("action", "decrease_liquidity")
+        .add_attribute("token_id", token_id)
+        .add_attribute("liquidity", liquidity))
+}
+
+/// Credits a token the tokens released by burning its liquidity, plus the fees it earned
+fn settle_decrease(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let pending = PENDING_DECREASE.load(deps.storage)?;
+    PENDING_DECREASE.remove(deps.storage);
+    let mut token = position_nft()
+        .tokens
+        .load(deps.storage, &pending.token_id)?;
+    let position = &mut token.extension;
+
+    let events = msg
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .events;
+    let BurnEvent {
+        amount0, amount1, ..
+    } = BurnEvent::find(&events, &position.pool)?.ok_or(ContractError::MissingBurnAmounts {})?;
+    if amount0 < pending.amount0_min || amount1 < pending.amount1_min {
+        return Err(ContractError::PriceSlippageCheck {});
+    }
+
+    // burning pokes the pool position, so its fee growth snapshot is current
+    let pool_position = query_pool_position(
+        deps.as_ref(),
+        &env,
+        &position.pool,
+        position.tick_lower,
+        position.tick_upper,
+    )?;
+    accrue_fees(position, &pool_position)?;
+    position.tokens_owed0 = position.tokens_owed0.saturating_add(amount0);
+    position.tokens_owed1 = position.tokens_owed1.saturating_add(amount1);
+    position.liquidity -= pending.liquidity;
+    position_nft()
+        .tokens
+        .save(deps.storage, &pending.token_id, &token)?;
+
+    Ok(Response::new().add_event(
+        Event::new("decrease_liquidity")
+            .add_attribute("token_id", pending.token_id)
+            .add_attribute("liquidity", token.extension.liquidity)
+            .add_attribute("amount0", amount0)
+            .add_attribute("amount1", amount1),
+    ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn collect(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    recipient: String,
+    amount0_max: Uint128,
+    amount1_max: Uint128,
+) -> Result<Response, ContractError> {
+    let position = load_authorized(deps.as_ref(), &env, &info, &token_id)?.extension;
+    let pending = PendingCollect {
+        token_id,
+        recipient: deps.api.addr_validate(&recipient)?,
+        amount0_max,
+        amount1_max,
+    };
+    if position.liquidity.is_zero() {
+        return finish_collect(deps, env, pending);
+    }
+
+    // trigger an update of the position fees owed and fee growth snapshots if it has any liquidity
+    PENDING_COLLECT.save(deps.storage, &pending)?;
+    let poke = WasmMsg::Execute {
+        contract_addr: position.pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Burn {
+            tick_lower: position.tick_lower,
+            tick_upper: position.tick_upper,
+            amount: Uint128::zero(),
+        })?,
+        funds: vec![],
+    };
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(poke, COLLECT_REPLY_ID))
+        .add_attribute("action", "collect")
+        .add_attribute("token_id", pending.token_id))
+}
+
+/// Sends a token's tokens owed, up to the requested maximums, out of the pool to the recipient
+fn finish_collect(
+    deps: DepsMut,
+    env: Env,
+    pending: PendingCollect,
+) -> Result<Response, ContractError> {
+    let mut token = position_nft()
+        .tokens
+        .load(deps.storage, &pending.token_id)?;
+    let position = &mut token.extension;
+    if !position.liquidity.is_zero() {
+        let pool_position = query_pool_position(
+            deps.as_ref(),
+            &env,
+            &position.pool,
+            position.tick_lower,
+            position.tick_upper,
+        )?;
+        accrue_fees(position, &pool_position)?;
+    }
+
+    // compute the arguments to give to the pool collect
+    let amount0 = pending.amount0_max.min(position.tokens_owed0);
+    let amount1 = pending.amount1_max.min(position.tokens_owed1);
+    position.tokens_owed0 -= amount0;
+    position.tokens_owed1 -= amount1;
+    let collect = WasmMsg::Execute {
+        contract_addr: position.pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Collect {
+            recipient: pending.recipient.to_string(),
+            tick_lower: position.tick_lower,
+            tick_upper: position.tick_upper,
+            amount0_requested: amount0,
+            amount1_requested: amount1,
+        })?,
+        funds: vec![],
+    };
+    position_nft()
+        .tokens
+        .save(deps.storage, &pending.token_id, &token)?;
+
+    Ok(Response::new().add_message(collect).add_event(
+        Event::new("collect")
+            .add_attribute("token_id", pending.token_id)
+            .add_attribute("recipient", pending.recipient)
+            .add_attribute("amount0", amount0)
+            .add_attribute("amount1", amount1),
+    ))
+}
+
+fn burn(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let position = load_authorized(deps.as_ref(), &env, &info, &token_id)?.extension;
+    if !position.liquidity.is_zero()
+        || !position.tokens_owed0.is_zero()
+        || !position.tokens_owed1.is_zero()
+    {
+        return Err(ContractError::NotCleared {});
+    }
+    Ok(position_nft().execute(deps, env, info, Cw721ExecuteMsg::Burn { token_id })?)
+}
+
+/// Loads a token, failing unless the sender is its owner or approved for it
+fn load_authorized(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+    token_id: &str,
+) -> Result<TokenInfo<Position>, ContractError> {
+    let nft = position_nft();
+    let token = nft.tokens.load(deps.storage, token_id)?;
+    nft.check_can_send(deps, env, info, &token)?;
+    Ok(token)
+}
+
+/// The manager's shared position in a pool and range
+fn query_pool_position(
+    deps: Deps,
+    env: &Env,
+    pool: &Addr,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> StdResult<PositionInfo> {
+    deps.querier.query_wasm_smart(
+        pool,
+        &PoolQueryMsg::Position {
+            owner: env.contract.address.to_string(),
+            tick_lower,
+            tick_upper,
+        },
+    )
+}
+
+/// Credits a position the fees its liquidity earned up to the shared pool position's fee growth
+fn accrue_fees(position: &mut Position, pool_position: &PositionInfo) -> StdResult<()> {
+    let fees0 = fees_earned(
+        pool_position.fee_growth_inside0_last_x128,
+        position.fee_growth_inside0_last_x128,
+        position.liquidity,
+    )?;
+    let fees1 = fees_earned(
+        pool_position.fee_growth_inside1_last_x128,
+        position.fee_growth_inside1_last_x128,
+        position.liquidity,
+    )?;
+    position.tokens_owed0 = position.tokens_owed0.saturating_add(fees0);
+    position.tokens_owed1 = position.tokens_owed1.saturating_add(fees1);
+    position.fee_growth_inside0_last_x128 = pool_position.fee_growth_inside0_last_x128;
+    position.fee_growth_inside1_last_x128 = pool_position.fee_growth_inside1_last_x128;
+    Ok(())
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        factory: config.factory,
+    })
+}
@@ -0,0 +1,48 @@
+use cosmwasm_std::{ConversionOverflowError, OverflowError, StdError, Uint128};
+use liquidity_pool::helpers::MintPaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("{0}")]
+    Cw721(#[from] cw721_base::ContractError),
+
+    #[error("{pool} is not a pool of the factory")]
+    InvalidPool { pool: String },
+
+    #[error("Liquidity must be greater than zero")]
+    ZeroLiquidity {},
+
+    #[error("Position {token_id} holds {liquidity} liquidity")]
+    InsufficientLiquidity {
+        token_id: String,
+        liquidity: Uint128,
+    },
+
+    #[error("Position is not cleared")]
+    NotCleared {},
+
+    #[error("Price slippage check")]
+    PriceSlippageCheck {},
+
+    #[error("{0}")]
+    MintPayment(#[from] MintPaymentError),
+
+    #[error("No liquidity is waiting for a mint callback from the sender")]
+    UnexpectedCallback {},
+
+    #[error("Burn of the decreased liquidity reported no amounts")]
+    MissingBurnAmounts {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+}
@@ -0,0 +1,149 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Uint128, Uint256};
+use cw721::Expiration;
+
+use crate::state::Position;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The factory whose pools positions may be opened in
+    pub factory: String,
+    /// Name of the position NFT collection
+    pub name: String,
+    /// Symbol of the position NFT collection
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a position over [tick_lower, tick_upper) in `pool` holding `liquidity`, minting a token for it to
+    /// `recipient`. The attached funds pay for the liquidity and whatever is left is refunded
+    Mint {
+        pool: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: Uint128,
+        recipient: String,
+    },
+    /// Adds `liquidity` to a position, paid with the attached funds; anyone may add to any position
+    IncreaseLiquidity {
+        token_id: String,
+        liquidity: Uint128,
+    },
+    /// Burns `liquidity` from a position, crediting the tokens to the position's tokens owed
+    /// Fails unless the burn releases at least the minimum amounts
+    DecreaseLiquidity {
+        token_id: String,
+        liquidity: Uint128,
+        amount0_min: Uint128,
+        amount1_min: Uint128,
+    },
+    /// Sends up to the maximum amounts of the tokens owed to a position, including its uncredited fees, to
+    /// `recipient`
+    Collect {
+        token_id: String,
+        recipient: String,
+        amount0_max: Uint128,
+        amount1_max: Uint128,
+    },
+    /// Burns the token of a position that has no liquidity and no tokens owed left
+    Burn { token_id: String },
+    /// Pays a pool for liquidity minted by `Mint` or `IncreaseLiquidity`; only callable by that pool
+    XionMintCallback {
+        amount0_owed: Uint256,
+        amount1_owed: Uint256,
+        data: Binary,
+    },
+    /// Moves a token to another account
+    TransferNft { recipient: String, token_id: String },
+    /// Moves a token to a contract and calls `ReceiveNft` on it
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    /// Allows `spender` to transfer, send or manage a token until the expiration
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    /// Removes an approval granted with `Approve`
+    Revoke { spender: String, token_id: String },
+    /// Allows `operator` to transfer, send or manage any of the sender's tokens until the expiration
+    ApproveAll {
+        operator: String,
+        expires: Option<Expiration>,
+    },
+    /// Removes an approval granted with `ApproveAll`
+    RevokeAll { operator: String },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// The position behind a token
+    #[returns(Position)]
+    Position { token_id: String },
+    #[returns(cw721::OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    #[returns(cw721::ApprovalResponse)]
+    Approval {
+        token_id: String,
+        spender: String,
+        include_expired: Option<bool>,
+    },
+    #[returns(cw721::ApprovalsResponse)]
+    Approvals {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    #[returns(cw721::OperatorResponse)]
+    Operator {
+        owner: String,
+        operator: String,
+        include_expired: Option<bool>,
+    },
+    #[returns(cw721::OperatorsResponse)]
+    AllOperators {
+        owner: String,
+        include_expired: Option<bool>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// The number of positions that have not been burned
+    #[returns(cw721::NumTokensResponse)]
+    NumTokens {},
+    #[returns(cw721::ContractInfoResponse)]
+    ContractInfo {},
+    /// A token's metadata, which is its position
+    #[returns(cw721::NftInfoResponse<Position>)]
+    NftInfo { token_id: String },
+    #[returns(cw721::AllNftInfoResponse<Position>)]
+    AllNftInfo {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    /// The tokens owned by `owner`
+    #[returns(cw721::TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(cw721::TokensResponse)]
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub factory: Addr,
+}
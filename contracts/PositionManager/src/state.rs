@@ -0,0 +1,80 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Empty, StdResult, Storage, Uint128, Uint256};
+use cw721_base::Cw721Contract;
+use cw_storage_plus::Item;
+
+#[cw_serde]
+pub struct Config {
+    pub factory: Addr,
+}
+
+/// A position held by the manager in a pool, carried on chain as the metadata of its token
+/// The manager's pool position for a range is shared by every token over that range, so each token tracks the fee
+/// growth it has been credited up to itself
+#[cw_serde]
+pub struct Position {
+    pub pool: Addr,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: Uint128,
+    pub fee_growth_inside0_last_x128: Uint256,
+    pub fee_growth_inside1_last_x128: Uint256,
+    /// Tokens owed to the position from burned liquidity and fees, as of the last action on it
+    pub tokens_owed0: Uint128,
+    pub tokens_owed1: Uint128,
+}
+
+/// The CW721 token contract, with positions as token metadata
+pub type PositionNft<'a> = Cw721Contract<'a, Position, Empty, Empty, Empty>;
+
+/// Liquidity being minted for a new or existing token, completed in the pool's mint callback
+#[cw_serde]
+pub struct PendingMint {
+    pub token_id: String,
+    /// The owner of the token to create; `None` when adding to an existing token
+    pub recipient: Option<Addr>,
+    pub pool: Addr,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: Uint128,
+    pub payer: Addr,
+    /// The funds the payer attached to pay for the liquidity
+    pub funds: Vec<Coin>,
+}
+
+/// Liquidity being burned from a token, completed in the burn reply
+#[cw_serde]
+pub struct PendingDecrease {
+    pub token_id: String,
+    pub liquidity: Uint128,
+    pub amount0_min: Uint128,
+    pub amount1_min: Uint128,
+}
+
+/// A collection waiting for the poke that brings the token's fees up to date
+#[cw_serde]
+pub struct PendingCollect {
+    pub token_id: String,
+    pub recipient: Addr,
+    pub amount0_max: Uint128,
+    pub amount1_max: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PENDING_MINT: Item<PendingMint> = Item::new("pending_mint");
+pub const PENDING_DECREASE: Item<PendingDecrease> = Item::new("pending_decrease");
+pub const PENDING_COLLECT: Item<PendingCollect> = Item::new("pending_collect");
+
+/// Token ids are never reused, unlike the CW721 token count which drops on burn
+pub const TOKEN_ID_COUNT: Item<u64> = Item::new("token_id_count");
+
+pub fn position_nft() -> PositionNft<'static> {
+    PositionNft::default()
+}
+
+/// Returns the next token id, starting at 1
+pub fn next_token_id(storage: &mut dyn Storage) -> StdResult<String> {
+    let id = TOKEN_ID_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    TOKEN_ID_COUNT.save(storage, &id)?;
+    Ok(id.to_string())
+}
@@ -0,0 +1,434 @@
+use cosmwasm_std::{coin, Addr, Empty, Int256, Uint128, Uint256};
+use cw721::{NftInfoResponse, NumTokensResponse, OwnerOfResponse};
+use cw721_base::OwnershipError;
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use factory::testing::{attribute, deploy_factory, deploy_pool, OWNER};
+use libraries::fixed_point_96::FixedPoint96;
+use libraries::tick_math::MIN_SQRT_RATIO;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::state::Position;
+
+const TOKEN0: &str = "uatom";
+const TOKEN1: &str = "uxion";
+const FEE: u32 = 3000;
+
+fn manager_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply(crate::contract::reply),
+    )
+}
+
+struct Suite {
+    app: App,
+    pool: Addr,
+    manager: Addr,
+}
+
+impl Suite {
+    /// Deploys a factory with an initialized uatom/uxion pool, and a position manager for the factory's pools
+    fn new() -> Self {
+        let mut app = App::new(|router, _, storage| {
+            for user in ["alice", "bob", "trader"] {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(user),
+                        vec![coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)],
+                    )
+                    .unwrap();
+            }
+        });
+        let factory = deploy_factory(&mut app);
+        let pool = deploy_pool(&mut app, &factory, TOKEN0, TOKEN1, FEE, FixedPoint96::Q96);
+
+        let manager_code_id = app.store_code(manager_contract());
+        let manager = app
+            .instantiate_contract(
+                manager_code_id,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg {
+                    factory: factory.to_string(),
+                    name: "Xion Positions".to_string(),
+                    symbol: "XION-POS".to_string(),
+                },
+                &[],
+                "manager",
+                None,
+            )
+            .unwrap();
+
+        Suite { app, pool, manager }
+    }
+
+    fn execute(&mut self, sender: &str, msg: &ExecuteMsg) -> anyhow::Result<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.manager.clone(), msg, &[])
+    }
+
+    fn mint(&mut self, owner: &str, liquidity: u128) -> String {
+        let res = self
+            .app
+            .execute_contract(
+                Addr::unchecked(owner),
+                self.manager.clone(),
+                &ExecuteMsg::Mint {
+                    pool: self.pool.to_string(),
+                    tick_lower: -600,
+                    tick_upper: 600,
+                    liquidity: Uint128::new(liquidity),
+                    recipient: owner.to_string(),
+                },
+                &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)],
+            )
+            .unwrap();
+        attribute(&res, "token_id")
+    }
+
+    fn swap(&mut self, amount: u128) {
+        self.app
+            .execute_contract(
+                Addr::unchecked("trader"),
+                self.pool.clone(),
+                &liquidity_pool::msg::ExecuteMsg::Swap {
+                    recipient: "trader".to_string(),
+                    zero_for_one: true,
+                    amount_specified: Int256::from(amount as i128),
+                    sqrt_price_limit_x96: MIN_SQRT_RATIO + Uint256::one(),
+                    data: Default::default(),
+                },
+                &[coin(amount, TOKEN0)],
+            )
+            .unwrap();
+    }
+
+    fn collect(&mut self, sender: &str, token_id: &str) -> anyhow::Result<AppResponse> {
+        self.execute(
+            sender,
+            &ExecuteMsg::Collect {
+                token_id: token_id.to_string(),
+                recipient: sender.to_string(),
+                amount0_max: Uint128::MAX,
+                amount1_max: Uint128::MAX,
+            },
+        )
+    }
+
+    fn position(&self, token_id: &str) -> Position {
+        self.query(&QueryMsg::Position {
+            token_id: token_id.to_string(),
+        })
+    }
+
+    fn balance(&self, address: &str, denom: &str) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, denom)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(&self, msg: &QueryMsg) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.manager, msg)
+            .unwrap()
+    }
+}
+
+fn contract_error(err: &anyhow::Error) -> &ContractError {
+    err.root_cause().downcast_ref::<ContractError>().unwrap()
+}
+
+#[test]
+fn mint_issues_a_token_holding_the_position() {
+    let mut suite = Suite::new();
+    let token_id = suite.mint("alice", 1_000_000);
+
+    let owner: OwnerOfResponse = suite.query(&QueryMsg::OwnerOf {
+        token_id: token_id.clone(),
+        include_expired: None,
+    });
+    assert_eq!(owner.owner, "alice");
+    let info: NftInfoResponse<Position> = suite.query(&QueryMsg::NftInfo {
+        token_id: token_id.clone(),
+    });
+    assert_eq!(info.extension.pool, suite.pool);
+    assert_eq!(
+        (info.extension.tick_lower, info.extension.tick_upper),
+        (-600, 600)
+    );
+    assert_eq!(info.extension.liquidity.u128(), 1_000_000);
+
+    // the pool is paid out of the attached funds and the rest is refunded
+    let paid0 = 1_000_000 - suite.balance("alice", TOKEN0);
+    assert!(paid0 > 0 && paid0 < 100_000);
+    assert_eq!(suite.balance(suite.pool.as_str(), TOKEN0), paid0);
+    assert_eq!(suite.balance(suite.manager.as_str(), TOKEN0), 0);
+
+    // anyone may add liquidity to a position
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("bob"),
+            suite.manager.clone(),
+            &ExecuteMsg::IncreaseLiquidity {
+                token_id: token_id.clone(),
+                liquidity: Uint128::new(500_000),
+            },
+            &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)],
+        )
+        .unwrap();
+    assert_eq!(suite.position(&token_id).liquidity.u128(), 1_500_000);
+    assert!(suite.balance("bob", TOKEN0) < 1_000_000);
+
+    let err = suite
+        .execute(
+            "alice",
+            &ExecuteMsg::XionMintCallback {
+                amount0_owed: Uint256::one(),
+                amount1_owed: Uint256::one(),
+                data: Default::default(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::UnexpectedCallback {});
+}
+
+#[test]
+fn transferred_positions_are_managed_by_their_new_owner() {
+    let mut suite = Suite::new();
+    let alice = suite.mint("alice", 1_000_000);
+    let bob = suite.mint("bob", 1_000_000);
+    suite.swap(10_000);
+
+    suite
+        .execute(
+            "alice",
+            &ExecuteMsg::TransferNft {
+                recipient: "carol".to_string(),
+                token_id: alice.clone(),
+            },
+        )
+        .unwrap();
+    let err = suite.collect("alice", &alice).unwrap_err();
+    assert_eq!(
+        err.root_cause().downcast_ref::<cw721_base::ContractError>(),
+        Some(&OwnershipError::NotOwner.into())
+    );
+
+    // the shared pool position's fees are split between the tokens by liquidity
+    let res = suite.collect("carol", &alice).unwrap();
+    let carol_fees: u128 = attribute(&res, "amount0").parse().unwrap();
+    let res = suite.collect("bob", &bob).unwrap();
+    let bob_fees: u128 = attribute(&res, "amount0").parse().unwrap();
+    assert!(carol_fees > 0);
+    assert!(carol_fees.abs_diff(bob_fees) <= 1);
+    assert_eq!(suite.balance("carol", TOKEN0), carol_fees);
+    assert!(suite.position(&alice).tokens_owed0.is_zero());
+
+    // an approved spender may manage the position too
+    suite
+        .execute(
+            "carol",
+            &ExecuteMsg::Approve {
+                spender: "dave".to_string(),
+                token_id: alice.clone(),
+                expires: None,
+            },
+        )
+        .unwrap();
+    let decrease = |amount0_min| ExecuteMsg::DecreaseLiquidity {
+        token_id: alice.clone(),
+        liquidity: Uint128::new(1_000_000),
+        amount0_min: Uint128::new(amount0_min),
+        amount1_min: Uint128::zero(),
+    };
+    let err = suite.execute("dave", &decrease(1_000_000)).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::PriceSlippageCheck {}.to_string()
+    );
+    suite.execute("dave", &decrease(0)).unwrap();
+    let position = suite.position(&alice);
+    assert!(position.liquidity.is_zero());
+    assert!(!position.tokens_owed0.is_zero());
+
+    let burn = ExecuteMsg::Burn {
+        token_id: alice.clone(),
+    };
+    let err = suite.execute("carol", &burn).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::NotCleared {});
+    suite.collect("carol", &alice).unwrap();
+    assert!(suite.balance("carol", TOKEN0) > carol_fees);
+    assert!(suite.balance("carol", TOKEN1) > 0);
+    suite.execute("carol", &burn).unwrap();
+
+    let tokens: NumTokensResponse = suite.query(&QueryMsg::NumTokens {});
+    assert_eq!(tokens.count, 1);
+    // bob's liquidity is untouched
+    assert_eq!(suite.position(&bob).liquidity.u128(), 1_000_000);
+}
+
+#[test]
+fn decrease_liquidity_checks_slippage() {
+    let mut suite = Suite::new();
+    let token_id = suite.mint("alice", 1_000_000);
+    let decrease = |liquidity, amount0_min, amount1_min| ExecuteMsg::DecreaseLiquidity {
+        token_id: token_id.clone(),
+        liquidity: Uint128::new(liquidity),
+        amount0_min: Uint128::new(amount0_min),
+        amount1_min: Uint128::new(amount1_min),
+    };
+
+    let err = suite.execute("alice", &decrease(0, 0, 0)).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::ZeroLiquidity {});
+    let err = suite
+        .execute("alice", &decrease(1_000_001, 0, 0))
+        .unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::InsufficientLiquidity {
+            token_id: token_id.clone(),
+            liquidity: Uint128::new(1_000_000),
+        }
+    );
+
+    // either minimum above the burned amounts reverts the whole decrease
+    for (amount0_min, amount1_min) in [(100_000, 0), (0, 100_000)] {
+        let err = suite
+            .execute("alice", &decrease(500_000, amount0_min, amount1_min))
+            .unwrap_err();
+        assert_eq!(
+            err.root_cause().to_string(),
+            ContractError::PriceSlippageCheck {}.to_string()
+        );
+        let position = suite.position(&token_id);
+        assert_eq!(position.liquidity.u128(), 1_000_000);
+        assert!(position.tokens_owed0.is_zero() && position.tokens_owed1.is_zero());
+    }
+
+    // minimums equal to the burned amounts pass
+    let res = suite.execute("alice", &decrease(500_000, 0, 0)).unwrap();
+    let amount0: u128 = attribute(&res, "amount0").parse().unwrap();
+    let amount1: u128 = attribute(&res, "amount1").parse().unwrap();
+    assert!(amount0 > 0 && amount1 > 0);
+    suite
+        .execute("alice", &decrease(500_000, amount0, amount1))
+        .unwrap();
+    let position = suite.position(&token_id);
+    assert!(position.liquidity.is_zero());
+    assert_eq!(position.tokens_owed0.u128(), 2 * amount0);
+    assert_eq!(position.tokens_owed1.u128(), 2 * amount1);
+}
+
+#[test]
+fn collect_with_and_without_liquidity() {
+    let mut suite = Suite::new();
+    let token_id = suite.mint("alice", 1_000_000);
+    suite.swap(10_000);
+    let collect = |amount0_max: u128, amount1_max: u128| ExecuteMsg::Collect {
+        token_id: token_id.clone(),
+        recipient: "carol".to_string(),
+        amount0_max: Uint128::new(amount0_max),
+        amount1_max: Uint128::new(amount1_max),
+    };
+
+    // with liquidity the pool position is poked first, so the fees are credited before they are collected
+    assert!(suite.position(&token_id).tokens_owed0.is_zero());
+    let res = suite.execute("alice", &collect(1, 0)).unwrap();
+    assert_eq!(attribute(&res, "amount0"), "1");
+    assert_eq!(suite.balance("carol", TOKEN0), 1);
+    let fees0 = suite.position(&token_id).tokens_owed0.u128();
+    assert!(fees0 > 0);
+
+    let res = suite
+        .execute("alice", &collect(u128::MAX, u128::MAX))
+        .unwrap();
+    assert_eq!(attribute(&res, "amount0"), fees0.to_string());
+    assert_eq!(attribute(&res, "amount1"), "0");
+    assert_eq!(suite.balance("carol", TOKEN0), fees0 + 1);
+    assert!(suite.position(&token_id).tokens_owed0.is_zero());
+
+    // without liquidity there is nothing to poke, only the tokens owed are collected
+    let res = suite
+        .execute(
+            "alice",
+            &ExecuteMsg::DecreaseLiquidity {
+                token_id: token_id.clone(),
+                liquidity: Uint128::new(1_000_000),
+                amount0_min: Uint128::zero(),
+                amount1_min: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    let amount0: u128 = attribute(&res, "amount0").parse().unwrap();
+    let amount1: u128 = attribute(&res, "amount1").parse().unwrap();
+    let res = suite
+        .execute("alice", &collect(u128::MAX, u128::MAX))
+        .unwrap();
+    assert_eq!(attribute(&res, "amount0"), amount0.to_string());
+    assert_eq!(attribute(&res, "amount1"), amount1.to_string());
+    assert_eq!(suite.balance("carol", TOKEN0), fees0 + 1 + amount0);
+    assert_eq!(suite.balance("carol", TOKEN1), amount1);
+
+    let res = suite
+        .execute("alice", &collect(u128::MAX, u128::MAX))
+        .unwrap();
+    assert_eq!(attribute(&res, "amount0"), "0");
+    assert_eq!(attribute(&res, "amount1"), "0");
+}
+
+#[test]
+fn burn_requires_a_cleared_position() {
+    let mut suite = Suite::new();
+    let token_id = suite.mint("alice", 1_000_000);
+    let burn = ExecuteMsg::Burn {
+        token_id: token_id.clone(),
+    };
+
+    let err = suite.execute("alice", &burn).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::NotCleared {});
+
+    suite
+        .execute(
+            "alice",
+            &ExecuteMsg::DecreaseLiquidity {
+                token_id: token_id.clone(),
+                liquidity: Uint128::new(1_000_000),
+                amount0_min: Uint128::zero(),
+                amount1_min: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    let err = suite.execute("alice", &burn).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::NotCleared {});
+
+    // any token still owed keeps the position
+    suite
+        .execute(
+            "alice",
+            &ExecuteMsg::Collect {
+                token_id: token_id.clone(),
+                recipient: "alice".to_string(),
+                amount0_max: Uint128::MAX,
+                amount1_max: Uint128::zero(),
+            },
+        )
+        .unwrap();
+    let err = suite.execute("alice", &burn).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::NotCleared {});
+
+    suite.collect("alice", &token_id).unwrap();
+    suite.execute("alice", &burn).unwrap();
+    let tokens: NumTokensResponse = suite.query(&QueryMsg::NumTokens {});
+    assert_eq!(tokens.count, 0);
+}
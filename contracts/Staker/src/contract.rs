@@ -0,0 +1,712 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Liquidity mining
+//! Anyone can fund a reward program for a pool over a period of time. Liquidity providers deposit liquidity through
+//! the staker and stake the deposit in the program; rewards are shared out in proportion to the seconds each staked
+//! deposit's liquidity spent in range, read from the pool's `SnapshotCumulativesInside`.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Binary, Deps, DepsMut, Env, Event, MessageInfo, Order, Reply, Response,
+    StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::Bound;
+use cw_utils::one_coin;
+use factory::helpers::FactoryContract;
+use interfaces::pool::events::BurnEvent;
+use interfaces::pool::{IXionPoolDerivedState, IXionPoolEvent};
+use libraries::full_math::FullMath;
+use libraries::position::{fees_earned, PositionInfo};
+use libraries::transfer_helper::{safe_transfer, Asset};
+use liquidity_pool::helpers::XionPoolContract;
+use liquidity_pool::msg::{ExecuteMsg as PoolExecuteMsg, QueryMsg as PoolQueryMsg};
+
+use crate::error::ContractError;
+use crate::msg::{
+    AssetInfo, ConfigResponse, DepositResponse, DepositsResponse, ExecuteMsg, InstantiateMsg,
+    QueryMsg, ReceiveMsg, RewardInfoResponse,
+};
+use crate::state::{
+    deposits, next_id, Config, Deposit, Incentive, PendingDeposit, PendingWithdrawal, Stake,
+    CONFIG, DEPOSIT_COUNT, INCENTIVES, INCENTIVE_COUNT, PENDING_DEPOSIT, PENDING_WITHDRAWAL,
+    REWARDS, STAKES,
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub const WITHDRAW_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = Config {
+        factory: deps.api.addr_validate(&msg.factory)?,
+        max_incentive_start_lead_time: msg.max_incentive_start_lead_time,
+        max_incentive_duration: msg.max_incentive_duration,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("factory", config.factory))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateIncentive {
+            pool,
+            start_time,
+            end_time,
+            refundee,
+        } => {
+            let reward = one_coin(&info)?;
+            create_incentive(
+                deps,
+                env,
+                pool,
+                start_time,
+                end_time,
+                refundee,
+                AssetInfo::native(reward.denom).with_amount(reward.amount),
+            )
+        }
+        ExecuteMsg::EndIncentive { incentive_id } => end_incentive(deps, env, incentive_id),
+        ExecuteMsg::Deposit {
+            pool,
+            tick_lower,
+            tick_upper,
+            liquidity,
+        } => deposit(deps, env, info, pool, tick_lower, tick_upper, liquidity),
+        ExecuteMsg::XionMintCallback {
+            amount0_owed,
+            amount1_owed,
+            ..
+        } => mint_callback(deps, env, info, amount0_owed, amount1_owed),
+        ExecuteMsg::Withdraw {
+            deposit_id,
+            recipient,
+        } => withdraw(deps, info, deposit_id, recipient),
+        ExecuteMsg::StakeToken {
+            incentive_id,
+            deposit_id,
+        } => stake_token(deps, env, info, incentive_id, deposit_id),
+        ExecuteMsg::UnstakeToken {
+            incentive_id,
+            deposit_id,
+        } => unstake_token(deps, env, info, incentive_id, deposit_id),
+        ExecuteMsg::ClaimReward {
+            reward_token,
+            recipient,
+            amount_requested,
+        } => claim_reward(deps, info, reward_token, recipient, amount_requested),
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        WITHDRAW_REPLY_ID => settle_withdrawal(deps, env, msg),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Incentive { incentive_id } => {
+            to_binary(&INCENTIVES.load(deps.storage, incentive_id)?)
+        }
+        QueryMsg::Deposit { deposit_id } => to_binary(&deposits().load(deps.storage, deposit_id)?),
+        QueryMsg::DepositsByOwner {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_deposits_by_owner(deps, owner, start_after, limit)?),
+        QueryMsg::Stake {
+            deposit_id,
+            incentive_id,
+        } => to_binary(&STAKES.load(deps.storage, (deposit_id, incentive_id))?),
+        QueryMsg::Rewards {
+            owner,
+            reward_token,
+        } => {
+            let owner = deps.api.addr_validate(&owner)?;
+            to_binary(
+                &REWARDS
+                    .may_load(deps.storage, (&owner, &reward_token.to_string()))?
+                    .unwrap_or_default(),
+            )
+        }
+        QueryMsg::RewardInfo {
+            incentive_id,
+            deposit_id,
+        } => {
+            let incentive = INCENTIVES.load(deps.storage, incentive_id)?;
+            let deposit = deposits().load(deps.storage, deposit_id)?;
+            let stake = STAKES.load(deps.storage, (deposit_id, incentive_id))?;
+            let (reward, seconds_inside_x128) =
+                reward_info(deps, &env, &incentive, &deposit, &stake)?;
+            to_binary(&RewardInfoResponse {
+                reward,
+                seconds_inside_x128,
+            })
+        }
+    }
+}
+
+fn receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let reward = AssetInfo::cw20(info.sender).with_amount(msg.amount);
+    match from_binary(&msg.msg)? {
+        ReceiveMsg::CreateIncentive {
+            pool,
+            start_time,
+            end_time,
+            refundee,
+        } => create_incentive(deps, env, pool, start_time, end_time, refundee, reward),
+    }
+}
+
+fn create_incentive(
+    deps: DepsMut,
+    env: Env,
+    pool: String,
+    start_time: u64,
+    end_time: u64,
+    refundee: String,
+    reward: Asset,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pool = deps.api.addr_validate(&pool)?;
+    let refundee = deps.api.addr_validate(&refundee)?;
+    let now = env.block.time.seconds();
+
+    if reward.amount.is_zero() {
+        return Err(ContractError::ZeroReward {});
+    }
+    if start_time < now {
+        return Err(ContractError::StartTimeInPast {});
+    }
+    if start_time - now > config.max_incentive_start_lead_time {
+        return Err(ContractError::StartTimeTooFarInFuture {});
+    }
+    if start_time >= end_time {
+        return Err(ContractError::StartTimeNotBeforeEndTime {});
+    }
+    if end_time - start_time > config.max_incentive_duration {
+        return Err(ContractError::IncentiveTooLong {});
+    }
+    if !FactoryContract(config.factory.clone()).is_pool(&deps.querier, &pool) {
+        return Err(ContractError::InvalidPool {
+            pool: pool.to_string(),
+        });
+    }
+
+    let incentive_id = next_id(deps.storage, &INCENTIVE_COUNT)?;
+    INCENTIVES.save(
+        deps.storage,
+        incentive_id,
+        &Incentive {
+            reward_token: reward.info.clone(),
+            pool: pool.clone(),
+            start_time,
+            end_time,
+            refundee: refundee.clone(),
+            total_reward_unclaimed: reward.amount,
+            total_seconds_claimed_x128: Uint256::zero(),
+            number_of_stakes: 0,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_incentive")
+        .add_attribute("incentive_id", incentive_id.to_string())
+        .add_attribute("reward_token", reward.info.to_string())
+        .add_attribute("reward", reward.amount)
+        .add_attribute("pool", pool)
+        .add_attribute("start_time", start_time.to_string())
+        .add_attribute("end_time", end_time.to_string())
+        .add_attribute("refundee", refundee))
+}
+
+fn end_incentive(deps: DepsMut, env: Env, incentive_id: u64) -> Result<Response, ContractError> {
+    let mut incentive = load_incentive(deps.as_ref(), incentive_id)?;
+    if env.block.time.seconds() < incentive.end_time {
+        return Err(ContractError::IncentiveNotEnded {});
+    }
+    let refund = incentive.total_reward_unclaimed;
+    if refund.is_zero() {
+        return Err(ContractError::NoRefund {});
+    }
+    if incentive.number_of_stakes > 0 {
+        return Err(ContractError::StakesRemaining {});
+    }
+
+    // issue the refund
+    incentive.total_reward_unclaimed = Uint128::zero();
+    INCENTIVES.save(deps.storage, incentive_id, &incentive)?;
+
+    Ok(Response::new()
+        .add_message(safe_transfer(
+            &incentive.reward_token,
+            &incentive.refundee,
+            refund,
+        )?)
+        .add_attribute("action", "end_incentive")
+        .add_attribute("incentive_id", incentive_id.to_string())
+        .add_attribute("refund", refund))
+}
+
+fn deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool: String,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: Uint128,
+) -> Result<Response, ContractError> {
+    if liquidity.is_zero() {
+        return Err(ContractError::ZeroLiquidity {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let pool = deps.api.addr_validate(&pool)?;
+    if !FactoryContract(config.factory.clone()).is_pool(&deps.querier, &pool) {
+        return Err(ContractError::InvalidPool {
+            pool: pool.to_string(),
+        });
+    }
+
+    // the pool calls back into `mint_callback` to be paid, which completes the deposit
+    let deposit_id = next_id(deps.storage, &DEPOSIT_COUNT)?;
+    PENDING_DEPOSIT.save(
+        deps.storage,
+        &PendingDeposit {
+            id: deposit_id,
+            deposit: Deposit {
+                owner: info.sender.clone(),
+                pool: pool.clone(),
+                tick_lower,
+                tick_upper,
+                liquidity,
+                fee_growth_inside0_last_x128: Uint256::zero(),
+                fee_growth_inside1_last_x128: Uint256::zero(),
+                number_of_stakes: 0,
+            },
+            funds: info.funds,
+        },
+    )?;
+    let mint = WasmMsg::Execute {
+        contract_addr: pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Mint {
+            recipient: env.contract.address.to_string(),
+            tick_lower,
+            tick_upper,
+            amount: liquidity,
+            data: to_binary(&deposit_id)?,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(mint)
+        .add_attribute("action", "deposit")
+        .add_attribute("deposit_id", deposit_id.to_string())
+        .add_attribute("owner", info.sender)
+        .add_attribute("pool", pool)
+        .add_attribute("tick_lower", tick_lower.to_string())
+        .add_attribute("tick_upper", tick_upper.to_string())
+        .add_attribute("liquidity", liquidity))
+}
+
+/// Pays the pool for a pending deposit out of the funds its owner attached, refunding the rest
+fn mint_callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount0_owed: Uint256,
+    amount1_owed: Uint256,
+) -> Result<Response, ContractError> {
+    let Some(PendingDeposit {
+        id,
+        mut deposit,
+        funds,
+    }) = PENDING_DEPOSIT.may_load(deps.storage)?
+    else {
+        return Err(ContractError::UnexpectedCallback {});
+    };
+    if info.sender != deposit.pool {
+        return Err(ContractError::UnexpectedCallback {});
+    }
+    PENDING_DEPOSIT.remove(deps.storage);
+
+    let payment = XionPoolContract(deposit.pool.clone()).pay_from_funds(
+        &deps.querier,
+        funds,
+        &deposit.owner,
+        amount0_owed,
+        amount1_owed,
+    )?;
+
+    // fees the shared position earned before this deposit belong to the deposits already in it
+    let position = query_position(deps.as_ref(), &env, &deposit)?;
+    deposit.fee_growth_inside0_last_x128 = position.fee_growth_inside0_last_x128;
+    deposit.fee_growth_inside1_last_x128 = position.fee_growth_inside1_last_x128;
+    deposits().save(deps.storage, id, &deposit)?;
+
+    Ok(Response::new()
+        .add_messages(payment)
+        .add_attribute("action", "deposit_paid")
+        .add_attribute("deposit_id", id.to_string()))
+}
+
+fn withdraw(
+    deps: DepsMut,
+    info: MessageInfo,
+    deposit_id: u64,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let deposit = load_own_deposit(deps.as_ref(), &info, deposit_id)?;
+    if deposit.number_of_stakes > 0 {
+        return Err(ContractError::DepositStaked {
+            stakes: deposit.number_of_stakes,
+        });
+    }
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    deposits().remove(deps.storage, deposit_id)?;
+    PENDING_WITHDRAWAL.save(
+        deps.storage,
+        &PendingWithdrawal {
+            deposit: deposit.clone(),
+            recipient: recipient.clone(),
+        },
+    )?;
+    let burn = WasmMsg::Execute {
+        contract_addr: deposit.pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Burn {
+            tick_lower: deposit.tick_lower,
+            tick_upper: deposit.tick_upper,
+            amount: deposit.liquidity,
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(burn, WITHDRAW_REPLY_ID))
+        .add_attribute("action", "withdraw")
+        .add_attribute("deposit_id", deposit_id.to_string())
+        .add_attribute("recipient", recipient))
+}
+
+/// Collects the burned liquidity of a withdrawn deposit, plus the fees it earned, to the recipient
+fn settle_withdrawal(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let PendingWithdrawal { deposit, recipient } = PENDING_WITHDRAWAL.load(deps.storage)?;
+    PENDING_WITHDRAWAL.remove(deps.storage);
+
+    let events = msg
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .events;
+    let BurnEvent {
+        amount0, amount1, ..
+    } = BurnEvent::find(&events, &deposit.pool)?.ok_or(ContractError::MissingBurnAmounts {})?;
+
+    // the burn brought the shared position's fee growth up to date
+    let position = query_position(deps.as_ref(), &env, &deposit)?;
+    let fees0 = fees_earned(
+        position.fee_growth_inside0_last_x128,
+        deposit.fee_growth_inside0_last_x128,
+        deposit.liquidity,
+    )?;
+    let fees1 = fees_earned(
+        position.fee_growth_inside1_last_x128,
+        deposit.fee_growth_inside1_last_x128,
+        deposit.liquidity,
+    )?;
+
+    let collect = WasmMsg::Execute {
+        contract_addr: deposit.pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Collect {
+            recipient: recipient.to_string(),
+            tick_lower: deposit.tick_lower,
+            tick_upper: deposit.tick_upper,
+            amount0_requested: amount0.saturating_add(fees0),
+            amount1_requested: amount1.saturating_add(fees1),
+        })?,
+        funds: vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(collect)
+        .add_attribute("action", "withdraw_collect")
+        .add_attribute("amount0", amount0)
+        .add_attribute("amount1", amount1)
+        .add_attribute("fees0", fees0)
+        .add_attribute("fees1", fees1))
+}
+
+fn stake_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    incentive_id: u64,
+    deposit_id: u64,
+) -> Result<Response, ContractError> {
+    let mut deposit = load_own_deposit(deps.as_ref(), &info, deposit_id)?;
+    let mut incentive = load_incentive(deps.as_ref(), incentive_id)?;
+    let now = env.block.time.seconds();
+
+    if now < incentive.start_time {
+        return Err(ContractError::IncentiveNotStarted {});
+    }
+    if now >= incentive.end_time {
+        return Err(ContractError::IncentiveEnded {});
+    }
+    if deposit.pool != incentive.pool {
+        return Err(ContractError::PoolMismatch {});
+    }
+    if STAKES.has(deps.storage, (deposit_id, incentive_id)) {
+        return Err(ContractError::AlreadyStaked {});
+    }
+
+    let (_, seconds_per_liquidity_inside_x128, _) = XionPoolContract(deposit.pool.clone())
+        .snapshot_cumulatives_inside(&deps.querier, deposit.tick_lower, deposit.tick_upper)?;
+
+    deposit.number_of_stakes += 1;
+    deposits().save(deps.storage, deposit_id, &deposit)?;
+    incentive.number_of_stakes += 1;
+    INCENTIVES.save(deps.storage, incentive_id, &incentive)?;
+    STAKES.save(
+        deps.storage,
+        (deposit_id, incentive_id),
+        &Stake {
+            seconds_per_liquidity_inside_initial_x128: seconds_per_liquidity_inside_x128,
+            liquidity: deposit.liquidity,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "stake_token")
+        .add_attribute("incentive_id", incentive_id.to_string())
+        .add_attribute("deposit_id", deposit_id.to_string())
+        .add_attribute("liquidity", deposit.liquidity))
+}
+
+fn unstake_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    incentive_id: u64,
+    deposit_id: u64,
+) -> Result<Response, ContractError> {
+    let mut deposit = load_deposit(deps.as_ref(), deposit_id)?;
+    let mut incentive = load_incentive(deps.as_ref(), incentive_id)?;
+
+    // anyone can call unstake_token if the block time is after the end time of the incentive
+    if env.block.time.seconds() < incentive.end_time && info.sender != deposit.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    let stake = STAKES
+        .may_load(deps.storage, (deposit_id, incentive_id))?
+        .ok_or(ContractError::StakeNotFound {})?;
+
+    let (reward, seconds_inside_x128) =
+        reward_info(deps.as_ref(), &env, &incentive, &deposit, &stake)?;
+
+    deposit.number_of_stakes -= 1;
+    deposits().save(deps.storage, deposit_id, &deposit)?;
+    incentive.number_of_stakes -= 1;
+    incentive.total_seconds_claimed_x128 = incentive
+        .total_seconds_claimed_x128
+        .wrapping_add(seconds_inside_x128);
+    // reward is never greater than total reward unclaimed
+    incentive.total_reward_unclaimed -= reward;
+    INCENTIVES.save(deps.storage, incentive_id, &incentive)?;
+    STAKES.remove(deps.storage, (deposit_id, incentive_id));
+
+    let token = incentive.reward_token.to_string();
+    REWARDS.update(
+        deps.storage,
+        (&deposit.owner, &token),
+        |rewards| -> StdResult<_> { Ok(rewards.unwrap_or_default().checked_add(reward)?) },
+    )?;
+
+    Ok(Response::new().add_event(
+        Event::new("unstake_token")
+            .add_attribute("incentive_id", incentive_id.to_string())
+            .add_attribute("deposit_id", deposit_id.to_string())
+            .add_attribute("owner", deposit.owner)
+            .add_attribute("reward_token", token)
+            .add_attribute("reward", reward),
+    ))
+}
+
+fn claim_reward(
+    deps: DepsMut,
+    info: MessageInfo,
+    reward_token: AssetInfo,
+    recipient: String,
+    amount_requested: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let token = reward_token.to_string();
+    let available = REWARDS
+        .may_load(deps.storage, (&info.sender, &token))?
+        .unwrap_or_default();
+    let reward = amount_requested.map_or(available, |amount| amount.min(available));
+
+    let mut response = Response::new()
+        .add_attribute("action", "claim_reward")
+        .add_attribute("reward_token", token.clone())
+        .add_attribute("recipient", recipient.clone())
+        .add_attribute("reward", reward);
+    if !reward.is_zero() {
+        REWARDS.save(deps.storage, (&info.sender, &token), &(available - reward))?;
+        response = response.add_message(safe_transfer(&reward_token, &recipient, reward)?);
+    }
+    Ok(response)
+}
+
+fn load_incentive(deps: Deps, incentive_id: u64) -> Result<Incentive, ContractError> {
+    INCENTIVES
+        .may_load(deps.storage, incentive_id)?
+        .ok_or(ContractError::IncentiveNotFound { incentive_id })
+}
+
+fn load_deposit(deps: Deps, deposit_id: u64) -> Result<Deposit, ContractError> {
+    deposits()
+        .may_load(deps.storage, deposit_id)?
+        .ok_or(ContractError::DepositNotFound { deposit_id })
+}
+
+/// Loads a deposit, failing unless it belongs to the sender
+fn load_own_deposit(
+    deps: Deps,
+    info: &MessageInfo,
+    deposit_id: u64,
+) -> Result<Deposit, ContractError> {
+    let deposit = load_deposit(deps, deposit_id)?;
+    if deposit.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(deposit)
+}
+
+/// The staker's shared position in the deposit's pool and range
+fn query_position(deps: Deps, env: &Env, deposit: &Deposit) -> StdResult<PositionInfo> {
+    deps.querier.query_wasm_smart(
+        &deposit.pool,
+        &PoolQueryMsg::Position {
+            owner: env.contract.address.to_string(),
+            tick_lower: deposit.tick_lower,
+            tick_upper: deposit.tick_upper,
+        },
+    )
+}
+
+/// Computes the reward a stake has earned so far, and the seconds inside its range it is paid for
+fn reward_info(
+    deps: Deps,
+    env: &Env,
+    incentive: &Incentive,
+    deposit: &Deposit,
+    stake: &Stake,
+) -> StdResult<(Uint128, Uint256)> {
+    let (_, seconds_per_liquidity_inside_x128, _) = XionPoolContract(deposit.pool.clone())
+        .snapshot_cumulatives_inside(&deps.querier, deposit.tick_lower, deposit.tick_upper)?;
+    compute_reward_amount(
+        incentive,
+        stake,
+        seconds_per_liquidity_inside_x128,
+        env.block.time.seconds(),
+    )
+}
+
+/// Computes the amount of rewards owed given parameters of the incentive and stake
+/// Rewards are the incentive's unclaimed rewards times the share of the unclaimed seconds the stake spent in range,
+/// where unclaimed seconds keep growing after the end time so that late unstakers are not over-rewarded.
+/// Returns the reward and the seconds inside, weighted by liquidity, as a Q128 value
+pub fn compute_reward_amount(
+    incentive: &Incentive,
+    stake: &Stake,
+    seconds_per_liquidity_inside_x128: Uint256,
+    current_time: u64,
+) -> StdResult<(Uint128, Uint256)> {
+    // this should never be called before the start time
+    debug_assert!(current_time >= incentive.start_time);
+
+    // this operation is safe, as the difference cannot be greater than 1/stake.liquidity
+    let seconds_inside_x128 = seconds_per_liquidity_inside_x128
+        .wrapping_sub(stake.seconds_per_liquidity_inside_initial_x128)
+        .wrapping_mul(Uint256::from(stake.liquidity));
+
+    let total_seconds_unclaimed_x128 =
+        (Uint256::from(incentive.end_time.max(current_time) - incentive.start_time) << 128)
+            .wrapping_sub(incentive.total_seconds_claimed_x128);
+
+    let reward = FullMath::checked_mul_div(
+        Uint256::from(incentive.total_reward_unclaimed),
+        seconds_inside_x128,
+        total_seconds_unclaimed_x128,
+    )?;
+    let reward = Uint128::try_from(reward)
+        .unwrap_or(incentive.total_reward_unclaimed)
+        .min(incentive.total_reward_unclaimed);
+    Ok((reward, seconds_inside_x128))
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        factory: config.factory,
+        max_incentive_start_lead_time: config.max_incentive_start_lead_time,
+        max_incentive_duration: config.max_incentive_duration,
+    })
+}
+
+fn query_deposits_by_owner(
+    deps: Deps,
+    owner: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DepositsResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let deposits = deposits()
+        .idx
+        .owner
+        .prefix(owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(deposit_id, deposit)| DepositResponse {
+                deposit_id,
+                deposit,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+    Ok(DepositsResponse { deposits })
+}
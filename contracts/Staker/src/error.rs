@@ -0,0 +1,88 @@
+use cosmwasm_std::{ConversionOverflowError, OverflowError, StdError};
+use cw_utils::PaymentError;
+use liquidity_pool::helpers::MintPaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("{pool} is not a pool of the factory")]
+    InvalidPool { pool: String },
+
+    #[error("Reward must be greater than zero")]
+    ZeroReward {},
+
+    #[error("Start time must be now or in the future")]
+    StartTimeInPast {},
+
+    #[error("Start time too far into future")]
+    StartTimeTooFarInFuture {},
+
+    #[error("Start time must be before end time")]
+    StartTimeNotBeforeEndTime {},
+
+    #[error("Incentive duration is too long")]
+    IncentiveTooLong {},
+
+    #[error("Incentive {incentive_id} not found")]
+    IncentiveNotFound { incentive_id: u64 },
+
+    #[error("Incentive has not started")]
+    IncentiveNotStarted {},
+
+    #[error("Incentive has ended")]
+    IncentiveEnded {},
+
+    #[error("Incentive has not ended")]
+    IncentiveNotEnded {},
+
+    #[error("Incentive has no rewards left to refund")]
+    NoRefund {},
+
+    #[error("Incentive still has stakes")]
+    StakesRemaining {},
+
+    #[error("Deposit {deposit_id} not found")]
+    DepositNotFound { deposit_id: u64 },
+
+    #[error("Deposit is staked in {stakes} incentives")]
+    DepositStaked { stakes: u32 },
+
+    #[error("Deposit is for a different pool than the incentive")]
+    PoolMismatch {},
+
+    #[error("Liquidity must be greater than zero")]
+    ZeroLiquidity {},
+
+    #[error("Deposit is already staked in the incentive")]
+    AlreadyStaked {},
+
+    #[error("Deposit is not staked in the incentive")]
+    StakeNotFound {},
+
+    #[error("{0}")]
+    MintPayment(#[from] MintPaymentError),
+
+    #[error("No deposit is waiting for a mint callback from the sender")]
+    UnexpectedCallback {},
+
+    #[error("Burn of the withdrawn liquidity reported no amounts")]
+    MissingBurnAmounts {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+}
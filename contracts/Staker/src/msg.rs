@@ -0,0 +1,129 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Uint128, Uint256};
+use cw20::Cw20ReceiveMsg;
+/// The token an incentive pays its rewards in
+pub use libraries::transfer_helper::AssetInfo;
+
+use crate::state::{Deposit, Incentive, Stake};
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The factory whose pools may be incentivized
+    pub factory: String,
+    /// The longest time, in seconds, an incentive may be created before it starts
+    pub max_incentive_start_lead_time: u64,
+    /// The longest an incentive may run for, in seconds
+    pub max_incentive_duration: u64,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Creates a reward program for `pool`, funded with the single native coin attached
+    /// CW20 rewards are funded through `Receive`
+    CreateIncentive {
+        pool: String,
+        start_time: u64,
+        end_time: u64,
+        /// Receives the rewards left unclaimed once the incentive ends
+        refundee: String,
+    },
+    /// Refunds the rewards left unclaimed to the refundee; only once the incentive has ended and every stake in it
+    /// has been unstaked
+    EndIncentive { incentive_id: u64 },
+    /// Mints `liquidity` over [tick_lower, tick_upper) in `pool` into a new deposit owned by the sender
+    /// The attached funds pay for the liquidity and whatever is left is refunded
+    Deposit {
+        pool: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: Uint128,
+    },
+    /// Pays a pool for the liquidity minted by `Deposit`; only callable by that pool
+    XionMintCallback {
+        amount0_owed: Uint256,
+        amount1_owed: Uint256,
+        data: Binary,
+    },
+    /// Burns an unstaked deposit's liquidity and sends the tokens, plus the swap fees it earned, to `recipient`
+    Withdraw { deposit_id: u64, recipient: String },
+    /// Stakes a deposit in an incentive for the same pool
+    StakeToken { incentive_id: u64, deposit_id: u64 },
+    /// Unstakes a deposit from an incentive, crediting the rewards it earned to the deposit owner
+    /// Anyone may unstake once the incentive has ended
+    UnstakeToken { incentive_id: u64, deposit_id: u64 },
+    /// Sends up to `amount_requested` of the sender's credited `reward_token` rewards to `recipient`, or all of
+    /// them if no amount is given
+    ClaimReward {
+        reward_token: AssetInfo,
+        recipient: String,
+        amount_requested: Option<Uint128>,
+    },
+    /// Entry point for CW20 transfers, carrying a `ReceiveMsg`
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Messages sent through a CW20 `Send`
+#[cw_serde]
+pub enum ReceiveMsg {
+    /// Creates a reward program for `pool` paying out the received tokens
+    CreateIncentive {
+        pool: String,
+        start_time: u64,
+        end_time: u64,
+        refundee: String,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(Incentive)]
+    Incentive { incentive_id: u64 },
+    #[returns(Deposit)]
+    Deposit { deposit_id: u64 },
+    /// Deposits owned by `owner`, by id
+    #[returns(DepositsResponse)]
+    DepositsByOwner {
+        owner: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(Stake)]
+    Stake { deposit_id: u64, incentive_id: u64 },
+    /// The rewards credited to `owner` in `reward_token` and not yet claimed
+    #[returns(Uint128)]
+    Rewards {
+        owner: String,
+        reward_token: AssetInfo,
+    },
+    /// The rewards a stake would be credited if it were unstaked now
+    #[returns(RewardInfoResponse)]
+    RewardInfo { incentive_id: u64, deposit_id: u64 },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub factory: Addr,
+    pub max_incentive_start_lead_time: u64,
+    pub max_incentive_duration: u64,
+}
+
+#[cw_serde]
+pub struct DepositResponse {
+    pub deposit_id: u64,
+    pub deposit: Deposit,
+}
+
+#[cw_serde]
+pub struct DepositsResponse {
+    pub deposits: Vec<DepositResponse>,
+}
+
+#[cw_serde]
+pub struct RewardInfoResponse {
+    pub reward: Uint128,
+    /// The seconds the stake's liquidity spent in range, times its liquidity, as a Q128 value
+    pub seconds_inside_x128: Uint256,
+}
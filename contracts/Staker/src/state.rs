@@ -0,0 +1,107 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, StdResult, Storage, Uint128, Uint256};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+
+use crate::msg::AssetInfo;
+
+#[cw_serde]
+pub struct Config {
+    pub factory: Addr,
+    pub max_incentive_start_lead_time: u64,
+    pub max_incentive_duration: u64,
+}
+
+/// A reward program for the liquidity providers of a pool
+#[cw_serde]
+pub struct Incentive {
+    pub reward_token: AssetInfo,
+    pub pool: Addr,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub refundee: Addr,
+    /// Rewards not yet credited to a staker
+    pub total_reward_unclaimed: Uint128,
+    /// Seconds in range, weighted by liquidity, already paid for by credited rewards, as a Q128 value
+    pub total_seconds_claimed_x128: Uint256,
+    pub number_of_stakes: u64,
+}
+
+/// Liquidity the staker holds in a pool on behalf of its owner
+/// The staker's pool position for a range is shared by every deposit over that range, so each deposit tracks the
+/// fee growth it has been paid up to itself
+#[cw_serde]
+pub struct Deposit {
+    pub owner: Addr,
+    pub pool: Addr,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: Uint128,
+    pub fee_growth_inside0_last_x128: Uint256,
+    pub fee_growth_inside1_last_x128: Uint256,
+    pub number_of_stakes: u32,
+}
+
+/// A deposit staked in an incentive
+#[cw_serde]
+pub struct Stake {
+    /// The range's seconds per liquidity when the deposit was staked
+    pub seconds_per_liquidity_inside_initial_x128: Uint256,
+    pub liquidity: Uint128,
+}
+
+/// A deposit whose liquidity is being minted, completed in the pool's mint callback
+#[cw_serde]
+pub struct PendingDeposit {
+    pub id: u64,
+    pub deposit: Deposit,
+    /// The funds the owner attached to pay for the liquidity
+    pub funds: Vec<Coin>,
+}
+
+/// A withdrawal whose liquidity is being burned, completed in the burn reply
+#[cw_serde]
+pub struct PendingWithdrawal {
+    pub deposit: Deposit,
+    pub recipient: Addr,
+}
+
+pub struct DepositIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, Deposit, u64>,
+}
+
+impl<'a> IndexList<Deposit> for DepositIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Deposit>> + '_> {
+        let v: Vec<&dyn Index<Deposit>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Deposits by id; withdrawn deposits are removed
+pub fn deposits<'a>() -> IndexedMap<'a, u64, Deposit, DepositIndexes<'a>> {
+    let indexes = DepositIndexes {
+        owner: MultiIndex::new(
+            |_, deposit| deposit.owner.clone(),
+            "deposits",
+            "deposits__owner",
+        ),
+    };
+    IndexedMap::new("deposits", indexes)
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const INCENTIVE_COUNT: Item<u64> = Item::new("incentive_count");
+pub const DEPOSIT_COUNT: Item<u64> = Item::new("deposit_count");
+pub const INCENTIVES: Map<u64, Incentive> = Map::new("incentives");
+/// Stakes keyed by (deposit id, incentive id)
+pub const STAKES: Map<(u64, u64), Stake> = Map::new("stakes");
+/// Credited rewards keyed by (owner, reward token)
+pub const REWARDS: Map<(&Addr, &str), Uint128> = Map::new("rewards");
+pub const PENDING_DEPOSIT: Item<PendingDeposit> = Item::new("pending_deposit");
+pub const PENDING_WITHDRAWAL: Item<PendingWithdrawal> = Item::new("pending_withdrawal");
+
+/// Returns the next id of a sequence, starting at 1
+pub fn next_id(storage: &mut dyn Storage, seq: &Item<u64>) -> StdResult<u64> {
+    let id = seq.may_load(storage)?.unwrap_or_default() + 1;
+    seq.save(storage, &id)?;
+    Ok(id)
+}
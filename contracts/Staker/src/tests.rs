@@ -0,0 +1,652 @@
+use cosmwasm_std::{coin, coins, Addr, Empty, Int256, Uint128, Uint256};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use factory::testing::{attribute, deploy_factory, deploy_pool, OWNER};
+use libraries::fixed_point_96::FixedPoint96;
+use libraries::position::PositionInfo;
+use libraries::tick_math::MIN_SQRT_RATIO;
+use liquidity_pool::helpers::MintPaymentError;
+
+use crate::error::ContractError;
+use crate::msg::{
+    AssetInfo, DepositsResponse, ExecuteMsg, InstantiateMsg, QueryMsg, RewardInfoResponse,
+};
+use crate::state::{Deposit, Incentive};
+
+const TOKEN0: &str = "uatom";
+const TOKEN1: &str = "uxion";
+const REWARD: &str = "ureward";
+const FEE: u32 = 3000;
+
+fn staker_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply(crate::contract::reply),
+    )
+}
+
+struct Suite {
+    app: App,
+    pool: Addr,
+    staker: Addr,
+}
+
+impl Suite {
+    /// Deploys a factory with an initialized uatom/uxion pool, and a staker for the factory's pools
+    fn new() -> Self {
+        let mut app = App::new(|router, _, storage| {
+            for user in ["alice", "bob", "trader"] {
+                router
+                    .bank
+                    .init_balance(
+                        storage,
+                        &Addr::unchecked(user),
+                        vec![coin(1_000_000, TOKEN0), coin(1_000_000, TOKEN1)],
+                    )
+                    .unwrap();
+            }
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("creator"), coins(10_000, REWARD))
+                .unwrap();
+        });
+        let factory = deploy_factory(&mut app);
+        let pool = deploy_pool(&mut app, &factory, TOKEN0, TOKEN1, FEE, FixedPoint96::Q96);
+
+        let staker_code_id = app.store_code(staker_contract());
+        let staker = app
+            .instantiate_contract(
+                staker_code_id,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg {
+                    factory: factory.to_string(),
+                    max_incentive_start_lead_time: 1_000,
+                    max_incentive_duration: 10_000,
+                },
+                &[],
+                "staker",
+                None,
+            )
+            .unwrap();
+
+        Suite { app, pool, staker }
+    }
+
+    fn now(&self) -> u64 {
+        self.app.block_info().time.seconds()
+    }
+
+    fn advance(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += 1;
+        });
+    }
+
+    fn execute(&mut self, sender: &str, msg: &ExecuteMsg) -> anyhow::Result<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.staker.clone(), msg, &[])
+    }
+
+    fn create_incentive(&mut self, start_time: u64, end_time: u64, reward: u128) -> u64 {
+        let res = self
+            .app
+            .execute_contract(
+                Addr::unchecked("creator"),
+                self.staker.clone(),
+                &ExecuteMsg::CreateIncentive {
+                    pool: self.pool.to_string(),
+                    start_time,
+                    end_time,
+                    refundee: "creator".to_string(),
+                },
+                &coins(reward, REWARD),
+            )
+            .unwrap();
+        attribute(&res, "incentive_id").parse().unwrap()
+    }
+
+    fn deposit(&mut self, owner: &str) -> u64 {
+        let res = self
+            .app
+            .execute_contract(
+                Addr::unchecked(owner),
+                self.staker.clone(),
+                &ExecuteMsg::Deposit {
+                    pool: self.pool.to_string(),
+                    tick_lower: -600,
+                    tick_upper: 600,
+                    liquidity: Uint128::new(1_000_000),
+                },
+                &[coin(100_000, TOKEN0), coin(100_000, TOKEN1)],
+            )
+            .unwrap();
+        attribute(&res, "deposit_id").parse().unwrap()
+    }
+
+    fn stake(
+        &mut self,
+        sender: &str,
+        incentive_id: u64,
+        deposit_id: u64,
+    ) -> anyhow::Result<AppResponse> {
+        self.execute(
+            sender,
+            &ExecuteMsg::StakeToken {
+                incentive_id,
+                deposit_id,
+            },
+        )
+    }
+
+    fn unstake(
+        &mut self,
+        sender: &str,
+        incentive_id: u64,
+        deposit_id: u64,
+    ) -> anyhow::Result<AppResponse> {
+        self.execute(
+            sender,
+            &ExecuteMsg::UnstakeToken {
+                incentive_id,
+                deposit_id,
+            },
+        )
+    }
+
+    fn rewards(&self, owner: &str) -> u128 {
+        let rewards: Uint128 = self.query(&QueryMsg::Rewards {
+            owner: owner.to_string(),
+            reward_token: AssetInfo::native(REWARD),
+        });
+        rewards.u128()
+    }
+
+    fn balance(&self, address: &str, denom: &str) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, denom)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    fn query<T: serde::de::DeserializeOwned>(&self, msg: &QueryMsg) -> T {
+        self.app.wrap().query_wasm_smart(&self.staker, msg).unwrap()
+    }
+}
+
+fn contract_error(err: &anyhow::Error) -> &ContractError {
+    err.root_cause().downcast_ref::<ContractError>().unwrap()
+}
+
+#[test]
+fn create_incentive_validates_times_and_pool() {
+    let mut suite = Suite::new();
+    let now = suite.now();
+    let create = |suite: &mut Suite, pool: String, start_time, end_time| {
+        suite.app.execute_contract(
+            Addr::unchecked("creator"),
+            suite.staker.clone(),
+            &ExecuteMsg::CreateIncentive {
+                pool,
+                start_time,
+                end_time,
+                refundee: "creator".to_string(),
+            },
+            &coins(100, REWARD),
+        )
+    };
+    let pool = suite.pool.to_string();
+
+    let err = create(&mut suite, pool.clone(), now - 1, now + 100).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::StartTimeInPast {});
+    let err = create(&mut suite, pool.clone(), now + 1_001, now + 2_000).unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::StartTimeTooFarInFuture {}
+    );
+    let err = create(&mut suite, pool.clone(), now + 100, now + 100).unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::StartTimeNotBeforeEndTime {}
+    );
+    let err = create(&mut suite, pool.clone(), now, now + 10_001).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::IncentiveTooLong {});
+    let staker = suite.staker.to_string();
+    let err = create(&mut suite, staker.clone(), now, now + 100).unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::InvalidPool { pool: staker }
+    );
+
+    let incentive_id = suite.create_incentive(now, now + 100, 500);
+    let incentive: Incentive = suite.query(&QueryMsg::Incentive { incentive_id });
+    assert_eq!(incentive.reward_token, AssetInfo::native(REWARD));
+    assert_eq!(incentive.total_reward_unclaimed.u128(), 500);
+    assert_eq!(suite.balance("creator", REWARD), 9_500);
+}
+
+#[test]
+fn deposit_reports_the_funds_attached_when_short() {
+    let mut suite = Suite::new();
+    let deposit = ExecuteMsg::Deposit {
+        pool: suite.pool.to_string(),
+        tick_lower: -600,
+        tick_upper: 600,
+        liquidity: Uint128::new(1_000_000),
+    };
+
+    for (funds, short, paid) in [
+        (
+            vec![coin(1_000, TOKEN0), coin(100_000, TOKEN1)],
+            TOKEN0,
+            1_000,
+        ),
+        (vec![coin(100_000, TOKEN0)], TOKEN1, 0),
+    ] {
+        let err = suite
+            .app
+            .execute_contract(
+                Addr::unchecked("alice"),
+                suite.staker.clone(),
+                &deposit,
+                &funds,
+            )
+            .unwrap_err();
+        match err.root_cause().downcast_ref::<MintPaymentError>().unwrap() {
+            MintPaymentError::InsufficientFunds {
+                denom,
+                owed,
+                paid: attached,
+            } => {
+                assert_eq!(denom, short);
+                assert!(owed.u128() > paid);
+                assert_eq!(attached.u128(), paid);
+            }
+            err => panic!("unexpected error {err}"),
+        }
+    }
+}
+
+#[test]
+fn deposit_pays_the_pool_and_refunds_the_rest() {
+    let mut suite = Suite::new();
+    let deposit_id = suite.deposit("alice");
+
+    let position: PositionInfo = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.pool,
+            &liquidity_pool::msg::QueryMsg::Position {
+                owner: suite.staker.to_string(),
+                tick_lower: -600,
+                tick_upper: 600,
+            },
+        )
+        .unwrap();
+    assert_eq!(position.liquidity.u128(), 1_000_000);
+    let paid0 = 1_000_000 - suite.balance("alice", TOKEN0);
+    let paid1 = 1_000_000 - suite.balance("alice", TOKEN1);
+    assert!(paid0 > 0 && paid0 < 100_000);
+    assert_eq!(suite.balance(suite.pool.as_str(), TOKEN0), paid0);
+    assert_eq!(suite.balance(suite.pool.as_str(), TOKEN1), paid1);
+    assert_eq!(suite.balance(suite.staker.as_str(), TOKEN0), 0);
+
+    let deposits: DepositsResponse = suite.query(&QueryMsg::DepositsByOwner {
+        owner: "alice".to_string(),
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(deposits.deposits.len(), 1);
+    assert_eq!(deposits.deposits[0].deposit_id, deposit_id);
+    assert_eq!(deposits.deposits[0].deposit.liquidity.u128(), 1_000_000);
+
+    // only the deposit owner can withdraw, and gets back what was paid less rounding
+    let withdraw = ExecuteMsg::Withdraw {
+        deposit_id,
+        recipient: "alice".to_string(),
+    };
+    let err = suite.execute("bob", &withdraw).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::Unauthorized {});
+    suite.execute("alice", &withdraw).unwrap();
+    assert!(suite.balance("alice", TOKEN0) >= 999_999);
+    assert!(suite.balance("alice", TOKEN1) >= 999_999);
+    let err = suite.execute("alice", &withdraw).unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::DepositNotFound { deposit_id }
+    );
+}
+
+#[test]
+fn rewards_are_shared_by_seconds_in_range() {
+    let mut suite = Suite::new();
+    let start = suite.now() + 100;
+    let incentive_id = suite.create_incentive(start, start + 1_000, 1_000);
+    let alice = suite.deposit("alice");
+
+    let err = suite
+        .execute(
+            "alice",
+            &ExecuteMsg::StakeToken {
+                incentive_id,
+                deposit_id: alice,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::IncentiveNotStarted {});
+
+    // alice is staked for the whole incentive, bob only joins the range for the second half
+    suite.advance(100);
+    suite
+        .execute(
+            "alice",
+            &ExecuteMsg::StakeToken {
+                incentive_id,
+                deposit_id: alice,
+            },
+        )
+        .unwrap();
+    let err = suite
+        .execute(
+            "bob",
+            &ExecuteMsg::StakeToken {
+                incentive_id,
+                deposit_id: alice,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::Unauthorized {});
+    suite.advance(500);
+    let bob = suite.deposit("bob");
+    suite
+        .execute(
+            "bob",
+            &ExecuteMsg::StakeToken {
+                incentive_id,
+                deposit_id: bob,
+            },
+        )
+        .unwrap();
+
+    // a swap earns both deposits fees without moving the price out of range
+    suite
+        .app
+        .execute_contract(
+            Addr::unchecked("trader"),
+            suite.pool.clone(),
+            &liquidity_pool::msg::ExecuteMsg::Swap {
+                recipient: "trader".to_string(),
+                zero_for_one: true,
+                amount_specified: Int256::from(10_000i128),
+                sqrt_price_limit_x96: MIN_SQRT_RATIO + Uint256::one(),
+                data: Default::default(),
+            },
+            &coins(10_000, TOKEN0),
+        )
+        .unwrap();
+
+    let err = suite
+        .execute(
+            "bob",
+            &ExecuteMsg::Withdraw {
+                deposit_id: bob,
+                recipient: "bob".to_string(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::DepositStaked { stakes: 1 }
+    );
+
+    suite.advance(500);
+    let info: RewardInfoResponse = suite.query(&QueryMsg::RewardInfo {
+        incentive_id,
+        deposit_id: alice,
+    });
+    assert_eq!(info.reward.u128(), 749);
+
+    // once the incentive is over anyone may unstake, crediting the owner
+    suite
+        .execute(
+            "alice",
+            &ExecuteMsg::UnstakeToken {
+                incentive_id,
+                deposit_id: alice,
+            },
+        )
+        .unwrap();
+    suite
+        .execute(
+            "anyone",
+            &ExecuteMsg::UnstakeToken {
+                incentive_id,
+                deposit_id: bob,
+            },
+        )
+        .unwrap();
+    let rewards = |suite: &Suite, owner: &str| -> Uint128 {
+        suite.query(&QueryMsg::Rewards {
+            owner: owner.to_string(),
+            reward_token: AssetInfo::native(REWARD),
+        })
+    };
+    assert_eq!(rewards(&suite, "alice").u128(), 749);
+    assert_eq!(rewards(&suite, "bob").u128(), 250);
+
+    suite
+        .execute(
+            "bob",
+            &ExecuteMsg::ClaimReward {
+                reward_token: AssetInfo::native(REWARD),
+                recipient: "bob".to_string(),
+                amount_requested: Some(Uint128::new(100)),
+            },
+        )
+        .unwrap();
+    suite
+        .execute(
+            "bob",
+            &ExecuteMsg::ClaimReward {
+                reward_token: AssetInfo::native(REWARD),
+                recipient: "bob".to_string(),
+                amount_requested: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(suite.balance("bob", REWARD), 250);
+    assert!(rewards(&suite, "bob").is_zero());
+
+    // the rounding dust goes back to the creator
+    suite
+        .execute("anyone", &ExecuteMsg::EndIncentive { incentive_id })
+        .unwrap();
+    assert_eq!(suite.balance("creator", REWARD), 9_001);
+    let err = suite
+        .execute("anyone", &ExecuteMsg::EndIncentive { incentive_id })
+        .unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::NoRefund {});
+
+    // withdrawals split the shared position's fees by deposit
+    let res = suite
+        .execute(
+            "alice",
+            &ExecuteMsg::Withdraw {
+                deposit_id: alice,
+                recipient: "alice".to_string(),
+            },
+        )
+        .unwrap();
+    let alice_fees: u128 = attribute(&res, "fees0").parse().unwrap();
+    let res = suite
+        .execute(
+            "bob",
+            &ExecuteMsg::Withdraw {
+                deposit_id: bob,
+                recipient: "bob".to_string(),
+            },
+        )
+        .unwrap();
+    let bob_fees: u128 = attribute(&res, "fees0").parse().unwrap();
+    assert!(alice_fees > 0);
+    assert!(alice_fees.abs_diff(bob_fees) <= 1);
+    // all that stays behind is the rounding in the pool's favour
+    assert!(suite.balance(suite.pool.as_str(), TOKEN0) <= 4);
+
+    let deposit: Option<Deposit> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.staker, &QueryMsg::Deposit { deposit_id: alice })
+        .ok();
+    assert!(deposit.is_none());
+}
+
+#[test]
+fn end_incentive_refunds_what_was_not_credited() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    let incentive_id = suite.create_incentive(start, start + 1_000, 1_000);
+    let deposit_id = suite.deposit("alice");
+    suite.stake("alice", incentive_id, deposit_id).unwrap();
+
+    let end = ExecuteMsg::EndIncentive { incentive_id };
+    let err = suite.execute("anyone", &end).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::IncentiveNotEnded {});
+
+    // the refund waits for every stake to be unstaked
+    suite.advance(1_000);
+    let err = suite.execute("anyone", &end).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::StakesRemaining {});
+
+    // alice was in range for the whole incentive, so she is credited all of it but the rounding
+    suite.unstake("alice", incentive_id, deposit_id).unwrap();
+    let credited = suite.rewards("alice");
+    assert_eq!(credited, 999);
+    let res = suite.execute("anyone", &end).unwrap();
+    assert_eq!(attribute(&res, "refund"), "1");
+    assert_eq!(suite.balance("creator", REWARD), 9_001);
+    let incentive: Incentive = suite.query(&QueryMsg::Incentive { incentive_id });
+    assert!(incentive.total_reward_unclaimed.is_zero());
+
+    let err = suite.execute("anyone", &end).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::NoRefund {});
+}
+
+#[test]
+fn end_incentive_without_stakes_refunds_everything() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    let incentive_id = suite.create_incentive(start, start + 100, 500);
+
+    suite.advance(100);
+    suite
+        .execute("anyone", &ExecuteMsg::EndIncentive { incentive_id })
+        .unwrap();
+    assert_eq!(suite.balance("creator", REWARD), 10_000);
+    assert_eq!(suite.balance(suite.staker.as_str(), REWARD), 0);
+}
+
+#[test]
+fn staked_deposits_cannot_be_withdrawn() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    let first = suite.create_incentive(start, start + 1_000, 1_000);
+    let second = suite.create_incentive(start, start + 1_000, 1_000);
+    let deposit_id = suite.deposit("alice");
+    suite.stake("alice", first, deposit_id).unwrap();
+    suite.stake("alice", second, deposit_id).unwrap();
+    let err = suite.stake("alice", first, deposit_id).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::AlreadyStaked {});
+
+    let withdraw = ExecuteMsg::Withdraw {
+        deposit_id,
+        recipient: "alice".to_string(),
+    };
+    let err = suite.execute("alice", &withdraw).unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::DepositStaked { stakes: 2 }
+    );
+    suite.unstake("alice", first, deposit_id).unwrap();
+    let err = suite.execute("alice", &withdraw).unwrap_err();
+    assert_eq!(
+        contract_error(&err),
+        &ContractError::DepositStaked { stakes: 1 }
+    );
+
+    suite.unstake("alice", second, deposit_id).unwrap();
+    suite.execute("alice", &withdraw).unwrap();
+    let deposit: Option<Deposit> = suite
+        .app
+        .wrap()
+        .query_wasm_smart(&suite.staker, &QueryMsg::Deposit { deposit_id })
+        .ok();
+    assert!(deposit.is_none());
+}
+
+#[test]
+fn others_may_only_unstake_once_the_incentive_ends() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    let incentive_id = suite.create_incentive(start, start + 1_000, 1_000);
+    let deposit_id = suite.deposit("alice");
+    suite.stake("alice", incentive_id, deposit_id).unwrap();
+
+    suite.advance(500);
+    let err = suite.unstake("bob", incentive_id, deposit_id).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::Unauthorized {});
+
+    // after the end time anyone can unstake, and the rewards go to the deposit owner
+    suite.advance(500);
+    suite.unstake("bob", incentive_id, deposit_id).unwrap();
+    assert_eq!(suite.rewards("alice"), 999);
+    assert_eq!(suite.rewards("bob"), 0);
+    let deposit: Deposit = suite.query(&QueryMsg::Deposit { deposit_id });
+    assert_eq!(deposit.number_of_stakes, 0);
+
+    let err = suite.unstake("bob", incentive_id, deposit_id).unwrap_err();
+    assert_eq!(contract_error(&err), &ContractError::StakeNotFound {});
+}
+
+#[test]
+fn rewards_can_be_claimed_in_parts() {
+    let mut suite = Suite::new();
+    let start = suite.now();
+    let incentive_id = suite.create_incentive(start, start + 1_000, 1_000);
+    let deposit_id = suite.deposit("alice");
+    suite.stake("alice", incentive_id, deposit_id).unwrap();
+    suite.advance(1_000);
+    suite.unstake("alice", incentive_id, deposit_id).unwrap();
+    assert_eq!(suite.rewards("alice"), 999);
+
+    let claim = |amount_requested: Option<u128>, recipient: &str| ExecuteMsg::ClaimReward {
+        reward_token: AssetInfo::native(REWARD),
+        recipient: recipient.to_string(),
+        amount_requested: amount_requested.map(Uint128::new),
+    };
+
+    // a part goes to another recipient, the rest stays credited
+    let res = suite.execute("alice", &claim(Some(300), "carol")).unwrap();
+    assert_eq!(attribute(&res, "reward"), "300");
+    assert_eq!(suite.balance("carol", REWARD), 300);
+    assert_eq!(suite.rewards("alice"), 699);
+
+    // requests above the credited rewards are capped to them
+    let res = suite
+        .execute("alice", &claim(Some(5_000), "alice"))
+        .unwrap();
+    assert_eq!(attribute(&res, "reward"), "699");
+    assert_eq!(suite.balance("alice", REWARD), 699);
+    assert_eq!(suite.rewards("alice"), 0);
+
+    // nothing left to claim is a no-op
+    let res = suite.execute("alice", &claim(None, "alice")).unwrap();
+    assert_eq!(attribute(&res, "reward"), "0");
+    assert_eq!(suite.balance("alice", REWARD), 699);
+}
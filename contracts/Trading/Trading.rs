@@ -1,211 +0,0 @@
-// SPDX-License-Identifier: BUSL-1.1
-#![cfg_attr(not(feature = "std"), no_std)]
-
-use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage,
-};
-use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::to_binary;
-
-// Define your trading contract struct here
-pub struct TradingContract {
-    // Define state variables here using cw_storage_plus
-    pub trades: Map<Storage, String, Trade>,
-    pub accounts: Map<Storage, String, Account>,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Trade {
-    pub trade_id: String,
-    pub trader: Addr,
-    pub token: String,
-    pub amount: u128,
-    pub price: u128,
-    pub trade_type: TradeType,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Account {
-    pub account_id: String,
-    pub trader: Addr,
-    pub token: String,
-    pub balance: u128,
-}
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub enum TradeType {
-    Buy,
-    Sell,
-}
-
-impl TradingContract {
-    pub fn new(storage: &mut dyn Storage) -> Self {
-        Self {
-            trades: Map::new(storage, "trades"),
-            accounts: Map::new(storage, "accounts"),
-        }
-    }
-
-    pub fn handle(&mut self, _deps: DepsMut, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Implement your contract's business logic here
-        unimplemented!()
-    }
-
-    pub fn init(&mut self, _deps: DepsMut, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Initialize the contract state if necessary
-        Ok(Response::default())
-    }
-
-    pub fn query_trades(&self, _deps: Deps, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Query trades from the trading contract
-        // Return trades as JSON or other format
-        unimplemented!()
-    }
-
-    pub fn query_balances(&self, _deps: Deps, _env: Env, _info: MessageInfo) -> StdResult<Response> {
-        // Query account balances from the trading contract
-        // Return balances as JSON or other format
-        unimplemented!()
-    }
-}
-
-// Example of how to test the contract's functionality
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_init_trading_contract() {
-        let mut deps = mock_dependencies(&[]);
-        let env = mock_env();
-        let info = mock_info("creator", &[]);
-
-        // Initialize the contract
-        let mut trading_contract = TradingContract::new(&mut deps.storage);
-        let res = trading_contract.init(deps.as_mut(), env.clone(), info.clone());
-        assert!(res.is_ok());
-
-        // Verify the contract state after initialization if needed
-        // Example: Check if there are no trades or accounts initially
-        // let state = config(deps.as_ref()).load().expect("load state");
-        // assert_eq!(state.trades.len(), 0);
-        // assert_eq!(state.accounts.len(), 0);
-    }
-
-    #[test]
-    fn test_handle_buy_trade() {
-        let mut deps = mock_dependencies(&[]);
-        let env = mock_env();
-        let info = mock_info("trader1", &[]); // Mock info of trader1
-
-        // Initialize the contract (if not already initialized)
-        let mut trading_contract = TradingContract::new(&mut deps.storage);
-        let _res = trading_contract.init(deps.as_mut(), env.clone(), info.clone());
-
-        // Add a buy trade to the trading contract
-        let trade_id = "trade1".to_string();
-        let trader = "trader1".to_string();
-        let token = "token".to_string();
-        let amount = 100;
-        let price = 10;
-        let trade_type = TradeType::Buy; // Example: Buy trade
-
-        let res = trading_contract.handle(
-            deps.as_mut(),
-            env.clone(),
-            info.clone(),
-            HandleMsg::AddTrade {
-                trade_id: trade_id.clone(),
-                trader: trader.clone(),
-                token: token.clone(),
-                amount: amount.clone(),
-                price: price.clone(),
-                trade_type: trade_type.clone(),
-            }
-        );
-
-        assert!(res.is_ok());
-
-        // Retrieve and verify the trade from the trading contract if needed
-        // Example: Check if the trade was added correctly
-        // let trades = query_trades(deps.as_ref()).expect("load trades");
-        // assert_eq!(trades.len(), 1);
-        // assert_eq!(trades[0].trade_id, trade_id);
-    }
-
-    #[test]
-    fn test_query_balances() {
-        let mut deps = mock_dependencies(&[]);
-        let env = mock_env();
-        let info = mock_info("query", &[]); // Mock info of query account
-
-        // Initialize the contract (if not already initialized)
-        let mut trading_contract = TradingContract::new(&mut deps.storage);
-        let _res = trading_contract.init(deps.as_mut(), env.clone(), info.clone());
-
-        // Query account balances from the trading contract
-        let res = trading_contract.query_balances(
-            deps.as_ref(),
-            env.clone(),
-            info.clone(),
-            QueryMsg::GetBalances {
-                trader: info.sender.clone(),
-            }
-        );
-
-        assert!(res.is_ok());
-
-        // Example: Verify the returned balances if needed
-        // let balances: Vec<Account> = from_binary(&res.unwrap().data).unwrap();
-        // assert_eq!(balances.len(), expected_balances_count);
-    }
-}
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Replace with actual Cosmos SDK endpoint URL
-    let rpc_client = RpcClient::new("http://localhost:26657".to_string());
-
-    // Replace with actual private key or use environment variables for security
-    let private_key = "your_private_key";
-
-    // Initialize a Cosmos signer
-    let signer = Signer::from_secret(private_key);
-
-    // Initialize the TradingContract instance with your parameters
-    let factory = ContractFactory::new(
-        include_bytes!("path/to/TradingContract.wasm").to_vec(),
-        include_str!("path/to/TradingContract.abi").to_string(),
-        signer,
-    );
-
-    // Example parameters for the TradingContract deployment
-    let trading_contract = TradingContract::new(/* initialize your parameters */);
-
-    // Deploy the TradingContract
-    let options = DeployOptions {
-        gas: None, // Replace with actual gas limit if needed
-        amount: None, // Replace with actual value if needed
-        label: None, // Replace with actual label if needed
-    };
-
-    // Deploy the contract and retrieve the transaction receipt
-    let receipt = factory
-        .deploy(
-            &rpc_client,
-            options,
-            trading_contract.factory,
-            (
-                trading_contract.token0,
-                trading_contract.token1,
-                trading_contract.fee,
-                trading_contract.tick_spacing,
-            ),
-        )
-        .await?;
-
-    // Get the deployed contract address from the transaction receipt
-    let address = receipt.contract_address.ok_or("Contract deployment failed")?;
-    println!("TradingContract deployed at address: {}", address);
-
-    Ok(())
-}
@@ -0,0 +1,540 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Swap router
+//! Routes swaps through one or more of the factory's pools. Each pool swap is paid in the pool's swap callback: by
+//! the router out of the coin attached by the swapper for the first pool, and out of the previous pool's output
+//! for the next ones. Exact output swaps run backwards from the last pool, each pool's callback swapping in the
+//! pool before it to pay for it.
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, Int256, MessageInfo, Reply, Response,
+    StdError, StdResult, SubMsg, Uint128, Uint256, WasmMsg,
+};
+use cw_utils::one_coin;
+use factory::msg::{PoolResponse, QueryMsg as FactoryQueryMsg};
+use libraries::safe_cast::to_u256;
+use libraries::tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO};
+use libraries::transfer_helper::{safe_transfer, AssetInfo};
+use liquidity_pool::msg::{
+    ExecuteMsg as PoolExecuteMsg, QueryMsg as PoolQueryMsg, QuoteResponse as PoolQuoteResponse,
+};
+
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, QuoteResponse};
+use crate::path::Path;
+use crate::state::{Config, Hop, SwapState, CONFIG, SWAP_STATE};
+
+pub const SWAP_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = Config {
+        factory: deps.api.addr_validate(&msg.factory)?,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("factory", config.factory))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ExactInputSingle {
+            token_out,
+            fee,
+            recipient,
+            deadline,
+            amount_out_minimum,
+            sqrt_price_limit_x96,
+        } => {
+            let token_in = one_coin(&info)?.denom;
+            let route = Route {
+                path: Path::single(token_in, fee, token_out),
+                recipient,
+                deadline,
+                sqrt_price_limit_x96,
+            };
+            exact_input(deps, env, info, route, amount_out_minimum)
+        }
+        ExecuteMsg::ExactInput {
+            path,
+            recipient,
+            deadline,
+            amount_out_minimum,
+        } => {
+            let route = Route {
+                path,
+                recipient,
+                deadline,
+                sqrt_price_limit_x96: None,
+            };
+            exact_input(deps, env, info, route, amount_out_minimum)
+        }
+        ExecuteMsg::ExactOutputSingle {
+            token_out,
+            fee,
+            recipient,
+            deadline,
+            amount_out,
+            amount_in_maximum,
+            sqrt_price_limit_x96,
+        } => {
+            let token_in = one_coin(&info)?.denom;
+            let route = Route {
+                path: Path::single(token_in, fee, token_out),
+                recipient,
+                deadline,
+                sqrt_price_limit_x96,
+            };
+            exact_output(deps, env, info, route, amount_out, amount_in_maximum)
+        }
+        ExecuteMsg::ExactOutput {
+            path,
+            recipient,
+            deadline,
+            amount_out,
+            amount_in_maximum,
+        } => {
+            let route = Route {
+                path,
+                recipient,
+                deadline,
+                sqrt_price_limit_x96: None,
+            };
+            exact_output(deps, env, info, route, amount_out, amount_in_maximum)
+        }
+        ExecuteMsg::XionSwapCallback {
+            amount0_delta,
+            amount1_delta,
+            ..
+        } => swap_callback(deps, info, amount0_delta, amount1_delta),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        SWAP_REPLY_ID => settle_swap(deps, env),
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::QuoteExactInput { path, amount_in } => to_binary(
+            &quote_exact_input(deps, &path, amount_in)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+        QueryMsg::QuoteExactOutput { path, amount_out } => to_binary(
+            &quote_exact_output(deps, &path, amount_out)
+                .map_err(|err| StdError::generic_err(err.to_string()))?,
+        ),
+    }
+}
+
+/// The parameters shared by every kind of swap
+struct Route {
+    path: Path,
+    recipient: String,
+    deadline: u64,
+    sqrt_price_limit_x96: Option<Uint256>,
+}
+
+fn exact_input(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    route: Route,
+    amount_out_minimum: Uint128,
+) -> Result<Response, ContractError> {
+    let state = start_swap(deps.as_ref(), &env, info, route, true, amount_out_minimum)?;
+    let hop = &state.hops[0];
+    let recipient = hop_recipient(&env, &state);
+    let swap = swap_msg(
+        hop,
+        to_int(state.funds.amount),
+        &recipient,
+        state.sqrt_price_limit_x96,
+    )?;
+    SWAP_STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(swap, SWAP_REPLY_ID))
+        .add_attribute("action", "exact_input")
+        .add_attribute("payer", state.payer)
+        .add_attribute("recipient", state.recipient)
+        .add_attribute("token_in", &hop.token_in)
+        .add_attribute("amount_in", state.funds.amount))
+}
+
+fn exact_output(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    route: Route,
+    amount_out: Uint128,
+    amount_in_maximum: Uint128,
+) -> Result<Response, ContractError> {
+    if amount_out.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    let mut state = start_swap(deps.as_ref(), &env, info, route, false, amount_in_maximum)?;
+    // nothing beyond the attached coin can be paid in
+    state.limit = state.limit.min(state.funds.amount);
+    state.hop = state.hops.len() as u32 - 1;
+    state.hop_amount = amount_out;
+    let hop = &state.hops[state.hop as usize];
+    let swap = swap_msg(
+        hop,
+        -to_int(amount_out),
+        &state.recipient,
+        state.sqrt_price_limit_x96,
+    )?;
+    SWAP_STATE.save(deps.storage, &state)?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(swap, SWAP_REPLY_ID))
+        .add_attribute("action", "exact_output")
+        .add_attribute("payer", state.payer)
+        .add_attribute("recipient", state.recipient)
+        .add_attribute("token_out", &hop.token_out)
+        .add_attribute("amount_out", amount_out))
+}
+
+/// Checks the deadline and the attached coin, and resolves the pools of the path
+fn start_swap(
+    deps: Deps,
+    env: &Env,
+    info: MessageInfo,
+    route: Route,
+    exact_input: bool,
+    limit: Uint128,
+) -> Result<SwapState, ContractError> {
+    if env.block.time.seconds() > route.deadline {
+        return Err(ContractError::TransactionTooOld {});
+    }
+    let funds = one_coin(&info)?;
+    let recipient = deps.api.addr_validate(&route.recipient)?;
+    let hops = resolve_hops(deps, &route.path)?;
+    if funds.denom != hops[0].token_in {
+        return Err(ContractError::WrongInputToken {
+            denom: funds.denom,
+            token_in: hops[0].token_in.clone(),
+        });
+    }
+
+    Ok(SwapState {
+        exact_input,
+        payer: info.sender,
+        recipient,
+        hops,
+        hop: 0,
+        sqrt_price_limit_x96: route.sqrt_price_limit_x96,
+        funds,
+        amount_in: Uint128::zero(),
+        amount_out: Uint128::zero(),
+        hop_amount: Uint128::zero(),
+        limit,
+    })
+}
+
+/// Decodes the path and looks up each of its pools in the factory
+fn resolve_hops(deps: Deps, path: &Path) -> Result<Vec<Hop>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    path.decode()?
+        .into_iter()
+        .map(|hop| {
+            let pool: PoolResponse = deps
+                .querier
+                .query_wasm_smart(
+                    &config.factory,
+                    &FactoryQueryMsg::Pool {
+                        token_a: hop.token_in.clone(),
+                        token_b: hop.token_out.clone(),
+                        fee: hop.fee,
+                    },
+                )
+                .map_err(|_| ContractError::PoolNotFound {
+                    token_a: hop.token_in.clone(),
+                    token_b: hop.token_out.clone(),
+                    fee: hop.fee,
+                })?;
+            Ok(Hop {
+                pool: pool.address,
+                token_in: hop.token_in,
+                token_out: hop.token_out,
+            })
+        })
+        .collect()
+}
+
+/// Pays the pool of the current hop what it is owed
+/// Exact input swaps pay out of the router's balance. Exact output swaps pay the first pool out of the router's
+/// balance and any other pool by swapping for its input in the pool before it.
+fn swap_callback(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount0_delta: Int256,
+    amount1_delta: Int256,
+) -> Result<Response, ContractError> {
+    let Some(mut state) = SWAP_STATE.may_load(deps.storage)? else {
+        return Err(ContractError::UnexpectedCallback {});
+    };
+    let hop = state.hops[state.hop as usize].clone();
+    if info.sender != hop.pool {
+        return Err(ContractError::UnexpectedCallback {});
+    }
+    let (owed, received) = if hop.zero_for_one() {
+        (amount0_delta, -amount1_delta)
+    } else {
+        (amount1_delta, -amount0_delta)
+    };
+    let owed = to_amount(owed)?;
+    let received = to_amount(received)?;
+    let last = state.hop as usize == state.hops.len() - 1;
+
+    let mut response = Response::new()
+        .add_attribute("action", "swap_callback")
+        .add_attribute("pool", &hop.pool)
+        .add_attribute("amount_in", owed)
+        .add_attribute("amount_out", received);
+    if state.exact_input {
+        if state.hop == 0 {
+            state.amount_in = owed;
+        }
+        if last {
+            state.amount_out = received;
+        }
+        state.hop_amount = received;
+        response = response.add_message(pay(&hop, owed)?);
+    } else {
+        // without a price limit the pool must deliver all of the output asked of it
+        if state.sqrt_price_limit_x96.is_none() && received != state.hop_amount {
+            return Err(ContractError::InsufficientOutput {
+                requested: state.hop_amount,
+                received,
+            });
+        }
+        if last {
+            state.amount_out = received;
+        }
+        if state.hop > 0 {
+            state.hop -= 1;
+            state.hop_amount = owed;
+            let swap = swap_msg(
+                &state.hops[state.hop as usize],
+                -to_int(owed),
+                &hop.pool,
+                None,
+            )?;
+            response = response.add_message(swap);
+        } else {
+            if owed > state.limit {
+                return Err(ContractError::TooMuchRequested {
+                    amount_in: owed,
+                    amount_in_maximum: state.limit,
+                });
+            }
+            state.amount_in = owed;
+            response = response.add_message(pay(&hop, owed)?);
+        }
+    }
+    SWAP_STATE.save(deps.storage, &state)?;
+
+    Ok(response)
+}
+
+/// Moves an exact input swap on to its next hop, or finishes the swap once its first pool swap is done
+fn settle_swap(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let mut state = SWAP_STATE.load(deps.storage)?;
+
+    if state.exact_input && (state.hop as usize) < state.hops.len() - 1 {
+        state.hop += 1;
+        let recipient = hop_recipient(&env, &state);
+        let swap = swap_msg(
+            &state.hops[state.hop as usize],
+            to_int(state.hop_amount),
+            &recipient,
+            None,
+        )?;
+        SWAP_STATE.save(deps.storage, &state)?;
+        return Ok(Response::new()
+            .add_submessage(SubMsg::reply_on_success(swap, SWAP_REPLY_ID))
+            .add_attribute("action", "swap_hop")
+            .add_attribute("hop", state.hop.to_string()));
+    }
+
+    SWAP_STATE.remove(deps.storage);
+    if state.exact_input && state.amount_out < state.limit {
+        return Err(ContractError::TooLittleReceived {
+            amount_out: state.amount_out,
+            amount_out_minimum: state.limit,
+        });
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "swap_settle")
+        .add_attribute("amount_in", state.amount_in)
+        .add_attribute("amount_out", state.amount_out);
+    let refund = state.funds.amount - state.amount_in;
+    if !refund.is_zero() {
+        response = response.add_message(safe_transfer(
+            &AssetInfo::native(state.funds.denom),
+            &state.payer,
+            refund,
+        )?);
+    }
+    Ok(response)
+}
+
+/// Exact input hops send their output to the router for the next hop, except the last one
+fn hop_recipient(env: &Env, state: &SwapState) -> Addr {
+    if state.hop as usize == state.hops.len() - 1 {
+        state.recipient.clone()
+    } else {
+        env.contract.address.clone()
+    }
+}
+
+/// A swap in the hop's pool paid in its callback; a positive amount is exact input, a negative one exact output
+fn swap_msg(
+    hop: &Hop,
+    amount_specified: Int256,
+    recipient: &Addr,
+    sqrt_price_limit_x96: Option<Uint256>,
+) -> StdResult<WasmMsg> {
+    let zero_for_one = hop.zero_for_one();
+    let sqrt_price_limit_x96 = sqrt_price_limit_x96.unwrap_or(if zero_for_one {
+        MIN_SQRT_RATIO + Uint256::one()
+    } else {
+        MAX_SQRT_RATIO - Uint256::one()
+    });
+    Ok(WasmMsg::Execute {
+        contract_addr: hop.pool.to_string(),
+        msg: to_binary(&PoolExecuteMsg::Swap {
+            recipient: recipient.to_string(),
+            zero_for_one,
+            amount_specified,
+            sqrt_price_limit_x96,
+            data: to_binary(&hop.token_in)?,
+        })?,
+        funds: vec![],
+    })
+}
+
+fn pay(hop: &Hop, amount: Uint128) -> StdResult<CosmosMsg> {
+    safe_transfer(&AssetInfo::native(&hop.token_in), &hop.pool, amount)
+}
+
+fn to_int(amount: Uint128) -> Int256 {
+    Int256::from(amount.u128())
+}
+
+/// The positive part of a pool delta
+fn to_amount(delta: Int256) -> Result<Uint128, ContractError> {
+    if delta <= Int256::zero() {
+        return Ok(Uint128::zero());
+    }
+    Ok(Uint128::try_from(to_u256(delta))?)
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        factory: config.factory,
+    })
+}
+
+/// Quotes each pool of the path in turn, feeding each pool's output into the next one
+fn quote_exact_input(
+    deps: Deps,
+    path: &Path,
+    amount_in: Uint128,
+) -> Result<QuoteResponse, ContractError> {
+    if amount_in.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    let mut quotes = vec![];
+    let mut amount = amount_in;
+    for hop in resolve_hops(deps, path)? {
+        let quote: PoolQuoteResponse = deps.querier.query_wasm_smart(
+            &hop.pool,
+            &PoolQueryMsg::QuoteExactInput {
+                zero_for_one: hop.zero_for_one(),
+                amount_in: amount,
+                sqrt_price_limit_x96: None,
+            },
+        )?;
+        amount = quote.amount_out;
+        quotes.push(quote);
+    }
+    Ok(quote_response(quotes[0].amount_in, amount, quotes))
+}
+
+/// Quotes each pool of the path from the last one, each pool's input being the output asked of the pool before it
+fn quote_exact_output(
+    deps: Deps,
+    path: &Path,
+    amount_out: Uint128,
+) -> Result<QuoteResponse, ContractError> {
+    if amount_out.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    let mut quotes = vec![];
+    let mut amount = amount_out;
+    for hop in resolve_hops(deps, path)?.iter().rev() {
+        let quote: PoolQuoteResponse = deps.querier.query_wasm_smart(
+            &hop.pool,
+            &PoolQueryMsg::QuoteExactOutput {
+                zero_for_one: hop.zero_for_one(),
+                amount_out: amount,
+                sqrt_price_limit_x96: None,
+            },
+        )?;
+        // the pool ran out of liquidity before delivering the whole output
+        if quote.amount_out < amount {
+            return Err(ContractError::InsufficientOutput {
+                requested: amount,
+                received: quote.amount_out,
+            });
+        }
+        amount = quote.amount_in;
+        quotes.push(quote);
+    }
+    quotes.reverse();
+    Ok(quote_response(amount, amount_out, quotes))
+}
+
+/// Combines the quotes of each pool, in path order
+fn quote_response(
+    amount_in: Uint128,
+    amount_out: Uint128,
+    quotes: Vec<PoolQuoteResponse>,
+) -> QuoteResponse {
+    QuoteResponse {
+        amount_in,
+        amount_out,
+        sqrt_price_x96_after_list: quotes.iter().map(|q| q.sqrt_price_x96_after).collect(),
+        initialized_ticks_crossed_list: quotes
+            .iter()
+            .map(|q| q.initialized_ticks_crossed)
+            .collect(),
+        gas_estimate: quotes.iter().map(|q| q.gas_estimate).sum(),
+    }
+}
@@ -0,0 +1,58 @@
+use cosmwasm_std::{ConversionOverflowError, StdError, Uint128};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Transaction too old")]
+    TransactionTooOld {},
+
+    #[error("Path must alternate tokens and fees, with no pool swapping a token for itself")]
+    InvalidPath {},
+
+    #[error("No pool for {token_a}/{token_b} with fee {fee}")]
+    PoolNotFound {
+        token_a: String,
+        token_b: String,
+        fee: u32,
+    },
+
+    #[error("Attached {denom}, but the path starts with {token_in}")]
+    WrongInputToken { denom: String, token_in: String },
+
+    #[error("Too little received: {amount_out}, minimum {amount_out_minimum}")]
+    TooLittleReceived {
+        amount_out: Uint128,
+        amount_out_minimum: Uint128,
+    },
+
+    #[error("Too much requested: {amount_in}, maximum {amount_in_maximum}")]
+    TooMuchRequested {
+        amount_in: Uint128,
+        amount_in_maximum: Uint128,
+    },
+
+    #[error("Pool returned {received} of the {requested} requested")]
+    InsufficientOutput {
+        requested: Uint128,
+        received: Uint128,
+    },
+
+    #[error("Amount must be greater than zero")]
+    ZeroAmount {},
+
+    #[error("No swap is waiting for a callback from the sender")]
+    UnexpectedCallback {},
+
+    #[error("Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+}
@@ -0,0 +1,93 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Binary, Int256, Uint128, Uint256};
+
+use crate::path::Path;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    /// The factory whose pools swaps are routed through
+    pub factory: String,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Swaps all of the single coin attached for as much as possible of `token_out` in one pool
+    ExactInputSingle {
+        token_out: String,
+        fee: u32,
+        recipient: String,
+        /// The latest block time, in seconds, the swap may execute at
+        deadline: u64,
+        amount_out_minimum: Uint128,
+        /// The price the swap stops at, none to swap the whole input
+        sqrt_price_limit_x96: Option<Uint256>,
+    },
+    /// Swaps all of the single coin attached for as much as possible of the last token of `path`, through each
+    /// pool of the path in turn
+    ExactInput {
+        path: Path,
+        recipient: String,
+        deadline: u64,
+        amount_out_minimum: Uint128,
+    },
+    /// Swaps as little as possible of the single coin attached for `amount_out` of `token_out` in one pool
+    /// Whatever is left of the coin is refunded
+    ExactOutputSingle {
+        token_out: String,
+        fee: u32,
+        recipient: String,
+        deadline: u64,
+        amount_out: Uint128,
+        amount_in_maximum: Uint128,
+        /// The price the swap stops at, none to require the whole output
+        sqrt_price_limit_x96: Option<Uint256>,
+    },
+    /// Swaps as little as possible of the single coin attached for `amount_out` of the last token of `path`
+    /// Whatever is left of the coin is refunded
+    ExactOutput {
+        path: Path,
+        recipient: String,
+        deadline: u64,
+        amount_out: Uint128,
+        amount_in_maximum: Uint128,
+    },
+    /// Pays a pool for a swap of the current route; only callable by the pool being swapped in
+    XionSwapCallback {
+        amount0_delta: Int256,
+        amount1_delta: Int256,
+        data: Binary,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(ConfigResponse)]
+    Config {},
+    /// Simulates swapping exactly `amount_in` of the first token of `path` through each of its pools
+    #[returns(QuoteResponse)]
+    QuoteExactInput { path: Path, amount_in: Uint128 },
+    /// Simulates swapping the first token of `path` for exactly `amount_out` of its last token
+    /// Fails if a pool of the path lacks the liquidity to deliver what is asked of it
+    #[returns(QuoteResponse)]
+    QuoteExactOutput { path: Path, amount_out: Uint128 },
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub factory: Addr,
+}
+
+#[cw_serde]
+pub struct QuoteResponse {
+    /// The amount of the first token of the path the swap would take
+    pub amount_in: Uint128,
+    /// The amount of the last token of the path the swap would deliver
+    pub amount_out: Uint128,
+    /// The price of each pool after the swap, in path order
+    pub sqrt_price_x96_after_list: Vec<Uint256>,
+    /// The number of initialized ticks each pool's swap would cross, in path order
+    pub initialized_ticks_crossed_list: Vec<u32>,
+    /// The sum of the pool quotes' gas heuristics, for comparing routes rather than setting a gas limit
+    pub gas_estimate: u64,
+}
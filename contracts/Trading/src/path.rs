@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Swap paths
+//! A path is the sequence of tokens a swap goes through, separated by the fee of the pool between each pair:
+//! `["uatom", 3000, "uxion", 500, "uosmo"]` swaps uatom for uxion in the 0.3% pool, then uxion for uosmo in the
+//! 0.05% pool. Paths are always written from the input token to the output token, for exact output swaps too.
+
+use cosmwasm_schema::cw_serde;
+
+use crate::error::ContractError;
+
+#[cw_serde]
+#[serde(untagged)]
+pub enum PathElement {
+    Token(String),
+    Fee(u32),
+}
+
+#[cw_serde]
+pub struct Path(pub Vec<PathElement>);
+
+/// One pool of a path, swapping `token_in` for `token_out`
+#[cw_serde]
+pub struct PoolHop {
+    pub token_in: String,
+    pub fee: u32,
+    pub token_out: String,
+}
+
+impl Path {
+    /// Builds the path of a single pool
+    pub fn single(token_in: impl Into<String>, fee: u32, token_out: impl Into<String>) -> Self {
+        Path(vec![
+            PathElement::Token(token_in.into()),
+            PathElement::Fee(fee),
+            PathElement::Token(token_out.into()),
+        ])
+    }
+
+    /// Decodes the pools of the path, in swap order
+    /// Fails unless the path alternates tokens and fees, starting and ending with a token, and has at least one pool
+    pub fn decode(&self) -> Result<Vec<PoolHop>, ContractError> {
+        let Some(PathElement::Token(first)) = self.0.first() else {
+            return Err(ContractError::InvalidPath {});
+        };
+        if self.0.len() < 3 || self.0.len().is_multiple_of(2) {
+            return Err(ContractError::InvalidPath {});
+        }
+
+        let mut token_in = first.clone();
+        self.0[1..]
+            .chunks(2)
+            .map(|pair| match pair {
+                [PathElement::Fee(fee), PathElement::Token(token_out)]
+                    if *token_out != token_in =>
+                {
+                    let hop = PoolHop {
+                        token_in: std::mem::replace(&mut token_in, token_out.clone()),
+                        fee: *fee,
+                        token_out: token_out.clone(),
+                    };
+                    Ok(hop)
+                }
+                _ => Err(ContractError::InvalidPath {}),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,49 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Uint128, Uint256};
+use cw_storage_plus::Item;
+
+#[cw_serde]
+pub struct Config {
+    pub factory: Addr,
+}
+
+/// A pool of a swap's path, resolved through the factory
+#[cw_serde]
+pub struct Hop {
+    pub pool: Addr,
+    pub token_in: String,
+    pub token_out: String,
+}
+
+impl Hop {
+    pub fn zero_for_one(&self) -> bool {
+        self.token_in < self.token_out
+    }
+}
+
+/// A swap in progress, kept from the first pool swap until its final reply
+#[cw_serde]
+pub struct SwapState {
+    pub exact_input: bool,
+    pub payer: Addr,
+    pub recipient: Addr,
+    pub hops: Vec<Hop>,
+    /// The index of the hop whose pool is swapping
+    pub hop: u32,
+    /// The price limit of a single pool swap; multi-hop swaps have none
+    pub sqrt_price_limit_x96: Option<Uint256>,
+    /// The input token attached by the payer
+    pub funds: Coin,
+    /// The input taken from the payer so far
+    pub amount_in: Uint128,
+    /// The output delivered to the recipient
+    pub amount_out: Uint128,
+    /// Exact input: the output of the last hop swapped, paid into the next one. Exact output: the output asked of
+    /// the hop swapping
+    pub hop_amount: Uint128,
+    /// The bound on the amount out for exact input swaps, or on the amount in for exact output swaps
+    pub limit: Uint128,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const SWAP_STATE: Item<SwapState> = Item::new("swap_state");
@@ -0,0 +1,330 @@
+use cosmwasm_std::{coin, coins, from_slice, to_vec, Addr, Empty, Uint128};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use factory::testing::{attribute, deploy_factory, deploy_pool, OWNER};
+use libraries::fixed_point_96::FixedPoint96;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, QuoteResponse};
+use crate::path::{Path, PathElement, PoolHop};
+
+const ATOM: &str = "uatom";
+const OSMO: &str = "uosmo";
+const XION: &str = "uxion";
+const FEE: u32 = 3000;
+
+fn router_contract() -> Box<dyn Contract<Empty>> {
+    Box::new(
+        ContractWrapper::new(
+            crate::contract::execute,
+            crate::contract::instantiate,
+            crate::contract::query,
+        )
+        .with_reply(crate::contract::reply),
+    )
+}
+
+struct Suite {
+    app: App,
+    router: Addr,
+}
+
+impl Suite {
+    /// Deploys a factory with uatom/uxion and uosmo/uxion pools at a price of 1, both with the same liquidity, and
+    /// a router for the factory's pools
+    fn new() -> Self {
+        let mut app = App::new(|router, _, storage| {
+            let funds = vec![
+                coin(1_000_000_000, ATOM),
+                coin(1_000_000_000, OSMO),
+                coin(1_000_000_000, XION),
+            ];
+            for user in ["provider", "trader"] {
+                router
+                    .bank
+                    .init_balance(storage, &Addr::unchecked(user), funds.clone())
+                    .unwrap();
+            }
+        });
+        let factory = deploy_factory(&mut app);
+        for token in [ATOM, OSMO] {
+            let pool = deploy_pool(&mut app, &factory, token, XION, FEE, FixedPoint96::Q96);
+            app.execute_contract(
+                Addr::unchecked("provider"),
+                pool,
+                &liquidity_pool::msg::ExecuteMsg::Mint {
+                    recipient: "provider".to_string(),
+                    tick_lower: -60_000,
+                    tick_upper: 60_000,
+                    amount: Uint128::new(10_000_000),
+                    data: Default::default(),
+                },
+                &[coin(10_000_000, token), coin(10_000_000, XION)],
+            )
+            .unwrap();
+        }
+
+        let router_code_id = app.store_code(router_contract());
+        let router = app
+            .instantiate_contract(
+                router_code_id,
+                Addr::unchecked(OWNER),
+                &InstantiateMsg {
+                    factory: factory.to_string(),
+                },
+                &[],
+                "router",
+                None,
+            )
+            .unwrap();
+
+        Suite { app, router }
+    }
+
+    fn deadline(&self) -> u64 {
+        self.app.block_info().time.seconds() + 60
+    }
+
+    fn swap(&mut self, msg: &ExecuteMsg, denom: &str, amount: u128) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked("trader"),
+            self.router.clone(),
+            msg,
+            &coins(amount, denom),
+        )
+    }
+
+    fn balance(&self, address: &str, denom: &str) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, denom)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    /// Checks the router kept nothing of any token
+    fn assert_router_empty(&self) {
+        for denom in [ATOM, OSMO, XION] {
+            assert_eq!(self.balance(self.router.as_str(), denom), 0);
+        }
+    }
+}
+
+/// An attribute holding an amount
+fn amount(res: &AppResponse, key: &str) -> u128 {
+    attribute(res, key).parse().unwrap()
+}
+
+fn path(elements: &[&str]) -> Path {
+    Path(
+        elements
+            .iter()
+            .map(|element| match element.parse() {
+                Ok(fee) => PathElement::Fee(fee),
+                Err(_) => PathElement::Token(element.to_string()),
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn paths_decode_into_pools() {
+    let atom_xion_osmo = path(&[ATOM, "3000", XION, "500", OSMO]);
+    assert_eq!(
+        atom_xion_osmo.decode().unwrap(),
+        vec![
+            PoolHop {
+                token_in: ATOM.to_string(),
+                fee: 3000,
+                token_out: XION.to_string(),
+            },
+            PoolHop {
+                token_in: XION.to_string(),
+                fee: 500,
+                token_out: OSMO.to_string(),
+            },
+        ]
+    );
+    // paths are written as a flat list of tokens and fees
+    let json = to_vec(&atom_xion_osmo).unwrap();
+    assert_eq!(json, br#"["uatom",3000,"uxion",500,"uosmo"]"#);
+    assert_eq!(from_slice::<Path>(&json).unwrap(), atom_xion_osmo);
+
+    for invalid in [
+        path(&[ATOM]),
+        path(&[ATOM, "3000"]),
+        path(&["3000", ATOM, "3000"]),
+        path(&[ATOM, XION, "3000"]),
+        path(&[ATOM, "3000", ATOM]),
+        path(&[ATOM, "3000", XION, "3000"]),
+    ] {
+        assert_eq!(invalid.decode().unwrap_err(), ContractError::InvalidPath {});
+    }
+}
+
+#[test]
+fn exact_input_swaps_through_each_pool() {
+    let mut suite = Suite::new();
+    let deadline = suite.deadline();
+
+    let single = ExecuteMsg::ExactInputSingle {
+        token_out: XION.to_string(),
+        fee: FEE,
+        recipient: "trader".to_string(),
+        deadline,
+        amount_out_minimum: Uint128::new(990),
+        sqrt_price_limit_x96: None,
+    };
+    let res = suite.swap(&single, ATOM, 1_000).unwrap();
+    // 0.3% fee, with a little price impact
+    assert_eq!(amount(&res, "amount_out"), 996);
+    assert_eq!(suite.balance("trader", XION), 1_000_000_000 + 996);
+    suite.assert_router_empty();
+
+    let multi = |amount_out_minimum| ExecuteMsg::ExactInput {
+        path: path(&[ATOM, "3000", XION, "3000", OSMO]),
+        recipient: "trader".to_string(),
+        deadline,
+        amount_out_minimum: Uint128::new(amount_out_minimum),
+    };
+    let err = suite.swap(&multi(1_000), ATOM, 1_000).unwrap_err();
+    assert_eq!(
+        err.root_cause().to_string(),
+        ContractError::TooLittleReceived {
+            amount_out: Uint128::new(992),
+            amount_out_minimum: Uint128::new(1_000),
+        }
+        .to_string()
+    );
+    let res = suite.swap(&multi(990), ATOM, 1_000).unwrap();
+    assert_eq!(amount(&res, "amount_in"), 1_000);
+    assert_eq!(amount(&res, "amount_out"), 992);
+    assert_eq!(suite.balance("trader", ATOM), 1_000_000_000 - 2_000);
+    assert_eq!(suite.balance("trader", OSMO), 1_000_000_000 + 992);
+    suite.assert_router_empty();
+
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(61);
+    });
+    let err = suite.swap(&single, ATOM, 1_000).unwrap_err();
+    assert_eq!(
+        err.root_cause().downcast_ref::<ContractError>(),
+        Some(&ContractError::TransactionTooOld {})
+    );
+}
+
+#[test]
+fn exact_output_pays_backwards_and_refunds_the_rest() {
+    let mut suite = Suite::new();
+    let deadline = suite.deadline();
+
+    let single = ExecuteMsg::ExactOutputSingle {
+        token_out: XION.to_string(),
+        fee: FEE,
+        recipient: "trader".to_string(),
+        deadline,
+        amount_out: Uint128::new(996),
+        amount_in_maximum: Uint128::new(1_000),
+        sqrt_price_limit_x96: None,
+    };
+    let res = suite.swap(&single, ATOM, 2_000).unwrap();
+    let amount_in = amount(&res, "amount_in");
+    assert!(amount_in <= 1_000);
+    assert_eq!(suite.balance("trader", ATOM), 1_000_000_000 - amount_in);
+    assert_eq!(suite.balance("trader", XION), 1_000_000_000 + 996);
+    suite.assert_router_empty();
+
+    let multi = |amount_in_maximum| ExecuteMsg::ExactOutput {
+        path: path(&[ATOM, "3000", XION, "3000", OSMO]),
+        recipient: "bob".to_string(),
+        deadline,
+        amount_out: Uint128::new(992),
+        amount_in_maximum: Uint128::new(amount_in_maximum),
+    };
+    let err = suite.swap(&multi(900), ATOM, 2_000).unwrap_err();
+    assert!(err
+        .root_cause()
+        .to_string()
+        .starts_with("Too much requested"));
+    let res = suite.swap(&multi(1_100), ATOM, 2_000).unwrap();
+    let amount_in = amount(&res, "amount_in");
+    assert!((990..=1_010).contains(&amount_in));
+    assert_eq!(amount(&res, "amount_out"), 992);
+    assert_eq!(suite.balance("bob", OSMO), 992);
+    suite.assert_router_empty();
+
+    // the path must start with the attached coin
+    let err = suite.swap(&multi(1_100), XION, 2_000).unwrap_err();
+    assert_eq!(
+        err.root_cause().downcast_ref::<ContractError>(),
+        Some(&ContractError::WrongInputToken {
+            denom: XION.to_string(),
+            token_in: ATOM.to_string(),
+        })
+    );
+}
+
+#[test]
+fn quotes_match_routed_swaps() {
+    let mut suite = Suite::new();
+    let deadline = suite.deadline();
+    let atom_xion_osmo = path(&[ATOM, "3000", XION, "3000", OSMO]);
+
+    let quote: QuoteResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.router,
+            &QueryMsg::QuoteExactInput {
+                path: atom_xion_osmo.clone(),
+                amount_in: Uint128::new(1_000),
+            },
+        )
+        .unwrap();
+    assert_eq!(quote.amount_in, Uint128::new(1_000));
+    assert_eq!(quote.amount_out, Uint128::new(992));
+    assert_eq!(quote.sqrt_price_x96_after_list.len(), 2);
+    assert_eq!(quote.initialized_ticks_crossed_list, vec![0, 0]);
+    assert!(quote.gas_estimate > 0);
+
+    let quote: QuoteResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.router,
+            &QueryMsg::QuoteExactOutput {
+                path: atom_xion_osmo.clone(),
+                amount_out: Uint128::new(992),
+            },
+        )
+        .unwrap();
+    assert_eq!(quote.amount_out, Uint128::new(992));
+    let res = suite
+        .swap(
+            &ExecuteMsg::ExactOutput {
+                path: atom_xion_osmo.clone(),
+                recipient: "trader".to_string(),
+                deadline,
+                amount_out: Uint128::new(992),
+                amount_in_maximum: Uint128::new(1_100),
+            },
+            ATOM,
+            2_000,
+        )
+        .unwrap();
+    assert_eq!(amount(&res, "amount_in"), quote.amount_in.u128());
+
+    // the pools only hold 10M of each token
+    let err = suite
+        .app
+        .wrap()
+        .query_wasm_smart::<QuoteResponse>(
+            &suite.router,
+            &QueryMsg::QuoteExactOutput {
+                path: atom_xion_osmo,
+                amount_out: Uint128::new(20_000_000),
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("Pool returned"), "{err}");
+}
@@ -0,0 +1,9 @@
+//! Callbacks a pool invokes on the contract that called it
+
+pub mod flash;
+pub mod mint;
+pub mod swap;
+
+pub use flash::IXionFlashCallback;
+pub use mint::IXionMintCallback;
+pub use swap::IXionSwapCallback;
@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult, Uint256};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Any contract that calls `IXionPoolActions::flash` must implement this interface
+pub trait IXionFlashCallback {
+    /// Called on the flash recipient after it has been sent the requested amounts.
+    /// The recipient must pay back the borrowed amounts plus `fee0`/`fee1` to the pool before returning.
+    fn xion_flash_callback(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        fee0: Uint256,
+        fee1: Uint256,
+        data: Binary,
+    ) -> StdResult<Response>;
+}
+
+/// The execute message a pool sends to deliver `IXionFlashCallback::xion_flash_callback`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum XionFlashCallbackMsg {
+    XionFlashCallback {
+        fee0: Uint256,
+        fee1: Uint256,
+        data: Binary,
+    },
+}
@@ -0,0 +1,29 @@
+use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdResult, Uint256};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Any contract that calls `IXionPoolActions::mint` with callback data must implement this interface
+pub trait IXionMintCallback {
+    /// Called on the minter after liquidity has been minted.
+    /// The minter must pay the pool the tokens owed for the minted liquidity before returning.
+    fn xion_mint_callback(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount0_owed: Uint256,
+        amount1_owed: Uint256,
+        data: Binary,
+    ) -> StdResult<Response>;
+}
+
+/// The execute message a pool sends to deliver `IXionMintCallback::xion_mint_callback`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum XionMintCallbackMsg {
+    XionMintCallback {
+        amount0_owed: Uint256,
+        amount1_owed: Uint256,
+        data: Binary,
+    },
+}
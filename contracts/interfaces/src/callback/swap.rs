@@ -0,0 +1,29 @@
+use cosmwasm_std::{Binary, DepsMut, Env, Int256, MessageInfo, Response, StdResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Any contract that calls `IXionPoolActions::swap` with callback data must implement this interface
+pub trait IXionSwapCallback {
+    /// Called on the swapper after the output of a swap has been sent to the recipient.
+    /// The deltas are from the pool's point of view: positive amounts must be paid to the pool before returning.
+    fn xion_swap_callback(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        amount0_delta: Int256,
+        amount1_delta: Int256,
+        data: Binary,
+    ) -> StdResult<Response>;
+}
+
+/// The execute message a pool sends to deliver `IXionSwapCallback::xion_swap_callback`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum XionSwapCallbackMsg {
+    XionSwapCallback {
+        amount0_delta: Int256,
+        amount1_delta: Int256,
+        data: Binary,
+    },
+}
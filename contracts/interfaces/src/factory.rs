@@ -0,0 +1,24 @@
+//! The part of the factory interface its pools call into
+
+use cosmwasm_std::{Addr, QuerierWrapper, StdResult};
+use serde::{Deserialize, Serialize};
+
+/// The factory query a pool makes to find the factory owner
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum XionFactoryQueryMsg {
+    Config {},
+}
+
+/// The fields of the factory's config response that pools read
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct XionFactoryConfig {
+    pub owner: Addr,
+}
+
+/// Looks up the current owner of the factory, who may run the pools' owner actions
+pub fn query_factory_owner(querier: &QuerierWrapper, factory: &Addr) -> StdResult<Addr> {
+    let config: XionFactoryConfig =
+        querier.query_wasm_smart(factory, &XionFactoryQueryMsg::Config {})?;
+    Ok(config.owner)
+}
@@ -0,0 +1,15 @@
+//! The interfaces of a Xion pool, broken up into the same pieces as the Uniswap V3 pool interface
+
+pub mod actions;
+pub mod derived_state;
+pub mod events;
+pub mod immutables;
+pub mod owner_actions;
+pub mod state;
+
+pub use actions::IXionPoolActions;
+pub use derived_state::IXionPoolDerivedState;
+pub use events::IXionPoolEvent;
+pub use immutables::IXionPoolImmutables;
+pub use owner_actions::IXionPoolOwnerActions;
+pub use state::IXionPoolState;
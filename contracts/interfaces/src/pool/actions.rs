@@ -0,0 +1,94 @@
+use cosmwasm_std::{
+    Binary, DepsMut, Env, Int256, MessageInfo, Response, StdError, Uint128, Uint256,
+};
+
+/// Permissionless pool actions
+/// Amounts owed to the pool are paid with the funds attached to the call; anything attached beyond what is owed is
+/// refunded to the sender. A mint or swap carrying non-empty `data` is instead paid in a callback on the sender,
+/// delivered as a submessage whose reply checks the pool's balances before the call is allowed to succeed.
+#[allow(clippy::too_many_arguments)]
+pub trait IXionPoolActions {
+    type Error: From<StdError>;
+
+    /// Sets the initial price for the pool
+    /// Price is represented as a sqrt(amount_token1/amount_token0) Q64.96 value
+    fn initialize(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        sqrt_price_x96: Uint256,
+    ) -> Result<Response, Self::Error>;
+
+    /// Adds liquidity for the given recipient/tick_lower/tick_upper position
+    fn mint(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: Uint128,
+        data: Binary,
+    ) -> Result<Response, Self::Error>;
+
+    /// Collects tokens owed to a position
+    fn collect(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount0_requested: Uint128,
+        amount1_requested: Uint128,
+    ) -> Result<Response, Self::Error>;
+
+    /// Burn liquidity from the sender and account tokens owed for the liquidity to the position
+    fn burn(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: Uint128,
+    ) -> Result<Response, Self::Error>;
+
+    /// Swap token0 for token1, or token1 for token0
+    /// A positive `amount_specified` is an exact input amount, a negative one an exact output amount
+    fn swap(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        zero_for_one: bool,
+        amount_specified: Int256,
+        sqrt_price_limit_x96: Uint256,
+        data: Binary,
+    ) -> Result<Response, Self::Error>;
+
+    /// Receive token0 and/or token1 and pay it back, plus a fee, in the callback
+    fn flash(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount0: Uint128,
+        amount1: Uint128,
+        data: Binary,
+    ) -> Result<Response, Self::Error>;
+
+    /// Increase the maximum number of price and liquidity observations that this pool will store
+    fn increase_observation_cardinality_next(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        observation_cardinality_next: u16,
+    ) -> Result<Response, Self::Error>;
+}
@@ -0,0 +1,19 @@
+use cosmwasm_std::{QuerierWrapper, StdResult, Uint256};
+
+/// Pool state that is not stored, but derived from the stored state
+pub trait IXionPoolDerivedState {
+    /// Returns the cumulative tick and seconds per liquidity as of each `seconds_ago` from the current block time
+    fn observe(
+        &self,
+        querier: &QuerierWrapper,
+        seconds_ago: Vec<u32>,
+    ) -> StdResult<(Vec<i64>, Vec<Uint256>)>;
+
+    /// Returns a snapshot of the tick cumulative, seconds per liquidity and seconds inside a tick range
+    fn snapshot_cumulatives_inside(
+        &self,
+        querier: &QuerierWrapper,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> StdResult<(i64, Uint256, u32)>;
+}
@@ -0,0 +1,206 @@
+//! Events emitted by a pool
+//! Each event is emitted as a custom event whose attributes are the event's fields, under the same names. CosmWasm
+//! prefixes custom event types with `wasm-` and adds the emitting contract's address as `_contract_address`, so a
+//! swap shows up in a transaction as a `wasm-swap` event with the attributes `_contract_address`, `sender`,
+//! `recipient`, `amount0`, `amount1`, `sqrt_price_x96`, `liquidity` and `tick`.
+//! Signed amounts are from the pool's point of view: positive amounts were paid into the pool, negative ones out.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Event, Int256, StdError, StdResult, Uint128, Uint256};
+use serde::{Deserialize, Serialize};
+
+/// Emitted once by `initialize`: `wasm-initialize`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct InitializeEvent {
+    pub sqrt_price_x96: Uint256,
+    pub tick: i32,
+}
+
+/// Emitted when liquidity is minted for a position: `wasm-mint`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MintEvent {
+    pub sender: String,
+    pub owner: String,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub amount: Uint128,
+    pub amount0: Uint128,
+    pub amount1: Uint128,
+}
+
+/// Emitted when fees and burned liquidity are collected by a position owner: `wasm-collect`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CollectEvent {
+    pub owner: String,
+    pub recipient: String,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub amount0: Uint128,
+    pub amount1: Uint128,
+}
+
+/// Emitted when liquidity is removed from a position, crediting the amounts to its tokens owed: `wasm-burn`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BurnEvent {
+    pub owner: String,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub amount: Uint128,
+    pub amount0: Uint128,
+    pub amount1: Uint128,
+}
+
+/// Emitted by each swap, with the pool's price, liquidity and tick after it: `wasm-swap`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SwapEvent {
+    pub sender: String,
+    pub recipient: String,
+    pub amount0: Int256,
+    pub amount1: Int256,
+    pub sqrt_price_x96: Uint256,
+    pub liquidity: Uint128,
+    pub tick: i32,
+}
+
+/// Emitted once a flash loan has been paid back, `paid0`/`paid1` being the fees paid on top: `wasm-flash`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FlashEvent {
+    pub sender: String,
+    pub recipient: String,
+    pub amount0: Uint128,
+    pub amount1: Uint128,
+    pub paid0: Uint128,
+    pub paid1: Uint128,
+}
+
+/// Emitted when the observation cardinality grows: `wasm-increase_observation_cardinality_next`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct IncreaseObservationCardinalityNextEvent {
+    pub observation_cardinality_next_old: u16,
+    pub observation_cardinality_next_new: u16,
+}
+
+/// Emitted when the factory owner changes the protocol fee: `wasm-set_fee_protocol`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SetFeeProtocolEvent {
+    pub fee_protocol0_old: u8,
+    pub fee_protocol1_old: u8,
+    pub fee_protocol0_new: u8,
+    pub fee_protocol1_new: u8,
+}
+
+/// Emitted when the factory owner collects protocol fees: `wasm-collect_protocol`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CollectProtocolEvent {
+    pub sender: String,
+    pub recipient: String,
+    pub amount0: Uint128,
+    pub amount1: Uint128,
+}
+
+/// An event of a pool, converted to and parsed from a CosmWasm event
+pub trait IXionPoolEvent: Sized {
+    /// The custom event type, which shows up prefixed with `wasm-` in transactions
+    const TYPE: &'static str;
+
+    /// The event to add to the pool's response
+    fn to_event(&self) -> Event;
+
+    /// Parses an event of this type, as emitted by the pool or as seen in a transaction or submessage reply
+    fn from_event(event: &Event) -> StdResult<Self>;
+
+    /// Finds and parses the first event of this type emitted by `pool`
+    fn find(events: &[Event], pool: &Addr) -> StdResult<Option<Self>> {
+        events
+            .iter()
+            .find(|event| {
+                is_type(event, Self::TYPE)
+                    && event
+                        .attributes
+                        .iter()
+                        .any(|attr| attr.key == "_contract_address" && attr.value == pool.as_str())
+            })
+            .map(Self::from_event)
+            .transpose()
+    }
+}
+
+fn is_type(event: &Event, ty: &str) -> bool {
+    event.ty == ty || event.ty.strip_prefix("wasm-") == Some(ty)
+}
+
+/// Parses the value of the attribute `key` of `event`
+fn parse_attribute<T>(event: &Event, key: &str) -> StdResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let value = event
+        .attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+        .ok_or_else(|| {
+            StdError::generic_err(format!("{} event has no {key} attribute", event.ty))
+        })?;
+    value.parse().map_err(|err: T::Err| {
+        StdError::parse_err(std::any::type_name::<T>(), format!("{key}: {err}"))
+    })
+}
+
+/// Implements `IXionPoolEvent` for an event struct, with one attribute per field
+macro_rules! pool_event {
+    ($event:ident, $ty:literal, { $($field:ident),+ $(,)? }) => {
+        impl IXionPoolEvent for $event {
+            const TYPE: &'static str = $ty;
+
+            fn to_event(&self) -> Event {
+                Event::new(Self::TYPE)
+                    $(.add_attribute(stringify!($field), self.$field.to_string()))+
+            }
+
+            fn from_event(event: &Event) -> StdResult<Self> {
+                if !is_type(event, Self::TYPE) {
+                    return Err(StdError::generic_err(format!(
+                        "expected a {} event, got {}",
+                        Self::TYPE,
+                        event.ty
+                    )));
+                }
+                Ok($event {
+                    $($field: parse_attribute(event, stringify!($field))?,)+
+                })
+            }
+        }
+    };
+}
+
+pool_event!(InitializeEvent, "initialize", { sqrt_price_x96, tick });
+pool_event!(MintEvent, "mint", {
+    sender, owner, tick_lower, tick_upper, amount, amount0, amount1,
+});
+pool_event!(CollectEvent, "collect", {
+    owner, recipient, tick_lower, tick_upper, amount0, amount1,
+});
+pool_event!(BurnEvent, "burn", {
+    owner, tick_lower, tick_upper, amount, amount0, amount1,
+});
+pool_event!(SwapEvent, "swap", {
+    sender, recipient, amount0, amount1, sqrt_price_x96, liquidity, tick,
+});
+pool_event!(FlashEvent, "flash", {
+    sender, recipient, amount0, amount1, paid0, paid1,
+});
+pool_event!(
+    IncreaseObservationCardinalityNextEvent,
+    "increase_observation_cardinality_next",
+    { observation_cardinality_next_old, observation_cardinality_next_new }
+);
+pool_event!(SetFeeProtocolEvent, "set_fee_protocol", {
+    fee_protocol0_old, fee_protocol1_old, fee_protocol0_new, fee_protocol1_new,
+});
+pool_event!(CollectProtocolEvent, "collect_protocol", {
+    sender, recipient, amount0, amount1,
+});
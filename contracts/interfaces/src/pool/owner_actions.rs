@@ -0,0 +1,28 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdError, Uint128};
+
+/// Pool actions restricted to the owner of the factory that created the pool
+pub trait IXionPoolOwnerActions {
+    type Error: From<StdError>;
+
+    /// Sets the denominator of the protocol's share of the swap fees of each token
+    /// Each value is either 0, turning the protocol fee off, or between 4 and 10 inclusive
+    fn set_fee_protocol(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        fee_protocol0: u8,
+        fee_protocol1: u8,
+    ) -> Result<Response, Self::Error>;
+
+    /// Collects up to the requested amounts of the protocol fees accrued to the pool
+    fn collect_protocol(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        recipient: String,
+        amount0_requested: Uint128,
+        amount1_requested: Uint128,
+    ) -> Result<Response, Self::Error>;
+}
@@ -1,26 +1,23 @@
 use cosmwasm_std::{Deps, StdResult};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Uint128, Uint256};
 
 pub trait IXionPoolState {
-    fn slot0(&self, deps: Deps) -> StdResult<(Uint160, i32, u16, u16, u16, u8, bool)>;
+    fn slot0(&self, deps: Deps) -> StdResult<(Uint256, i32, u16, u16, u16, u8, bool)>;
     fn fee_growth_global0_x128(&self, deps: Deps) -> StdResult<Uint256>;
     fn fee_growth_global1_x128(&self, deps: Deps) -> StdResult<Uint256>;
     fn protocol_fees(&self, deps: Deps) -> StdResult<(Uint128, Uint128)>;
     fn liquidity(&self, deps: Deps) -> StdResult<Uint128>;
+    #[allow(clippy::type_complexity)]
     fn ticks(
         &self,
         deps: Deps,
         tick: i32,
-    ) -> StdResult<(Uint128, i128, Uint256, Uint256, i56, Uint160, u32, bool)>;
+    ) -> StdResult<(Uint128, i128, Uint256, Uint256, i64, Uint256, u32, bool)>;
     fn tick_bitmap(&self, deps: Deps, word_position: i16) -> StdResult<Uint256>;
     fn positions(
         &self,
         deps: Deps,
         key: [u8; 32],
     ) -> StdResult<(Uint128, Uint256, Uint256, Uint128, Uint128)>;
-    fn observations(
-        &self,
-        deps: Deps,
-        index: u256,
-    ) -> StdResult<(u32, i56, Uint160, bool)>;
+    fn observations(&self, deps: Deps, index: u16) -> StdResult<(u32, i64, Uint256, bool)>;
 }
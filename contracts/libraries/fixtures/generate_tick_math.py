@@ -0,0 +1,125 @@
+#!/usr/bin/env python3
+"""Generates the TickMath fixture tables from an arbitrary precision port of the Uniswap V3 TickMath library.
+
+Python integers follow the Solidity semantics exactly here (`>>` on negative numbers is arithmetic), so the tables are
+an independent reference for `libraries::tick_math`. Run from this directory: `python3 generate_tick_math.py`.
+"""
+
+import random
+
+U256 = 2**256
+MIN_TICK = -887272
+MAX_TICK = 887272
+MIN_SQRT_RATIO = 4295128739
+MAX_SQRT_RATIO = 1461446703485210103287273052203988822378723970342
+
+RATIO_MULTIPLIERS = [
+    (0x2, 0xFFF97272373D413259A46990580E213A),
+    (0x4, 0xFFF2E50F5F656932EF12357CF3C7FDCC),
+    (0x8, 0xFFE5CACA7E10E4E61C3624EAA0941CD0),
+    (0x10, 0xFFCB9843D60F6159C9DB58835C926644),
+    (0x20, 0xFF973B41FA98C081472E6896DFB254C0),
+    (0x40, 0xFF2EA16466C96A3843EC78B326B52861),
+    (0x80, 0xFE5DEE046A99A2A811C461F1969C3053),
+    (0x100, 0xFCBE86C7900A88AEDCFFC83B479AA3A4),
+    (0x200, 0xF987A7253AC413176F2B074CF7815E54),
+    (0x400, 0xF3392B0822B70005940C7A398E4B70F3),
+    (0x800, 0xE7159475A2C29B7443B29C7FA6E889D9),
+    (0x1000, 0xD097F3BDFD2022B8845AD8F792AA5825),
+    (0x2000, 0xA9F746462D870FDF8A65DC1F90E061E5),
+    (0x4000, 0x70D869A156D2A1B890BB3DF62BAF32F7),
+    (0x8000, 0x31BE135F97D08FD981231505542FCFA6),
+    (0x10000, 0x9AA508B5B7A84E1C677DE54F3E99BC9),
+    (0x20000, 0x5D6AF8DEDB81196699C329225EE604),
+    (0x40000, 0x2216E584F5FA1EA926041BEDFE98),
+    (0x80000, 0x48A170391F7DC42444E8FA2),
+]
+
+
+def get_sqrt_ratio_at_tick(tick):
+    abs_tick = abs(tick)
+    assert abs_tick <= MAX_TICK
+    ratio = 0xFFFCB933BD6FAD37AA2D162D1A594001 if abs_tick & 1 else 2**128
+    for bit, multiplier in RATIO_MULTIPLIERS:
+        if abs_tick & bit:
+            ratio = (ratio * multiplier) % U256 >> 128
+    if tick > 0:
+        ratio = (U256 - 1) // ratio
+    return (ratio >> 32) + (0 if ratio % (1 << 32) == 0 else 1)
+
+
+def get_tick_at_sqrt_ratio(sqrt_price_x96):
+    assert MIN_SQRT_RATIO <= sqrt_price_x96 < MAX_SQRT_RATIO
+    ratio = sqrt_price_x96 << 32
+    msb = ratio.bit_length() - 1
+    r = ratio >> (msb - 127) if msb >= 128 else ratio << (127 - msb)
+
+    # log_2 is an int256 in two's complement, with fractional bits or-ed into its low 64 bits
+    log_2 = ((msb - 128) << 64) % U256
+    for shift in range(63, 49, -1):
+        r = (r * r) >> 127
+        f = r >> 128
+        log_2 |= f << shift
+        r >>= f
+    if log_2 >= 2**255:
+        log_2 -= U256
+
+    log_sqrt10001 = log_2 * 255738958999603826347141
+    tick_low = (log_sqrt10001 - 3402992956809132418596140100660247210) >> 128
+    tick_hi = (log_sqrt10001 + 291339464771989622907027621153398088495) >> 128
+    if tick_low == tick_hi or get_sqrt_ratio_at_tick(tick_hi) > sqrt_price_x96:
+        return tick_low
+    return tick_hi
+
+
+def main():
+    random.seed(20261018)
+
+    # the bounds, each power of two and its neighbours, round ticks and the usable bounds of the common spacings
+    ticks = {MIN_TICK, MIN_TICK + 1, -1, 0, 1, MAX_TICK - 1, MAX_TICK}
+    for bit in range(20):
+        for sign in (1, -1):
+            for delta in (-1, 0, 1):
+                tick = sign * 2**bit + delta
+                if MIN_TICK <= tick <= MAX_TICK:
+                    ticks.add(tick)
+    ticks.update(range(-880000, 880001, 10000))
+    for tick_spacing in (1, 10, 60, 200):
+        max_tick = MAX_TICK // tick_spacing * tick_spacing
+        ticks.update((-max_tick, max_tick))
+    while len(ticks) < 400:
+        ticks.add(random.randint(MIN_TICK, MAX_TICK))
+
+    with open("sqrt_ratio_at_tick.csv", "w") as table:
+        table.write(
+            "# tick,sqrt_price_x96: get_sqrt_ratio_at_tick reference values, "
+            "generated from the Uniswap V3 TickMath algorithm\n"
+        )
+        for tick in sorted(ticks):
+            table.write(f"{tick},{get_sqrt_ratio_at_tick(tick)}\n")
+
+    # the price of each tick above and its neighbours, plus prices spread over the whole range
+    prices = {MIN_SQRT_RATIO, MIN_SQRT_RATIO + 1, MAX_SQRT_RATIO - 1, 2**96 - 1, 2**96, 2**96 + 1}
+    for tick in ticks:
+        sqrt_price_x96 = get_sqrt_ratio_at_tick(tick)
+        for delta in (-1, 0, 1):
+            if MIN_SQRT_RATIO <= sqrt_price_x96 + delta < MAX_SQRT_RATIO:
+                prices.add(sqrt_price_x96 + delta)
+    spread = set()
+    while len(spread) < 200:
+        price = random.getrandbits(random.randint(33, 160))
+        if MIN_SQRT_RATIO <= price < MAX_SQRT_RATIO:
+            spread.add(price)
+    prices |= spread
+
+    with open("tick_at_sqrt_ratio.csv", "w") as table:
+        table.write(
+            "# sqrt_price_x96,tick: get_tick_at_sqrt_ratio reference values, "
+            "generated from the Uniswap V3 TickMath algorithm\n"
+        )
+        for sqrt_price_x96 in sorted(prices):
+            table.write(f"{sqrt_price_x96},{get_tick_at_sqrt_ratio(sqrt_price_x96)}\n")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! This library provides functionality for computing bit properties of an unsigned integer
+
+use cosmwasm_std::StdError;
+use cosmwasm_std::Uint256;
+
+pub struct BitMath;
+
+impl BitMath {
+    /// Returns the index of the most significant bit of the number,
+    /// where the least significant bit is at index 0 and the most significant bit is at index 255
+    /// The function satisfies the property:
+    /// x >= 2**mostSignificantBit(x) and x < 2**(mostSignificantBit(x)+1)
+    pub fn most_significant_bit(x: Uint256) -> Result<u8, StdError> {
+        if x.is_zero() {
+            return Err(StdError::generic_err("Input must be greater than 0"));
+        }
+
+        let bytes = x.to_be_bytes();
+        let (i, byte) = bytes
+            .iter()
+            .enumerate()
+            .find(|(_, b)| **b != 0)
+            .expect("non-zero input has a non-zero byte");
+
+        Ok(((31 - i) * 8) as u8 + (7 - byte.leading_zeros() as u8))
+    }
+
+    /// Returns the index of the least significant bit of the number,
+    /// where the least significant bit is at index 0 and the most significant bit is at index 255
+    /// The function satisfies the property:
+    /// (x & 2**leastSignificantBit(x)) != 0 and (x & (2**(leastSignificantBit(x)) - 1)) == 0)
+    pub fn least_significant_bit(x: Uint256) -> Result<u8, StdError> {
+        if x.is_zero() {
+            return Err(StdError::generic_err("Input must be greater than 0"));
+        }
+
+        let bytes = x.to_le_bytes();
+        let (i, byte) = bytes
+            .iter()
+            .enumerate()
+            .find(|(_, b)| **b != 0)
+            .expect("non-zero input has a non-zero byte");
+
+        Ok((i * 8) as u8 + byte.trailing_zeros() as u8)
+    }
+
+    /// Returns the bitwise AND of two 256-bit words
+    pub fn and(x: Uint256, y: Uint256) -> Uint256 {
+        Self::zip_bytes(x, y, |a, b| a & b)
+    }
+
+    /// Returns the bitwise XOR of two 256-bit words
+    pub fn xor(x: Uint256, y: Uint256) -> Uint256 {
+        Self::zip_bytes(x, y, |a, b| a ^ b)
+    }
+
+    fn zip_bytes(x: Uint256, y: Uint256, op: impl Fn(u8, u8) -> u8) -> Uint256 {
+        let (x, y) = (x.to_be_bytes(), y.to_be_bytes());
+        let mut out = [0u8; 32];
+        for i in 0..32 {
+            out[i] = op(x[i], y[i]);
+        }
+        Uint256::from_be_bytes(out)
+    }
+}
@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! A library for handling binary fixed point numbers, see https://en.wikipedia.org/wiki/Q_(number_format)
+
+use cosmwasm_std::Uint256;
+
+pub struct FixedPoint128;
+
+impl FixedPoint128 {
+    /// 2**128, one byte above the top of a u128
+    pub const Q128: Uint256 = Uint256::from_be_bytes([
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, //
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    ]);
+}
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! A library for handling binary fixed point numbers, see https://en.wikipedia.org/wiki/Q_(number_format)
+//! Used in SqrtPriceMath.sol
+
+use cosmwasm_std::Uint256;
+
+pub struct FixedPoint96;
+
+impl FixedPoint96 {
+    pub const RESOLUTION: u32 = 96;
+    pub const Q96: Uint256 = Uint256::from_u128(0x1000000000000000000000000);
+}
@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MIT
+
+//! A library for handling 512-bit math functions
+//! Facilitates multiplication and division that can have overflow of an intermediate value without any loss of precision
+//! Credit to Remco Bloemen under MIT license https://xn--2-umb.com/21/muldiv
+
+use cosmwasm_std::{
+    DivideByZeroError, OverflowError, OverflowOperation, StdError, StdResult, Uint256, Uint512,
+};
+
+pub struct FullMath;
+
+impl FullMath {
+    /// Calculates floor(a×b÷denominator) with full precision. Panics if result overflows a u256 or denominator == 0
+    pub fn mul_div(a: Uint256, b: Uint256, denominator: Uint256) -> Uint256 {
+        FullMath::checked_mul_div(a, b, denominator).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates ceil(a×b÷denominator) with full precision. Panics if result overflows a u256 or denominator == 0
+    pub fn mul_div_rounding_up(a: Uint256, b: Uint256, denominator: Uint256) -> Uint256 {
+        FullMath::checked_mul_div_rounding_up(a, b, denominator)
+            .unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Calculates floor(a×b÷denominator) with full precision
+    /// Fails if denominator == 0 or the result overflows a u256
+    pub fn checked_mul_div(a: Uint256, b: Uint256, denominator: Uint256) -> StdResult<Uint256> {
+        let (result, _) = FullMath::div_rem(a, b, denominator)?;
+        Ok(result)
+    }
+
+    /// Calculates ceil(a×b÷denominator) with full precision
+    /// Fails if denominator == 0 or the result overflows a u256
+    pub fn checked_mul_div_rounding_up(
+        a: Uint256,
+        b: Uint256,
+        denominator: Uint256,
+    ) -> StdResult<Uint256> {
+        let (result, remainder) = FullMath::div_rem(a, b, denominator)?;
+        if remainder.is_zero() {
+            return Ok(result);
+        }
+        result
+            .checked_add(Uint256::one())
+            .map_err(|_| StdError::overflow(OverflowError::new(OverflowOperation::Add, result, 1)))
+    }
+
+    /// The quotient and remainder of the 512-bit product a×b divided by denominator
+    fn div_rem(a: Uint256, b: Uint256, denominator: Uint256) -> StdResult<(Uint256, Uint512)> {
+        if denominator.is_zero() {
+            return Err(StdError::divide_by_zero(DivideByZeroError::new(a)));
+        }
+
+        // 512-bit multiply [prod1 prod0] = a * b, so the intermediate product can never overflow
+        let product = a.full_mul(b);
+        let denominator = Uint512::from(denominator);
+
+        // Make sure the result is less than 2**256
+        let result = Uint256::try_from(product / denominator)
+            .map_err(|_| StdError::overflow(OverflowError::new(OverflowOperation::Mul, a, b)))?;
+        Ok((result, product % denominator))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::fixed_point_128::FixedPoint128;
+
+    fn uint(value: u128) -> Uint256 {
+        Uint256::from(value)
+    }
+
+    #[test]
+    fn mul_div_keeps_the_full_intermediate_product() {
+        let q128 = FixedPoint128::Q128;
+        assert_eq!(FullMath::mul_div(q128, q128, q128), q128);
+        assert_eq!(
+            FullMath::mul_div(Uint256::MAX, Uint256::MAX, Uint256::MAX),
+            Uint256::MAX
+        );
+        assert_eq!(FullMath::mul_div(q128, uint(50), uint(100)), q128 / uint(2));
+        assert_eq!(FullMath::mul_div(uint(7), uint(3), uint(2)), uint(10));
+        assert_eq!(
+            FullMath::mul_div_rounding_up(uint(7), uint(3), uint(2)),
+            uint(11)
+        );
+        assert_eq!(
+            FullMath::mul_div_rounding_up(uint(6), uint(3), uint(2)),
+            uint(9)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Divide by zero")]
+    fn mul_div_panics_on_zero_denominator() {
+        FullMath::mul_div(uint(1), uint(1), Uint256::zero());
+    }
+
+    #[test]
+    fn checked_variants_report_errors() {
+        let q128 = FixedPoint128::Q128;
+        assert!(matches!(
+            FullMath::checked_mul_div(q128, q128, Uint256::zero()),
+            Err(StdError::DivideByZero { .. })
+        ));
+        assert!(matches!(
+            FullMath::checked_mul_div(q128, q128, Uint256::one()),
+            Err(StdError::Overflow { .. })
+        ));
+        assert!(matches!(
+            FullMath::checked_mul_div(Uint256::MAX, Uint256::MAX, Uint256::MAX - Uint256::one()),
+            Err(StdError::Overflow { .. })
+        ));
+
+        // the floor is exactly 2^256 - 1, so rounding it up overflows
+        let a = uint(535006138814359);
+        let b =
+            Uint256::from_str("432862656469423142931042426214547535783388063929571229938474969")
+                .unwrap();
+        assert_eq!(FullMath::checked_mul_div(a, b, uint(2)), Ok(Uint256::MAX));
+        assert!(matches!(
+            FullMath::checked_mul_div_rounding_up(a, b, uint(2)),
+            Err(StdError::Overflow { .. })
+        ));
+    }
+}
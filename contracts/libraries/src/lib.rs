@@ -1,14 +1,24 @@
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
-}
+//! Math and state libraries for the Xion concentrated liquidity pools
+//! Ports of the Uniswap V3 core libraries, shared by the pool, order book and trading contracts.
 
+pub mod bit_math;
+pub mod fixed_point_128;
+pub mod fixed_point_96;
+pub mod full_math;
+pub mod liquidity_math;
+pub mod low_gas_safe_math;
+pub mod oracle;
+pub mod position;
+pub mod safe_cast;
+pub mod sqrt_price_math;
+pub mod swap_math;
 #[cfg(test)]
-mod tests {
-    use super::*;
+mod test_support;
+pub mod tick;
+pub mod tick_bitmap;
+pub mod tick_math;
+pub mod transfer_helper;
+pub mod types;
+pub mod unsafe_math;
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
-    }
-}
+pub use crate::types::{i24, i56, u160, u256};
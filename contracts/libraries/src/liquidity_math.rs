@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Math library for liquidity
+
+/// Add a signed liquidity delta to liquidity and revert if it overflows or underflows
+///
+/// # Arguments
+///
+/// * `x` - The liquidity before change
+/// * `y` - The delta by which liquidity should be changed
+///
+/// # Returns
+///
+/// * `z` - The liquidity delta
+///
+/// # Panics
+///
+/// Panics if overflow or underflow occurs during the calculation.
+pub fn add_delta(x: u128, y: i128) -> u128 {
+    if y < 0 {
+        // Check for underflow
+        x.checked_sub(y.unsigned_abs()).expect("LS")
+    } else {
+        // Check for overflow
+        x.checked_add(y as u128).expect("LA")
+    }
+}
@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Oracle library for storing price and liquidity observations
+//! Observations are kept in a ring buffer owned by the caller; the functions here compute what goes into it, and read
+//! it back through a `load` function that returns the observation at an index, uninitialized slots as the default.
+
+use cosmwasm_std::{StdError, StdResult, Uint256};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{i24, i56, u160};
+
+/// Struct for storing each observation
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct Observation {
+    /// the block timestamp of the observation
+    pub block_timestamp: u32,
+    /// the tick accumulator, i.e. tick * time elapsed since the pool was first initialized
+    pub tick_cumulative: i56,
+    /// the seconds per liquidity, i.e. seconds elapsed / max(1, liquidity) since the pool was first initialized
+    pub seconds_per_liquidity_cumulative_x128: u160,
+    /// whether or not the observation is initialized
+    pub initialized: bool,
+}
+
+impl Observation {
+    /// Constructs a new observation
+    pub fn new(block_timestamp: u32) -> Self {
+        Observation {
+            block_timestamp,
+            tick_cumulative: 0,
+            seconds_per_liquidity_cumulative_x128: Uint256::zero(),
+            initialized: true,
+        }
+    }
+}
+
+/// Transforms a previous observation into a new observation, given the passage of time and the current tick and liquidity values
+/// block_timestamp _must_ be chronologically equal to or greater than last.block_timestamp, safe for 0 or 1 overflows
+pub fn transform(
+    last: &Observation,
+    block_timestamp: u32,
+    tick: i24,
+    liquidity: u128,
+) -> Observation {
+    let delta = block_timestamp.wrapping_sub(last.block_timestamp);
+    Observation {
+        block_timestamp,
+        tick_cumulative: last
+            .tick_cumulative
+            .wrapping_add((tick as i56).wrapping_mul(delta as i56)),
+        seconds_per_liquidity_cumulative_x128: last
+            .seconds_per_liquidity_cumulative_x128
+            .wrapping_add((Uint256::from(delta) << 128) / Uint256::from(liquidity.max(1))),
+        initialized: true,
+    }
+}
+
+/// Initializes the oracle ring buffer with its first observation
+/// Returns the observation to store at index 0, together with the resulting cardinality and next cardinality
+pub fn initialize(time: u32) -> (Observation, u16, u16) {
+    (Observation::new(time), 1, 1)
+}
+
+/// Prepares the oracle ring buffer to store up to `next` observations
+/// Each new slot is pre-written through `store` with a placeholder, so the cost of creating the slot is paid here
+/// rather than by the swap that later fills it. The placeholder has a non-zero timestamp but is not initialized.
+/// Returns the next cardinality, which is `current` if `next` does not grow the buffer
+pub fn grow<F>(current: u16, next: u16, mut store: F) -> StdResult<u16>
+where
+    F: FnMut(u16, &Observation) -> StdResult<()>,
+{
+    if current == 0 {
+        return Err(StdError::generic_err("oracle is not initialized"));
+    }
+    // no-op if the passed next value isn't greater than the current next value
+    if next <= current {
+        return Ok(current);
+    }
+    let placeholder = Observation {
+        block_timestamp: 1,
+        ..Observation::default()
+    };
+    // store in each slot to prevent fresh storage writes in swaps
+    // this data will not be used because the initialized boolean is still false
+    for index in current..next {
+        store(index, &placeholder)?;
+    }
+    Ok(next)
+}
+
+/// Computes the observation to write into the ring buffer
+/// Writable at most once per block. Index represents the most recently written element. cardinality and index must be tracked externally.
+/// If the index is at the end of the allowable array length (according to cardinality), and the next cardinality
+/// is greater than the current one, cardinality may be increased. This restriction is created to preserve ordering.
+/// Returns the updated index and cardinality, and the observation to store at the updated index (None if nothing
+/// needs to be written because an observation was already recorded this block)
+pub fn write(
+    last: &Observation,
+    index: u16,
+    block_timestamp: u32,
+    tick: i24,
+    liquidity: u128,
+    cardinality: u16,
+    cardinality_next: u16,
+) -> (u16, u16, Option<Observation>) {
+    // early return if we've already written an observation this block
+    if last.block_timestamp == block_timestamp {
+        return (index, cardinality, None);
+    }
+
+    // if the conditions are right, we can bump the cardinality
+    let cardinality_updated = if cardinality_next > cardinality && index == (cardinality - 1) {
+        cardinality_next
+    } else {
+        cardinality
+    };
+
+    let index_updated = ((index as u32 + 1) % cardinality_updated as u32) as u16;
+    (
+        index_updated,
+        cardinality_updated,
+        Some(transform(last, block_timestamp, tick, liquidity)),
+    )
+}
+
+/// Comparator for 32-bit timestamps
+/// safe for 0 or 1 overflows, a and b _must_ be chronologically before or equal to time
+/// Returns whether `a` is chronologically <= `b`
+pub fn lte(time: u32, a: u32, b: u32) -> bool {
+    // if there hasn't been overflow, no need to adjust
+    if a <= time && b <= time {
+        return a <= b;
+    }
+
+    let a_adjusted = if a > time {
+        a as u64
+    } else {
+        a as u64 + (1 << 32)
+    };
+    let b_adjusted = if b > time {
+        b as u64
+    } else {
+        b as u64 + (1 << 32)
+    };
+
+    a_adjusted <= b_adjusted
+}
+
+/// Fetches the observations before_or_at and at_or_after a target, i.e. where [before_or_at, at_or_after] is satisfied.
+/// The result may be the same observation, or adjacent observations.
+/// The answer must be contained in the array, used when the target is located within the stored observation
+/// boundaries: older than the most recent observation and younger, or the same age as, the oldest observation
+fn binary_search<F>(
+    load: &F,
+    time: u32,
+    target: u32,
+    index: u16,
+    cardinality: u16,
+) -> StdResult<(Observation, Observation)>
+where
+    F: Fn(u16) -> StdResult<Observation>,
+{
+    let cardinality = cardinality as u32;
+    // oldest observation
+    let mut l = (index as u32 + 1) % cardinality;
+    // newest observation
+    let mut r = l + cardinality - 1;
+    loop {
+        let i = (l + r) / 2;
+
+        let before_or_at = load((i % cardinality) as u16)?;
+
+        // we've landed on an uninitialized tick, keep searching higher (more recently)
+        if !before_or_at.initialized {
+            l = i + 1;
+            continue;
+        }
+
+        let at_or_after = load(((i + 1) % cardinality) as u16)?;
+
+        let target_at_or_after = lte(time, before_or_at.block_timestamp, target);
+
+        // check if we've found the answer!
+        if target_at_or_after && lte(time, target, at_or_after.block_timestamp) {
+            return Ok((before_or_at, at_or_after));
+        }
+
+        if !target_at_or_after {
+            r = i - 1;
+        } else {
+            l = i + 1;
+        }
+    }
+}
+
+/// Fetches the observations before_or_at and at_or_after a given target, i.e. where [before_or_at, at_or_after] is
+/// satisfied
+/// Fails if the target is older than the oldest stored observation
+fn get_surrounding_observations<F>(
+    load: &F,
+    time: u32,
+    target: u32,
+    tick: i24,
+    index: u16,
+    liquidity: u128,
+    cardinality: u16,
+) -> StdResult<(Observation, Observation)>
+where
+    F: Fn(u16) -> StdResult<Observation>,
+{
+    // optimistically set before to the newest observation
+    let before_or_at = load(index)?;
+
+    // if the target is chronologically at or after the newest observation, we can early return
+    if lte(time, before_or_at.block_timestamp, target) {
+        if before_or_at.block_timestamp == target {
+            // if newest observation equals target, we're in the same block, so we can ignore at_or_after
+            return Ok((before_or_at.clone(), before_or_at));
+        }
+        // otherwise, we need to transform
+        let at_or_after = transform(&before_or_at, target, tick, liquidity);
+        return Ok((before_or_at, at_or_after));
+    }
+
+    // now, set before to the oldest observation
+    let mut before_or_at = load(((index as u32 + 1) % cardinality as u32) as u16)?;
+    if !before_or_at.initialized {
+        before_or_at = load(0)?;
+    }
+
+    // ensure that the target is chronologically at or after the oldest observation
+    if !lte(time, before_or_at.block_timestamp, target) {
+        return Err(StdError::generic_err(
+            "target is older than the oldest observation",
+        ));
+    }
+
+    // if we've reached this point, we have to binary search
+    binary_search(load, time, target, index, cardinality)
+}
+
+/// Returns the accumulator values as of each time seconds ago from the given time
+/// Reverts if `seconds_ago` > oldest observation
+/// 0 may be passed as `seconds_ago` to return the current cumulative values.
+/// If called with a timestamp falling between two observations, returns the counterfactual accumulator values at
+/// exactly the timestamp between the two observations.
+pub fn observe_single<F>(
+    load: &F,
+    time: u32,
+    seconds_ago: u32,
+    tick: i24,
+    index: u16,
+    liquidity: u128,
+    cardinality: u16,
+) -> StdResult<(i56, u160)>
+where
+    F: Fn(u16) -> StdResult<Observation>,
+{
+    if seconds_ago == 0 {
+        let mut last = load(index)?;
+        if last.block_timestamp != time {
+            last = transform(&last, time, tick, liquidity);
+        }
+        return Ok((
+            last.tick_cumulative,
+            last.seconds_per_liquidity_cumulative_x128,
+        ));
+    }
+
+    let target = time.wrapping_sub(seconds_ago);
+
+    let (before_or_at, at_or_after) =
+        get_surrounding_observations(load, time, target, tick, index, liquidity, cardinality)?;
+
+    if target == before_or_at.block_timestamp {
+        // we're at the left boundary
+        Ok((
+            before_or_at.tick_cumulative,
+            before_or_at.seconds_per_liquidity_cumulative_x128,
+        ))
+    } else if target == at_or_after.block_timestamp {
+        // we're at the right boundary
+        Ok((
+            at_or_after.tick_cumulative,
+            at_or_after.seconds_per_liquidity_cumulative_x128,
+        ))
+    } else {
+        // we're in the middle
+        let observation_time_delta = at_or_after
+            .block_timestamp
+            .wrapping_sub(before_or_at.block_timestamp);
+        let target_delta = target.wrapping_sub(before_or_at.block_timestamp);
+        Ok((
+            before_or_at.tick_cumulative.wrapping_add(
+                (at_or_after
+                    .tick_cumulative
+                    .wrapping_sub(before_or_at.tick_cumulative)
+                    / observation_time_delta as i56)
+                    .wrapping_mul(target_delta as i56),
+            ),
+            before_or_at
+                .seconds_per_liquidity_cumulative_x128
+                .wrapping_add(
+                    at_or_after
+                        .seconds_per_liquidity_cumulative_x128
+                        .wrapping_sub(before_or_at.seconds_per_liquidity_cumulative_x128)
+                        .wrapping_mul(Uint256::from(target_delta))
+                        / Uint256::from(observation_time_delta),
+                ),
+        ))
+    }
+}
+
+/// Returns the accumulator values as of each time seconds ago from the given time in the array of `seconds_agos`
+/// Reverts if any of `seconds_agos` > oldest observation
+pub fn observe<F>(
+    load: &F,
+    time: u32,
+    seconds_agos: &[u32],
+    tick: i24,
+    index: u16,
+    liquidity: u128,
+    cardinality: u16,
+) -> StdResult<(Vec<i56>, Vec<u160>)>
+where
+    F: Fn(u16) -> StdResult<Observation>,
+{
+    if cardinality == 0 {
+        return Err(StdError::generic_err("oracle is not initialized"));
+    }
+
+    seconds_agos
+        .iter()
+        .map(|&seconds_ago| {
+            observe_single(load, time, seconds_ago, tick, index, liquidity, cardinality)
+        })
+        .collect::<StdResult<Vec<_>>>()
+        .map(|values| values.into_iter().unzip())
+}
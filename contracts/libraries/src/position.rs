@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Positions represent an owner address' liquidity between a lower and upper tick boundary
+//! Positions store additional state for tracking fees owed to the position
+
+use cosmwasm_std::{StdResult, Uint128, Uint256};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::fixed_point_128::FixedPoint128;
+use crate::full_math::FullMath;
+use crate::liquidity_math::add_delta;
+use crate::types::u256;
+
+/// Struct for storing position information
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct PositionInfo {
+    /// The amount of liquidity owned by this position
+    pub liquidity: Uint128,
+    /// Fee growth per unit of liquidity as of the last update to liquidity or fees owed
+    pub fee_growth_inside0_last_x128: u256,
+    pub fee_growth_inside1_last_x128: u256,
+    /// The fees owed to the position owner in token0/token1
+    pub tokens_owed0: Uint128,
+    pub tokens_owed1: Uint128,
+}
+
+/// Credits accumulated fees to a user's position
+/// @param self_ The individual position to update
+/// @param liquidity_delta The change in pool liquidity as a result of the position update
+/// @param fee_growth_inside0_x128 The all-time fee growth in token0, per unit of liquidity, inside the position's tick boundaries
+/// @param fee_growth_inside1_x128 The all-time fee growth in token1, per unit of liquidity, inside the position's tick boundaries
+pub fn update(
+    self_: &mut PositionInfo,
+    liquidity_delta: i128,
+    fee_growth_inside0_x128: u256,
+    fee_growth_inside1_x128: u256,
+) -> StdResult<()> {
+    let liquidity = self_.liquidity.u128();
+
+    let liquidity_next = if liquidity_delta == 0 {
+        assert!(liquidity > 0, "NP"); // disallow pokes for 0 liquidity positions
+        liquidity
+    } else {
+        add_delta(liquidity, liquidity_delta)
+    };
+
+    // calculate accumulated fees
+    let tokens_owed0 = truncate_to_u128(FullMath::checked_mul_div(
+        fee_growth_inside0_x128.wrapping_sub(self_.fee_growth_inside0_last_x128),
+        Uint256::from(liquidity),
+        FixedPoint128::Q128,
+    )?);
+    let tokens_owed1 = truncate_to_u128(FullMath::checked_mul_div(
+        fee_growth_inside1_x128.wrapping_sub(self_.fee_growth_inside1_last_x128),
+        Uint256::from(liquidity),
+        FixedPoint128::Q128,
+    )?);
+
+    // update the position
+    if liquidity_delta != 0 {
+        self_.liquidity = Uint128::new(liquidity_next);
+    }
+    self_.fee_growth_inside0_last_x128 = fee_growth_inside0_x128;
+    self_.fee_growth_inside1_last_x128 = fee_growth_inside1_x128;
+    if tokens_owed0 > 0 || tokens_owed1 > 0 {
+        // overflow is acceptable, have to withdraw before you hit type(u128).max fees
+        self_.tokens_owed0 = Uint128::new(self_.tokens_owed0.u128().wrapping_add(tokens_owed0));
+        self_.tokens_owed1 = Uint128::new(self_.tokens_owed1.u128().wrapping_add(tokens_owed1));
+    }
+    Ok(())
+}
+
+/// The fees `liquidity` earned while the fee growth inside its range went from `fee_growth_inside_last_x128` to
+/// `fee_growth_inside_x128`, saturating at the u128 maximum
+/// Used by contracts that share one pool position between several owners, to split the fees by liquidity
+pub fn fees_earned(
+    fee_growth_inside_x128: u256,
+    fee_growth_inside_last_x128: u256,
+    liquidity: Uint128,
+) -> StdResult<Uint128> {
+    let fees = FullMath::checked_mul_div(
+        fee_growth_inside_x128.wrapping_sub(fee_growth_inside_last_x128),
+        Uint256::from(liquidity),
+        FixedPoint128::Q128,
+    )?;
+    Ok(Uint128::try_from(fees).unwrap_or(Uint128::MAX))
+}
+
+/// Keeps the low 128 bits of a u256, matching a uint128(...) downcast
+fn truncate_to_u128(x: Uint256) -> u128 {
+    let bytes = x.to_be_bytes();
+    let mut low = [0u8; 16];
+    low.copy_from_slice(&bytes[16..]);
+    u128::from_be_bytes(low)
+}
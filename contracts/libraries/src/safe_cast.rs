@@ -0,0 +1,43 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Library for safe casting methods
+
+use cosmwasm_std::{Int256, Uint256};
+
+/// The largest value representable in 160 bits, i.e. type(uint160).max
+pub const MAX_U160: Uint256 = Uint256::from_be_bytes([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, //
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, //
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+]);
+
+/// Cast a u256 to a u160, panic on overflow
+pub fn to_u160(y: Uint256) -> Uint256 {
+    assert!(y <= MAX_U160, "SafeCast: value doesn't fit in 160 bits");
+    y
+}
+
+/// Cast an i256 to an i128, panic on overflow or underflow
+pub fn to_i128(y: Int256) -> i128 {
+    assert!(
+        y >= Int256::from(i128::MIN) && y <= Int256::from(i128::MAX),
+        "SafeCast: value doesn't fit in 128 bits"
+    );
+    let bytes = y.to_be_bytes();
+    let mut low = [0u8; 16];
+    low.copy_from_slice(&bytes[16..]);
+    i128::from_be_bytes(low)
+}
+
+/// Cast a u256 to an i256, panic on overflow
+pub fn to_i256(y: Uint256) -> Int256 {
+    let bytes = y.to_be_bytes();
+    assert!(bytes[0] < 0x80, "SafeCast: value doesn't fit in an int256");
+    Int256::from_be_bytes(bytes)
+}
+
+/// Cast a non-negative i256 to a u256, panic on negative input
+pub fn to_u256(y: Int256) -> Uint256 {
+    assert!(y >= Int256::zero(), "SafeCast: value must be non-negative");
+    Uint256::from_be_bytes(y.to_be_bytes())
+}
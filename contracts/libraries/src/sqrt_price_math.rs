@@ -0,0 +1,355 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+use cosmwasm_std::{Int256, Uint256};
+
+use crate::fixed_point_96::FixedPoint96;
+use crate::full_math::FullMath;
+use crate::safe_cast::{to_i256, to_u160, MAX_U160};
+use crate::types::{u160, u256};
+use crate::unsafe_math::div_rounding_up;
+
+/// Gets the next sqrt price given a delta of token0
+/// Always rounds up, because in the exact output case (increasing price) we need to move the price at least
+/// far enough to get the desired output amount, and in the exact input case (decreasing price) we need to move the
+/// price less in order to not send too much output.
+/// The most precise formula for this is liquidity * sqrtPX96 / (liquidity +- amount * sqrtPX96),
+/// if this is impossible because of overflow, we calculate liquidity / (liquidity / sqrtPX96 +- amount).
+/// Returns the price after adding or removing amount, depending on add
+pub fn get_next_sqrt_price_from_amount0_rounding_up(
+    sqrt_px96: u160,
+    liquidity: u128,
+    amount: u256,
+    add: bool,
+) -> u160 {
+    // Short circuit amount == 0 because the result is otherwise not guaranteed to equal the input price
+    if amount.is_zero() {
+        return sqrt_px96;
+    }
+
+    let numerator1 = Uint256::from(liquidity) << FixedPoint96::RESOLUTION;
+
+    if add {
+        if let Ok(product) = amount.checked_mul(sqrt_px96) {
+            if let Ok(denominator) = numerator1.checked_add(product) {
+                // Always fits in 160 bits
+                return FullMath::mul_div_rounding_up(numerator1, sqrt_px96, denominator);
+            }
+        }
+        div_rounding_up(
+            numerator1,
+            (numerator1 / sqrt_px96)
+                .checked_add(amount)
+                .expect("Denominator overflow"),
+        )
+    } else {
+        // If the product overflows, we know the denominator underflows
+        // In addition, we must check that the denominator does not underflow
+        let product = amount.checked_mul(sqrt_px96).expect("Product overflow");
+        assert!(numerator1 > product, "Denominator underflow");
+        let denominator = numerator1 - product;
+        to_u160(FullMath::mul_div_rounding_up(
+            numerator1,
+            sqrt_px96,
+            denominator,
+        ))
+    }
+}
+
+/// Gets the next sqrt price given a delta of token1
+/// Always rounds down, because in the exact output case (decreasing price) we need to move the price at least
+/// far enough to get the desired output amount, and in the exact input case (increasing price) we need to move the
+/// price less in order to not send too much output.
+/// The formula we compute is within <1 wei of the lossless version: sqrtPX96 +- amount / liquidity
+/// Returns the price after adding or removing amount
+pub fn get_next_sqrt_price_from_amount1_rounding_down(
+    sqrt_px96: u160,
+    liquidity: u128,
+    amount: u256,
+    add: bool,
+) -> u160 {
+    let liquidity = Uint256::from(liquidity);
+
+    // If we're adding (subtracting), rounding down requires rounding the quotient down (up)
+    // In both cases, avoid a mulDiv for most inputs
+    if add {
+        let quotient = if amount <= MAX_U160 {
+            (amount << FixedPoint96::RESOLUTION) / liquidity
+        } else {
+            FullMath::mul_div(amount, FixedPoint96::Q96, liquidity)
+        };
+        to_u160(sqrt_px96.checked_add(quotient).expect("Price overflow"))
+    } else {
+        let quotient = if amount <= MAX_U160 {
+            div_rounding_up(amount << FixedPoint96::RESOLUTION, liquidity)
+        } else {
+            FullMath::mul_div_rounding_up(amount, FixedPoint96::Q96, liquidity)
+        };
+        assert!(sqrt_px96 > quotient, "Price underflow");
+        // Always fits 160 bits
+        sqrt_px96 - quotient
+    }
+}
+
+/// Gets the next sqrt price given an input amount of token0 or token1
+/// Throws if price or liquidity are 0, or if the next price is out of bounds
+/// Returns the price after adding the input amount to token0 or token1
+pub fn get_next_sqrt_price_from_input(
+    sqrt_px96: u160,
+    liquidity: u128,
+    amount_in: u256,
+    zero_for_one: bool,
+) -> u160 {
+    assert!(!sqrt_px96.is_zero(), "Price must be greater than 0");
+    assert!(liquidity > 0, "Liquidity must be greater than 0");
+
+    // Round to make sure that we don't pass the target price
+    if zero_for_one {
+        get_next_sqrt_price_from_amount0_rounding_up(sqrt_px96, liquidity, amount_in, true)
+    } else {
+        get_next_sqrt_price_from_amount1_rounding_down(sqrt_px96, liquidity, amount_in, true)
+    }
+}
+
+/// Gets the next sqrt price given an output amount of token0 or token1
+/// Throws if price or liquidity are 0 or the next price is out of bounds
+/// Returns the price after removing the output amount of token0 or token1
+pub fn get_next_sqrt_price_from_output(
+    sqrt_px96: u160,
+    liquidity: u128,
+    amount_out: u256,
+    zero_for_one: bool,
+) -> u160 {
+    assert!(!sqrt_px96.is_zero(), "Price must be greater than 0");
+    assert!(liquidity > 0, "Liquidity must be greater than 0");
+
+    // Round to make sure that we pass the target price
+    if zero_for_one {
+        get_next_sqrt_price_from_amount1_rounding_down(sqrt_px96, liquidity, amount_out, false)
+    } else {
+        get_next_sqrt_price_from_amount0_rounding_up(sqrt_px96, liquidity, amount_out, false)
+    }
+}
+
+/// Gets the amount0 delta between two prices
+/// Calculates liquidity / sqrt(lower) - liquidity / sqrt(upper),
+/// i.e., liquidity * (sqrt(upper) - sqrt(lower)) / (sqrt(upper) * sqrt(lower))
+/// Returns amount0 required to cover a position of size liquidity between the two passed prices
+pub fn get_amount0_delta(
+    sqrt_ratio_a_x96: u160,
+    sqrt_ratio_b_x96: u160,
+    liquidity: u128,
+    round_up: bool,
+) -> u256 {
+    let (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = if sqrt_ratio_a_x96 > sqrt_ratio_b_x96 {
+        (sqrt_ratio_b_x96, sqrt_ratio_a_x96)
+    } else {
+        (sqrt_ratio_a_x96, sqrt_ratio_b_x96)
+    };
+
+    assert!(!sqrt_ratio_a_x96.is_zero(), "Price must be greater than 0");
+
+    let numerator1 = Uint256::from(liquidity) << FixedPoint96::RESOLUTION;
+    let numerator2 = sqrt_ratio_b_x96 - sqrt_ratio_a_x96;
+
+    if round_up {
+        div_rounding_up(
+            FullMath::mul_div_rounding_up(numerator1, numerator2, sqrt_ratio_b_x96),
+            sqrt_ratio_a_x96,
+        )
+    } else {
+        FullMath::mul_div(numerator1, numerator2, sqrt_ratio_b_x96) / sqrt_ratio_a_x96
+    }
+}
+
+/// Gets the amount1 delta between two prices
+/// Calculates liquidity * (sqrt(upper) - sqrt(lower))
+/// Returns amount1 required to cover a position of size liquidity between the two passed prices
+pub fn get_amount1_delta(
+    sqrt_ratio_a_x96: u160,
+    sqrt_ratio_b_x96: u160,
+    liquidity: u128,
+    round_up: bool,
+) -> u256 {
+    let (sqrt_ratio_a_x96, sqrt_ratio_b_x96) = if sqrt_ratio_a_x96 > sqrt_ratio_b_x96 {
+        (sqrt_ratio_b_x96, sqrt_ratio_a_x96)
+    } else {
+        (sqrt_ratio_a_x96, sqrt_ratio_b_x96)
+    };
+
+    let numerator = sqrt_ratio_b_x96 - sqrt_ratio_a_x96;
+
+    if round_up {
+        FullMath::mul_div_rounding_up(Uint256::from(liquidity), numerator, FixedPoint96::Q96)
+    } else {
+        FullMath::mul_div(Uint256::from(liquidity), numerator, FixedPoint96::Q96)
+    }
+}
+
+/// Helper that gets signed token0 delta
+/// Returns amount0 corresponding to the passed liquidity_delta between the two prices
+pub fn get_amount0_delta_signed(
+    sqrt_ratio_a_x96: u160,
+    sqrt_ratio_b_x96: u160,
+    liquidity_delta: i128,
+) -> Int256 {
+    if liquidity_delta < 0 {
+        -to_i256(get_amount0_delta(
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            liquidity_delta.unsigned_abs(),
+            false,
+        ))
+    } else {
+        to_i256(get_amount0_delta(
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            liquidity_delta as u128,
+            true,
+        ))
+    }
+}
+
+/// Helper that gets signed token1 delta
+/// Returns amount1 corresponding to the passed liquidity_delta between the two prices
+pub fn get_amount1_delta_signed(
+    sqrt_ratio_a_x96: u160,
+    sqrt_ratio_b_x96: u160,
+    liquidity_delta: i128,
+) -> Int256 {
+    if liquidity_delta < 0 {
+        -to_i256(get_amount1_delta(
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            liquidity_delta.unsigned_abs(),
+            false,
+        ))
+    } else {
+        to_i256(get_amount1_delta(
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            liquidity_delta as u128,
+            true,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::test_support::sqrt_price;
+    use crate::tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO};
+
+    proptest! {
+        #[test]
+        fn amount_deltas_round_up_by_at_most_one(
+            a in sqrt_price(),
+            b in sqrt_price(),
+            liquidity in any::<u128>(),
+        ) {
+            for delta in [get_amount0_delta, get_amount1_delta] {
+                let down = delta(a, b, liquidity, false);
+                let up = delta(a, b, liquidity, true);
+                prop_assert!(down <= up && up <= down + Uint256::one());
+                prop_assert_eq!(up, delta(b, a, liquidity, true));
+            }
+        }
+
+        #[test]
+        fn amount_deltas_are_monotonic_in_liquidity(
+            a in sqrt_price(),
+            b in sqrt_price(),
+            liquidity in any::<u128>(),
+            more in any::<u128>(),
+            round_up in any::<bool>(),
+        ) {
+            let more = liquidity.saturating_add(more);
+            for delta in [get_amount0_delta, get_amount1_delta] {
+                prop_assert!(delta(a, b, liquidity, round_up) <= delta(a, b, more, round_up));
+            }
+        }
+
+        #[test]
+        fn signed_deltas_round_in_favour_of_the_pool(
+            a in sqrt_price(),
+            b in sqrt_price(),
+            liquidity in 0..=i128::MAX,
+        ) {
+            // minting pays the rounded up amount in, burning takes the rounded down amount out
+            let liquidity_abs = liquidity as u128;
+            prop_assert_eq!(
+                get_amount0_delta_signed(a, b, liquidity),
+                to_i256(get_amount0_delta(a, b, liquidity_abs, true))
+            );
+            prop_assert_eq!(
+                get_amount0_delta_signed(a, b, -liquidity),
+                -to_i256(get_amount0_delta(a, b, liquidity_abs, false))
+            );
+            prop_assert_eq!(
+                get_amount1_delta_signed(a, b, liquidity),
+                to_i256(get_amount1_delta(a, b, liquidity_abs, true))
+            );
+            prop_assert_eq!(
+                get_amount1_delta_signed(a, b, -liquidity),
+                -to_i256(get_amount1_delta(a, b, liquidity_abs, false))
+            );
+        }
+
+        #[test]
+        fn next_price_from_input_never_gives_more_than_was_paid(
+            sqrt_price in sqrt_price(),
+            liquidity in 1..=u128::MAX,
+            amount_in in any::<u128>(),
+            zero_for_one in any::<bool>(),
+        ) {
+            let amount_in = if zero_for_one {
+                Uint256::from(amount_in)
+            } else {
+                // keep the price below the maximum
+                let room = get_amount1_delta(sqrt_price, MAX_SQRT_RATIO, liquidity, false);
+                prop_assume!(!room.is_zero());
+                Uint256::from(amount_in) % room
+            };
+
+            let next = get_next_sqrt_price_from_input(sqrt_price, liquidity, amount_in, zero_for_one);
+
+            // the price moves in the swap's direction, and only as far as the input pays for
+            if zero_for_one {
+                prop_assert!(next <= sqrt_price && !next.is_zero());
+                prop_assert!(get_amount0_delta(next, sqrt_price, liquidity, true) <= amount_in);
+            } else {
+                prop_assert!(next >= sqrt_price && next <= MAX_U160);
+                prop_assert!(get_amount1_delta(sqrt_price, next, liquidity, true) <= amount_in);
+            }
+        }
+
+        #[test]
+        fn next_price_from_output_always_covers_what_is_taken(
+            sqrt_price in sqrt_price(),
+            liquidity in 1..=u128::MAX,
+            amount_out in any::<u128>(),
+            zero_for_one in any::<bool>(),
+        ) {
+            // keep the output below what the pool holds over the whole price range
+            let room = if zero_for_one {
+                get_amount1_delta(MIN_SQRT_RATIO, sqrt_price, liquidity, false)
+            } else {
+                get_amount0_delta(sqrt_price, MAX_SQRT_RATIO, liquidity, false)
+            };
+            prop_assume!(!room.is_zero());
+            let amount_out = Uint256::from(amount_out) % room;
+
+            let next = get_next_sqrt_price_from_output(sqrt_price, liquidity, amount_out, zero_for_one);
+
+            // the price moves in the swap's direction, at least as far as the output requires
+            if zero_for_one {
+                prop_assert!(next <= sqrt_price);
+                prop_assert!(get_amount1_delta(next, sqrt_price, liquidity, false) >= amount_out);
+            } else {
+                prop_assert!(next >= sqrt_price);
+                prop_assert!(get_amount0_delta(sqrt_price, next, liquidity, false) >= amount_out);
+            }
+        }
+    }
+}
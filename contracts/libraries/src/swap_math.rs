@@ -0,0 +1,232 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+use cosmwasm_std::{Int256, Uint256};
+
+use crate::full_math::FullMath;
+use crate::safe_cast::to_u256;
+use crate::sqrt_price_math::{
+    get_amount0_delta, get_amount1_delta, get_next_sqrt_price_from_input,
+    get_next_sqrt_price_from_output,
+};
+use crate::types::{u160, u256};
+
+/// The denominator of `fee_pips`, i.e. fees are expressed in hundredths of a bip
+const FEE_DENOMINATOR: u32 = 1_000_000;
+
+/// Computes the result of swapping some amount in, or amount out, given the parameters of the swap
+/// The fee, plus the amount in, will never exceed the amount remaining if the swap's `amount_specified` is positive
+/// Returns the price after swapping the amount in/out, not to exceed the price target,
+/// the amount to be swapped in, the amount to be received and the amount of input taken as a fee
+pub fn compute_swap_step(
+    sqrt_ratio_current_x96: u160,
+    sqrt_ratio_target_x96: u160,
+    liquidity: u128,
+    amount_remaining: Int256,
+    fee_pips: u32,
+) -> (u160, u256, u256, u256) {
+    let zero_for_one = sqrt_ratio_current_x96 >= sqrt_ratio_target_x96;
+    let exact_in = amount_remaining >= Int256::zero();
+
+    let mut amount_in = Uint256::zero();
+    let mut amount_out = Uint256::zero();
+    let sqrt_ratio_next_x96;
+
+    if exact_in {
+        let amount_remaining_less_fee = FullMath::mul_div(
+            to_u256(amount_remaining),
+            Uint256::from(FEE_DENOMINATOR - fee_pips),
+            Uint256::from(FEE_DENOMINATOR),
+        );
+
+        amount_in = if zero_for_one {
+            get_amount0_delta(
+                sqrt_ratio_target_x96,
+                sqrt_ratio_current_x96,
+                liquidity,
+                true,
+            )
+        } else {
+            get_amount1_delta(
+                sqrt_ratio_current_x96,
+                sqrt_ratio_target_x96,
+                liquidity,
+                true,
+            )
+        };
+
+        sqrt_ratio_next_x96 = if amount_remaining_less_fee >= amount_in {
+            sqrt_ratio_target_x96
+        } else {
+            get_next_sqrt_price_from_input(
+                sqrt_ratio_current_x96,
+                liquidity,
+                amount_remaining_less_fee,
+                zero_for_one,
+            )
+        };
+    } else {
+        amount_out = if zero_for_one {
+            get_amount1_delta(
+                sqrt_ratio_target_x96,
+                sqrt_ratio_current_x96,
+                liquidity,
+                false,
+            )
+        } else {
+            get_amount0_delta(
+                sqrt_ratio_current_x96,
+                sqrt_ratio_target_x96,
+                liquidity,
+                false,
+            )
+        };
+
+        sqrt_ratio_next_x96 = if to_u256(-amount_remaining) >= amount_out {
+            sqrt_ratio_target_x96
+        } else {
+            get_next_sqrt_price_from_output(
+                sqrt_ratio_current_x96,
+                liquidity,
+                to_u256(-amount_remaining),
+                zero_for_one,
+            )
+        };
+    }
+
+    let max = sqrt_ratio_target_x96 == sqrt_ratio_next_x96;
+
+    // Calculate input/output amounts
+    if zero_for_one {
+        if !(max && exact_in) {
+            amount_in =
+                get_amount0_delta(sqrt_ratio_next_x96, sqrt_ratio_current_x96, liquidity, true);
+        }
+        if !max || exact_in {
+            amount_out = get_amount1_delta(
+                sqrt_ratio_next_x96,
+                sqrt_ratio_current_x96,
+                liquidity,
+                false,
+            );
+        }
+    } else {
+        if !(max && exact_in) {
+            amount_in =
+                get_amount1_delta(sqrt_ratio_current_x96, sqrt_ratio_next_x96, liquidity, true);
+        }
+        if !max || exact_in {
+            amount_out = get_amount0_delta(
+                sqrt_ratio_current_x96,
+                sqrt_ratio_next_x96,
+                liquidity,
+                false,
+            );
+        }
+    }
+
+    // Cap the output amount to not exceed the remaining output amount
+    if !exact_in && amount_out > to_u256(-amount_remaining) {
+        amount_out = to_u256(-amount_remaining);
+    }
+
+    let fee_amount = if exact_in && sqrt_ratio_next_x96 != sqrt_ratio_target_x96 {
+        // Didn't reach the target, so take the remainder of the maximum input as fee
+        to_u256(amount_remaining) - amount_in
+    } else {
+        FullMath::mul_div_rounding_up(
+            amount_in,
+            Uint256::from(fee_pips),
+            Uint256::from(FEE_DENOMINATOR - fee_pips),
+        )
+    };
+
+    (sqrt_ratio_next_x96, amount_in, amount_out, fee_amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::test_support::sqrt_price;
+
+    proptest! {
+        #[test]
+        fn swap_step_invariants(
+            sqrt_price in sqrt_price(),
+            target in sqrt_price(),
+            liquidity in any::<u128>(),
+            amount_remaining in any::<i128>().prop_filter("non-zero", |amount| *amount != 0),
+            fee_pips in 0..FEE_DENOMINATOR,
+        ) {
+            let amount_remaining = Int256::from(amount_remaining);
+            let (next, amount_in, amount_out, fee_amount) =
+                compute_swap_step(sqrt_price, target, liquidity, amount_remaining, fee_pips);
+
+            // the price moves towards the target without crossing it
+            let zero_for_one = sqrt_price >= target;
+            if zero_for_one {
+                prop_assert!(target <= next && next <= sqrt_price);
+            } else {
+                prop_assert!(sqrt_price <= next && next <= target);
+            }
+
+            if amount_remaining > Int256::zero() {
+                let amount_remaining = to_u256(amount_remaining);
+                prop_assert!(amount_in + fee_amount <= amount_remaining);
+                // a step that stops short of the target consumes the whole input
+                if next != target {
+                    prop_assert_eq!(amount_in + fee_amount, amount_remaining);
+                }
+            } else {
+                let amount_remaining = to_u256(-amount_remaining);
+                prop_assert!(amount_out <= amount_remaining);
+                // a step that stops short of the target delivers the whole output
+                if next != target {
+                    prop_assert_eq!(amount_out, amount_remaining);
+                }
+            }
+
+            // rounding favours the pool: the input covers the price move, the output never exceeds it
+            let (max_in, max_out) = if zero_for_one {
+                (
+                    get_amount0_delta(next, sqrt_price, liquidity, true),
+                    get_amount1_delta(next, sqrt_price, liquidity, false),
+                )
+            } else {
+                (
+                    get_amount1_delta(sqrt_price, next, liquidity, true),
+                    get_amount0_delta(sqrt_price, next, liquidity, false),
+                )
+            };
+            prop_assert_eq!(amount_in, max_in);
+            prop_assert!(amount_out <= max_out);
+            if fee_pips > 0 && !amount_in.is_zero() {
+                prop_assert!(!fee_amount.is_zero());
+            }
+        }
+
+        #[test]
+        fn swap_step_output_is_monotonic_in_liquidity(
+            sqrt_price in sqrt_price(),
+            target in sqrt_price(),
+            liquidity in any::<u128>(),
+            more in any::<u128>(),
+            amount_remaining in any::<i128>().prop_filter("non-zero", |amount| *amount != 0),
+            fee_pips in 0..FEE_DENOMINATOR,
+        ) {
+            // deeper liquidity never moves the price further for the same amount
+            let amount_remaining = Int256::from(amount_remaining);
+            let more = liquidity.saturating_add(more);
+            let (next, ..) =
+                compute_swap_step(sqrt_price, target, liquidity, amount_remaining, fee_pips);
+            let (next_deeper, ..) =
+                compute_swap_step(sqrt_price, target, more, amount_remaining, fee_pips);
+            if sqrt_price >= target {
+                prop_assert!(next_deeper >= next);
+            } else {
+                prop_assert!(next_deeper <= next);
+            }
+        }
+    }
+}
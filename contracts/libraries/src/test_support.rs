@@ -0,0 +1,13 @@
+//! Strategies shared by the libraries' property tests
+
+use cosmwasm_std::Uint256;
+use proptest::prelude::*;
+
+use crate::tick_math::{get_sqrt_ratio_at_tick, MAX_TICK, MIN_TICK};
+use crate::types::u160;
+
+/// A valid sqrt price, spread evenly over the ticks rather than the raw price range
+pub fn sqrt_price() -> impl Strategy<Value = u160> {
+    (MIN_TICK..MAX_TICK, any::<u32>())
+        .prop_map(|(tick, offset)| get_sqrt_ratio_at_tick(tick) + Uint256::from(offset))
+}
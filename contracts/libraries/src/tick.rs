@@ -0,0 +1,516 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Tick state
+//! Holds the state of each initialized tick. `Ticks` keeps the ticks in contract storage; the free functions update
+//! a single `TickInfo` that the caller loads and stores itself.
+
+use cosmwasm_std::{Int128, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::Map;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::tick_math::{MAX_TICK, MIN_TICK};
+use crate::types::{i24, i56, u160, u256};
+
+/// Represents tick information
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct TickInfo {
+    /// the total position liquidity that references this tick
+    pub liquidity_gross: Uint128,
+    /// amount of net liquidity added (subtracted) when tick is crossed from left to right (right to left),
+    pub liquidity_net: Int128,
+    /// fee growth per unit of liquidity on the _other_ side of this tick (relative to the current tick)
+    pub fee_growth_outside0_x128: u256,
+    pub fee_growth_outside1_x128: u256,
+    /// the cumulative tick value on the other side of the tick
+    pub tick_cumulative_outside: i56,
+    /// the seconds per unit of liquidity on the _other_ side of this tick (relative to the current tick)
+    pub seconds_per_liquidity_outside_x128: u160,
+    /// the seconds spent on the other side of the tick (relative to the current tick)
+    pub seconds_outside: u32,
+    /// true iff the tick is initialized, i.e. the value is exactly equivalent to the expression liquidity_gross != 0
+    /// `update_tick` keeps it in sync with liquidity_gross, including for ticks that still need to be cleared
+    pub initialized: bool,
+}
+
+impl TickInfo {
+    /// Creates a new tick info instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TickError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Liquidity of tick {tick} would exceed the maximum of {max_liquidity} per tick")]
+    LiquidityOverflow { tick: i24, max_liquidity: Uint128 },
+
+    #[error("Liquidity of tick {tick} would fall below zero from {liquidity_gross}")]
+    LiquidityUnderflow { tick: i24, liquidity_gross: Uint128 },
+
+    #[error("Net liquidity of tick {tick} would overflow")]
+    LiquidityNetOverflow { tick: i24 },
+
+    #[error("Tick {tick} is not initialized")]
+    NotInitialized { tick: i24 },
+}
+
+/// The ticks of a pool, stored as one `Map` entry per initialized tick
+/// A tick is only stored while some position references it; `clear` removes it once its liquidity_gross is back to 0
+pub struct Ticks<'a> {
+    ticks: Map<'a, i24, TickInfo>,
+}
+
+impl<'a> Ticks<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        Ticks {
+            ticks: Map::new(namespace),
+        }
+    }
+
+    /// Loads a tick, treating a tick that isn't stored as uninitialized
+    pub fn info(&self, storage: &dyn Storage, tick: i24) -> StdResult<TickInfo> {
+        Ok(self.ticks.may_load(storage, tick)?.unwrap_or_default())
+    }
+
+    /// Loads a tick, failing unless it is initialized
+    pub fn load_initialized(
+        &self,
+        storage: &dyn Storage,
+        tick: i24,
+    ) -> Result<TickInfo, TickError> {
+        match self.ticks.may_load(storage, tick)? {
+            Some(info) if info.initialized => Ok(info),
+            _ => Err(TickError::NotInitialized { tick }),
+        }
+    }
+
+    /// Updates a stored tick with `update_tick`, initializing it if needed
+    /// Returns true if the tick was flipped from initialized to uninitialized, or vice versa. A tick flipped to
+    /// uninitialized is still stored, so that fee growth inside the position can be computed, and must then be
+    /// removed with `clear`
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &self,
+        storage: &mut dyn Storage,
+        tick: i24,
+        tick_current: i24,
+        liquidity_delta: i128,
+        fee_growth_global0_x128: u256,
+        fee_growth_global1_x128: u256,
+        seconds_per_liquidity_cumulative_x128: u160,
+        tick_cumulative: i56,
+        time: u32,
+        upper: bool,
+        max_liquidity: u128,
+    ) -> Result<bool, TickError> {
+        let mut info = self.info(storage, tick)?;
+        let flipped = update_tick(
+            &mut info,
+            tick,
+            tick_current,
+            liquidity_delta,
+            fee_growth_global0_x128,
+            fee_growth_global1_x128,
+            seconds_per_liquidity_cumulative_x128,
+            tick_cumulative,
+            time,
+            upper,
+            max_liquidity,
+        )?;
+        self.ticks.save(storage, tick, &info)?;
+        Ok(flipped)
+    }
+
+    /// Removes a tick that is no longer referenced by any position
+    pub fn clear(&self, storage: &mut dyn Storage, tick: i24) {
+        self.ticks.remove(storage, tick);
+    }
+
+    /// Crosses a stored tick with `cross_tick`, failing if the tick is not initialized
+    /// Returns the amount of liquidity added (subtracted) when the tick is crossed from left to right (right to left)
+    #[allow(clippy::too_many_arguments)]
+    pub fn cross(
+        &self,
+        storage: &mut dyn Storage,
+        tick: i24,
+        fee_growth_global0_x128: u256,
+        fee_growth_global1_x128: u256,
+        seconds_per_liquidity_cumulative_x128: u160,
+        tick_cumulative: i56,
+        time: u32,
+    ) -> Result<i128, TickError> {
+        let mut info = self.load_initialized(storage, tick)?;
+        let liquidity_net = cross_tick(
+            &mut info,
+            fee_growth_global0_x128,
+            fee_growth_global1_x128,
+            seconds_per_liquidity_cumulative_x128,
+            tick_cumulative,
+            time,
+        );
+        self.ticks.save(storage, tick, &info)?;
+        Ok(liquidity_net)
+    }
+}
+
+/// Derives max liquidity per tick from given tick spacing
+/// @param tick_spacing The amount of required tick separation, realized in multiples of `tick_spacing`
+/// e.g., a tick_spacing of 3 requires ticks to be initialized every 3rd tick i.e., ..., -6, -3, 0, 3, 6, ...
+/// @return The max liquidity per tick
+pub fn tick_spacing_to_max_liquidity_per_tick(tick_spacing: i24) -> u128 {
+    let min_tick = (MIN_TICK / tick_spacing) * tick_spacing;
+    let max_tick = (MAX_TICK / tick_spacing) * tick_spacing;
+    let num_ticks = ((max_tick - min_tick) / tick_spacing) as u128 + 1;
+    u128::MAX / num_ticks
+}
+
+/// Retrieves fee growth data
+/// @param lower The tick information of the lower tick boundary of the position
+/// @param upper The tick information of the upper tick boundary of the position
+/// @param tick_lower The lower tick boundary of the position
+/// @param tick_upper The upper tick boundary of the position
+/// @param tick_current The current tick
+/// @param fee_growth_global0_x128 The all-time global fee growth, per unit of liquidity, in token0
+/// @param fee_growth_global1_x128 The all-time global fee growth, per unit of liquidity, in token1
+/// @return fee_growth_inside0_x128 The all-time fee growth in token0, per unit of liquidity, inside the position's tick boundaries
+/// @return fee_growth_inside1_x128 The all-time fee growth in token1, per unit of liquidity, inside the position's tick boundaries
+pub fn get_fee_growth_inside(
+    lower: &TickInfo,
+    upper: &TickInfo,
+    tick_lower: i24,
+    tick_upper: i24,
+    tick_current: i24,
+    fee_growth_global0_x128: u256,
+    fee_growth_global1_x128: u256,
+) -> (u256, u256) {
+    // Calculate fee growth below
+    let (fee_growth_below0_x128, fee_growth_below1_x128) = if tick_current >= tick_lower {
+        (
+            lower.fee_growth_outside0_x128,
+            lower.fee_growth_outside1_x128,
+        )
+    } else {
+        (
+            fee_growth_global0_x128.wrapping_sub(lower.fee_growth_outside0_x128),
+            fee_growth_global1_x128.wrapping_sub(lower.fee_growth_outside1_x128),
+        )
+    };
+
+    // Calculate fee growth above
+    let (fee_growth_above0_x128, fee_growth_above1_x128) = if tick_current < tick_upper {
+        (
+            upper.fee_growth_outside0_x128,
+            upper.fee_growth_outside1_x128,
+        )
+    } else {
+        (
+            fee_growth_global0_x128.wrapping_sub(upper.fee_growth_outside0_x128),
+            fee_growth_global1_x128.wrapping_sub(upper.fee_growth_outside1_x128),
+        )
+    };
+
+    (
+        fee_growth_global0_x128
+            .wrapping_sub(fee_growth_below0_x128)
+            .wrapping_sub(fee_growth_above0_x128),
+        fee_growth_global1_x128
+            .wrapping_sub(fee_growth_below1_x128)
+            .wrapping_sub(fee_growth_above1_x128),
+    )
+}
+
+/// Updates a tick and returns true if the tick was flipped from initialized to uninitialized, or vice versa
+/// @param info The tick information of the tick that will be updated
+/// @param tick The tick that will be updated
+/// @param tick_current The current tick
+/// @param liquidity_delta A new amount of liquidity to be added (subtracted) when tick is crossed from left to right (right to left)
+/// @param fee_growth_global0_x128 The all-time global fee growth, per unit of liquidity, in token0
+/// @param fee_growth_global1_x128 The all-time global fee growth, per unit of liquidity, in token1
+/// @param seconds_per_liquidity_cumulative_x128 The all-time seconds per max(1, liquidity) of the pool
+/// @param tick_cumulative The tick * time elapsed since the pool was first initialized
+/// @param time The current block timestamp
+/// @param upper true for updating a position's upper tick, or false for updating a position's lower tick
+/// @param max_liquidity The maximum liquidity allocation for a single tick
+/// @return flipped Whether the tick was flipped from initialized to uninitialized, or vice versa
+/// Fails with `LiquidityOverflow` if the tick's liquidity_gross would exceed max_liquidity, `LiquidityUnderflow` if it
+/// would fall below zero, and `LiquidityNetOverflow` if its liquidity_net would overflow
+#[allow(clippy::too_many_arguments)]
+pub fn update_tick(
+    info: &mut TickInfo,
+    tick: i24,
+    tick_current: i24,
+    liquidity_delta: i128,
+    fee_growth_global0_x128: u256,
+    fee_growth_global1_x128: u256,
+    seconds_per_liquidity_cumulative_x128: u160,
+    tick_cumulative: i56,
+    time: u32,
+    upper: bool,
+    max_liquidity: u128,
+) -> Result<bool, TickError> {
+    let liquidity_gross_before = info.liquidity_gross.u128();
+    let liquidity_gross_after = if liquidity_delta < 0 {
+        liquidity_gross_before
+            .checked_sub(liquidity_delta.unsigned_abs())
+            .ok_or(TickError::LiquidityUnderflow {
+                tick,
+                liquidity_gross: info.liquidity_gross,
+            })?
+    } else {
+        liquidity_gross_before.saturating_add(liquidity_delta as u128)
+    };
+
+    if liquidity_gross_after > max_liquidity {
+        return Err(TickError::LiquidityOverflow {
+            tick,
+            max_liquidity: Uint128::new(max_liquidity),
+        });
+    }
+
+    // When the lower (upper) tick is crossed left to right (right to left), liquidity must be added (removed)
+    let liquidity_net = info.liquidity_net.i128();
+    let liquidity_net = if upper {
+        liquidity_net.checked_sub(liquidity_delta)
+    } else {
+        liquidity_net.checked_add(liquidity_delta)
+    }
+    .ok_or(TickError::LiquidityNetOverflow { tick })?;
+
+    let flipped = (liquidity_gross_after == 0) != (liquidity_gross_before == 0);
+
+    if liquidity_gross_before == 0 {
+        // By convention, we assume that all growth before a tick was initialized happened _below_ the tick
+        if tick <= tick_current {
+            info.fee_growth_outside0_x128 = fee_growth_global0_x128;
+            info.fee_growth_outside1_x128 = fee_growth_global1_x128;
+            info.seconds_per_liquidity_outside_x128 = seconds_per_liquidity_cumulative_x128;
+            info.tick_cumulative_outside = tick_cumulative;
+            info.seconds_outside = time;
+        }
+    }
+
+    info.liquidity_gross = Uint128::new(liquidity_gross_after);
+    info.liquidity_net = Int128::new(liquidity_net);
+    info.initialized = liquidity_gross_after != 0;
+
+    Ok(flipped)
+}
+
+/// Transitions to next tick as needed by price movement
+/// @param info The tick information of the destination tick of the transition
+/// @param fee_growth_global0_x128 The all-time global fee growth, per unit of liquidity, in token0
+/// @param fee_growth_global1_x128 The all-time global fee growth, per unit of liquidity, in token1
+/// @param seconds_per_liquidity_cumulative_x128 The current seconds per liquidity
+/// @param tick_cumulative The tick * time elapsed since the pool was first initialized
+/// @param time The current block.timestamp
+/// @return liquidity_net The amount of liquidity added (subtracted) when tick is crossed from left to right (right to left)
+pub fn cross_tick(
+    info: &mut TickInfo,
+    fee_growth_global0_x128: u256,
+    fee_growth_global1_x128: u256,
+    seconds_per_liquidity_cumulative_x128: u160,
+    tick_cumulative: i56,
+    time: u32,
+) -> i128 {
+    info.fee_growth_outside0_x128 =
+        fee_growth_global0_x128.wrapping_sub(info.fee_growth_outside0_x128);
+    info.fee_growth_outside1_x128 =
+        fee_growth_global1_x128.wrapping_sub(info.fee_growth_outside1_x128);
+    info.seconds_per_liquidity_outside_x128 =
+        seconds_per_liquidity_cumulative_x128.wrapping_sub(info.seconds_per_liquidity_outside_x128);
+    info.tick_cumulative_outside = tick_cumulative.wrapping_sub(info.tick_cumulative_outside);
+    info.seconds_outside = time.wrapping_sub(info.seconds_outside);
+
+    info.liquidity_net.i128()
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+
+    use super::*;
+
+    const TICKS: Ticks = Ticks::new("ticks");
+
+    /// Updates a tick with the current tick at 0 and all the global growth values set to `growth`
+    fn update(
+        storage: &mut MockStorage,
+        tick: i24,
+        liquidity_delta: i128,
+        growth: u32,
+        upper: bool,
+        max_liquidity: u128,
+    ) -> Result<bool, TickError> {
+        TICKS.update(
+            storage,
+            tick,
+            0,
+            liquidity_delta,
+            u256::from(growth),
+            u256::from(growth),
+            u160::from(growth),
+            growth as i56,
+            growth,
+            upper,
+            max_liquidity,
+        )
+    }
+
+    #[test]
+    fn max_liquidity_per_tick_matches_uniswap() {
+        assert_eq!(
+            tick_spacing_to_max_liquidity_per_tick(10),
+            1917569901783203986719870431555990
+        );
+        assert_eq!(
+            tick_spacing_to_max_liquidity_per_tick(60),
+            11505743598341114571880798222544994
+        );
+        assert_eq!(
+            tick_spacing_to_max_liquidity_per_tick(200),
+            38350317471085141830651933667504588
+        );
+        assert_eq!(
+            tick_spacing_to_max_liquidity_per_tick(887272),
+            u128::MAX / 3
+        );
+    }
+
+    #[test]
+    fn update_flips_and_tracks_net_liquidity() {
+        let mut storage = MockStorage::new();
+
+        // initializing below the current tick assumes all growth so far happened below it
+        assert_eq!(update(&mut storage, -60, 3, 7, false, 10), Ok(true));
+        assert_eq!(update(&mut storage, 60, 3, 7, true, 10), Ok(true));
+        assert_eq!(update(&mut storage, -60, 2, 9, false, 10), Ok(false));
+
+        let lower = TICKS.info(&storage, -60).unwrap();
+        assert_eq!(lower.liquidity_gross, Uint128::new(5));
+        assert_eq!(lower.liquidity_net, Int128::new(5));
+        assert_eq!(lower.fee_growth_outside0_x128, u256::from(7u32));
+        assert_eq!(lower.seconds_outside, 7);
+        assert!(lower.initialized);
+
+        let upper = TICKS.info(&storage, 60).unwrap();
+        assert_eq!(upper.liquidity_net, Int128::new(-3));
+        assert!(upper.fee_growth_outside0_x128.is_zero());
+        assert_eq!(upper.seconds_outside, 0);
+
+        // flipping back to uninitialized keeps the tick until it is cleared
+        assert_eq!(update(&mut storage, 60, -3, 9, true, 10), Ok(true));
+        let upper = TICKS.info(&storage, 60).unwrap();
+        assert_eq!(upper.liquidity_gross, Uint128::zero());
+        assert!(!upper.initialized);
+        assert_eq!(
+            TICKS.load_initialized(&storage, 60),
+            Err(TickError::NotInitialized { tick: 60 })
+        );
+        TICKS.clear(&mut storage, 60);
+        assert_eq!(TICKS.info(&storage, 60).unwrap(), TickInfo::default());
+        assert_eq!(
+            TICKS.load_initialized(&storage, 60),
+            Err(TickError::NotInitialized { tick: 60 })
+        );
+    }
+
+    #[test]
+    fn update_above_max_liquidity_fails_without_writing() {
+        let mut storage = MockStorage::new();
+        update(&mut storage, 0, 6, 1, false, 10).unwrap();
+
+        assert_eq!(
+            update(&mut storage, 0, 5, 1, false, 10),
+            Err(TickError::LiquidityOverflow {
+                tick: 0,
+                max_liquidity: Uint128::new(10),
+            })
+        );
+        assert_eq!(
+            TICKS.info(&storage, 0).unwrap().liquidity_gross,
+            Uint128::new(6)
+        );
+        assert_eq!(update(&mut storage, 0, 4, 1, false, 10), Ok(false));
+    }
+
+    #[test]
+    fn update_out_of_range_liquidity_fails_without_writing() {
+        let mut storage = MockStorage::new();
+        update(&mut storage, 0, 6, 1, false, u128::MAX).unwrap();
+
+        assert_eq!(
+            update(&mut storage, 0, -7, 1, false, u128::MAX),
+            Err(TickError::LiquidityUnderflow {
+                tick: 0,
+                liquidity_gross: Uint128::new(6),
+            })
+        );
+        assert_eq!(
+            update(&mut storage, 0, i128::MAX, 1, false, u128::MAX),
+            Err(TickError::LiquidityNetOverflow { tick: 0 })
+        );
+        assert_eq!(
+            TICKS.info(&storage, 0).unwrap().liquidity_net,
+            Int128::new(6)
+        );
+
+        // the same delta fits in an upper tick, whose net liquidity goes the other way
+        assert_eq!(
+            update(&mut storage, 0, i128::MAX, 1, true, u128::MAX),
+            Ok(false)
+        );
+        assert_eq!(
+            TICKS.info(&storage, 0).unwrap().liquidity_net,
+            Int128::new(6 - i128::MAX)
+        );
+    }
+
+    #[test]
+    fn cross_flips_outside_values() {
+        let mut storage = MockStorage::new();
+        update(&mut storage, -60, 4, 7, false, 10).unwrap();
+
+        let liquidity_net = TICKS
+            .cross(
+                &mut storage,
+                -60,
+                u256::from(10u32),
+                u256::from(20u32),
+                u160::from(30u32),
+                40,
+                50,
+            )
+            .unwrap();
+        assert_eq!(liquidity_net, 4);
+
+        let info = TICKS.info(&storage, -60).unwrap();
+        assert_eq!(info.fee_growth_outside0_x128, u256::from(3u32));
+        assert_eq!(info.fee_growth_outside1_x128, u256::from(13u32));
+        assert_eq!(info.seconds_per_liquidity_outside_x128, u160::from(23u32));
+        assert_eq!(info.tick_cumulative_outside, 33);
+        assert_eq!(info.seconds_outside, 43);
+    }
+
+    #[test]
+    fn cross_of_a_missing_tick_fails_without_writing() {
+        let mut storage = MockStorage::new();
+        assert_eq!(
+            TICKS.cross(
+                &mut storage,
+                60,
+                u256::one(),
+                u256::one(),
+                u160::one(),
+                1,
+                1
+            ),
+            Err(TickError::NotInitialized { tick: 60 })
+        );
+        assert_eq!(TICKS.ticks.may_load(&storage, 60), Ok(None));
+    }
+}
@@ -0,0 +1,349 @@
+// SPDX-License-Identifier: BUSL-1.1
+
+//! Packed tick initialized state library
+//! Stores a packed mapping of tick index to its initialized state. Each word holds 256 ticks (in units of
+//! tick spacing). `TickBitmap` keeps the words in contract storage; the free functions work on a single word that
+//! the caller loads and stores itself.
+
+use cosmwasm_std::{Order, StdError, StdResult, Storage, Uint256};
+use cw_storage_plus::{Bound, Map};
+
+use crate::bit_math::BitMath;
+use crate::tick_math::{MAX_TICK, MIN_TICK};
+use crate::types::{i24, u256};
+
+/// A tick bitmap stored as one `Map` entry per non-empty word, keyed by word position
+/// Words that become empty are removed, so every stored word has at least one initialized tick
+pub struct TickBitmap<'a> {
+    words: Map<'a, i16, u256>,
+}
+
+impl<'a> TickBitmap<'a> {
+    pub const fn new(namespace: &'a str) -> Self {
+        TickBitmap {
+            words: Map::new(namespace),
+        }
+    }
+
+    /// Loads the word at `word_pos`, which is zero if no tick in it is initialized
+    pub fn word(&self, storage: &dyn Storage, word_pos: i16) -> StdResult<u256> {
+        Ok(self.words.may_load(storage, word_pos)?.unwrap_or_default())
+    }
+
+    /// Flips the initialized state for a given tick from false to true, or vice versa
+    pub fn flip_tick(
+        &self,
+        storage: &mut dyn Storage,
+        tick: i24,
+        tick_spacing: i24,
+    ) -> StdResult<()> {
+        let (word_pos, _) = position(tick / tick_spacing);
+        let word = flip_tick(self.word(storage, word_pos)?, tick, tick_spacing)?;
+        if word.is_zero() {
+            self.words.remove(storage, word_pos);
+            Ok(())
+        } else {
+            self.words.save(storage, word_pos, &word)
+        }
+    }
+
+    /// Returns the next initialized tick contained in the same word (or adjacent word) as the tick that is either
+    /// to the left (less than or equal to) or right (greater than) of the given tick
+    /// Returns the next tick and whether it is initialized; uninitialized results sit at the word boundary
+    pub fn next_initialized_tick_within_one_word(
+        &self,
+        storage: &dyn Storage,
+        tick: i24,
+        tick_spacing: i24,
+        lte: bool,
+    ) -> StdResult<(i24, bool)> {
+        let word = self.word(storage, next_word_position(tick, tick_spacing, lte))?;
+        Ok(next_initialized_tick_within_one_word(
+            word,
+            tick,
+            tick_spacing,
+            lte,
+        ))
+    }
+
+    /// Like `next_initialized_tick_within_one_word`, but keeps searching the following words, up to `max_words`
+    /// words in total and never past the word holding MIN_TICK or MAX_TICK
+    /// Empty words are skipped with a single range read, so the cost doesn't grow with the distance searched
+    /// Returns the next tick and whether it is initialized; uninitialized results sit at the boundary of the last
+    /// word searched, which may lie beyond MIN_TICK or MAX_TICK
+    pub fn next_initialized_tick(
+        &self,
+        storage: &dyn Storage,
+        tick: i24,
+        tick_spacing: i24,
+        lte: bool,
+        max_words: u16,
+    ) -> StdResult<(i24, bool)> {
+        let (next, initialized) =
+            self.next_initialized_tick_within_one_word(storage, tick, tick_spacing, lte)?;
+        let word_pos = next_word_position(tick, tick_spacing, lte) as i32;
+        let words_after = max_words.saturating_sub(1) as i32;
+
+        if lte {
+            let limit = (word_pos - words_after).max(word_bounds(tick_spacing).0 as i32) as i16;
+            if initialized || limit as i32 >= word_pos {
+                return Ok((next, initialized));
+            }
+            let found = self
+                .words
+                .range(
+                    storage,
+                    Some(Bound::inclusive(limit)),
+                    Some(Bound::exclusive(word_pos as i16)),
+                    Order::Descending,
+                )
+                .next()
+                .transpose()?;
+            Ok(match found {
+                Some((pos, word)) => {
+                    let msb = BitMath::most_significant_bit(word)?;
+                    ((pos as i24 * 256 + msb as i24) * tick_spacing, true)
+                }
+                None => (limit as i24 * 256 * tick_spacing, false),
+            })
+        } else {
+            let limit = (word_pos + words_after).min(word_bounds(tick_spacing).1 as i32) as i16;
+            if initialized || limit as i32 <= word_pos {
+                return Ok((next, initialized));
+            }
+            let found = self
+                .words
+                .range(
+                    storage,
+                    Some(Bound::exclusive(word_pos as i16)),
+                    Some(Bound::inclusive(limit)),
+                    Order::Ascending,
+                )
+                .next()
+                .transpose()?;
+            Ok(match found {
+                Some((pos, word)) => {
+                    let lsb = BitMath::least_significant_bit(word)?;
+                    ((pos as i24 * 256 + lsb as i24) * tick_spacing, true)
+                }
+                None => ((limit as i24 * 256 + 255) * tick_spacing, false),
+            })
+        }
+    }
+}
+
+/// The positions of the words holding MIN_TICK and MAX_TICK
+fn word_bounds(tick_spacing: i24) -> (i16, i16) {
+    (
+        position(compress(MIN_TICK, tick_spacing)).0,
+        position(compress(MAX_TICK, tick_spacing)).0,
+    )
+}
+
+/// Computes the position in the mapping where the initialized bit for a tick lives
+/// Returns (word_pos, bit_pos)
+pub fn position(tick: i24) -> (i16, u8) {
+    let word_pos = (tick >> 8) as i16;
+    let bit_pos = (tick & 0xff) as u8;
+    (word_pos, bit_pos)
+}
+
+/// Divides a tick by the tick spacing, rounding towards negative infinity
+pub fn compress(tick: i24, tick_spacing: i24) -> i24 {
+    let compressed = tick / tick_spacing;
+    if tick < 0 && tick % tick_spacing != 0 {
+        compressed - 1
+    } else {
+        compressed
+    }
+}
+
+/// Flips the initialized state for a given tick from false to true, or vice versa
+/// `word` must be the word stored at `position(tick / tick_spacing).0`; the updated word is returned
+/// Fails if the tick is not a multiple of the tick spacing
+pub fn flip_tick(word: u256, tick: i24, tick_spacing: i24) -> StdResult<u256> {
+    if tick % tick_spacing != 0 {
+        return Err(StdError::generic_err(
+            "tick must be a multiple of tick_spacing",
+        ));
+    }
+    let (_, bit_pos) = position(tick / tick_spacing);
+    Ok(BitMath::xor(word, Uint256::one() << bit_pos as u32))
+}
+
+/// Returns the position of the word `next_initialized_tick_within_one_word` must be given for these arguments
+pub fn next_word_position(tick: i24, tick_spacing: i24, lte: bool) -> i16 {
+    let compressed = compress(tick, tick_spacing);
+    if lte {
+        position(compressed).0
+    } else {
+        position(compressed + 1).0
+    }
+}
+
+/// Returns the next initialized tick contained in the same word (or adjacent word) as the tick that is either
+/// to the left (less than or equal to) or right (greater than) of the given tick
+/// `word` must be the word stored at `next_word_position(tick, tick_spacing, lte)`
+/// Returns the next tick and whether it is initialized; uninitialized results sit at the word boundary
+pub fn next_initialized_tick_within_one_word(
+    word: u256,
+    tick: i24,
+    tick_spacing: i24,
+    lte: bool,
+) -> (i24, bool) {
+    let compressed = compress(tick, tick_spacing);
+
+    if lte {
+        let (_, bit_pos) = position(compressed);
+        // all the 1s at or to the right of the current bit_pos
+        let mask = (Uint256::one() << bit_pos as u32) - Uint256::one()
+            + (Uint256::one() << bit_pos as u32);
+        let masked = BitMath::and(word, mask);
+
+        // if there are no initialized ticks to the right of or at the current tick, return rightmost in the word
+        if masked.is_zero() {
+            return ((compressed - bit_pos as i32) * tick_spacing, false);
+        }
+        let msb = BitMath::most_significant_bit(masked).expect("masked is non-zero");
+        ((compressed - (bit_pos - msb) as i32) * tick_spacing, true)
+    } else {
+        // start from the word of the next tick, since the current tick state doesn't matter
+        let (_, bit_pos) = position(compressed + 1);
+        // all the 1s at or to the left of the bit_pos
+        let mask = !((Uint256::one() << bit_pos as u32) - Uint256::one());
+        let masked = BitMath::and(word, mask);
+
+        // if there are no initialized ticks to the left of the current tick, return leftmost in the word
+        if masked.is_zero() {
+            return (
+                (compressed + 1 + (u8::MAX - bit_pos) as i32) * tick_spacing,
+                false,
+            );
+        }
+        let lsb = BitMath::least_significant_bit(masked).expect("masked is non-zero");
+        (
+            (compressed + 1 + (lsb - bit_pos) as i32) * tick_spacing,
+            true,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+
+    use super::*;
+
+    const BITMAP: TickBitmap = TickBitmap::new("tick_bitmap");
+
+    /// A bitmap with spacing 1 and the ticks initialized that the Uniswap TickBitmap tests use
+    fn bitmap(ticks: &[i24]) -> MockStorage {
+        let mut storage = MockStorage::new();
+        for tick in ticks {
+            BITMAP.flip_tick(&mut storage, *tick, 1).unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn flip_tick_sets_and_clears_bits() {
+        let mut storage = bitmap(&[-230, -259, -229, 500, -259, -229, -259]);
+        assert_eq!(BITMAP.word(&storage, -1).unwrap(), Uint256::one() << 26u32);
+        assert_eq!(BITMAP.word(&storage, -2).unwrap(), Uint256::one() << 253u32);
+        assert_eq!(BITMAP.word(&storage, 1).unwrap(), Uint256::one() << 244u32);
+
+        // an emptied word is removed rather than stored as zero
+        BITMAP.flip_tick(&mut storage, 500, 1).unwrap();
+        assert!(BITMAP.words.may_load(&storage, 1).unwrap().is_none());
+        assert!(BITMAP.word(&storage, 0).unwrap().is_zero());
+    }
+
+    #[test]
+    fn flip_tick_rejects_unspaced_ticks() {
+        let mut storage = MockStorage::new();
+        assert_eq!(
+            BITMAP.flip_tick(&mut storage, 61, 60).unwrap_err(),
+            StdError::generic_err("tick must be a multiple of tick_spacing")
+        );
+        assert!(BITMAP.word(&storage, 0).unwrap().is_zero());
+    }
+
+    #[test]
+    fn next_initialized_tick_within_one_word_matches_uniswap() {
+        let storage = bitmap(&[-200, -55, -4, 70, 78, 84, 139, 240, 535]);
+        let next = |tick, lte| {
+            BITMAP
+                .next_initialized_tick_within_one_word(&storage, tick, 1, lte)
+                .unwrap()
+        };
+
+        assert_eq!(next(78, false), (84, true));
+        assert_eq!(next(-55, false), (-4, true));
+        assert_eq!(next(77, false), (78, true));
+        assert_eq!(next(-56, false), (-55, true));
+        assert_eq!(next(255, false), (511, false));
+        assert_eq!(next(-257, false), (-200, true));
+        assert_eq!(next(340, false), (511, false));
+        assert_eq!(next(328, false), (511, false));
+
+        assert_eq!(next(78, true), (78, true));
+        assert_eq!(next(79, true), (78, true));
+        assert_eq!(next(258, true), (256, false));
+        assert_eq!(next(256, true), (256, false));
+        assert_eq!(next(72, true), (70, true));
+        assert_eq!(next(-257, true), (-512, false));
+        assert_eq!(next(1023, true), (768, false));
+        assert_eq!(next(900, true), (768, false));
+    }
+
+    #[test]
+    fn next_initialized_tick_searches_following_words() {
+        let storage = bitmap(&[-5000, -200, 70, 5000]);
+        let next = |tick, lte, max_words| {
+            BITMAP
+                .next_initialized_tick(&storage, tick, 1, lte, max_words)
+                .unwrap()
+        };
+
+        // the word of the tick itself is searched first
+        assert_eq!(next(78, true, 30), (70, true));
+        assert_eq!(next(-100, false, 30), (70, true));
+
+        // empty words are skipped up to the bound, inclusive of the last word
+        assert_eq!(next(300, false, 30), (5000, true));
+        assert_eq!(next(300, false, 19), (5000, true));
+        assert_eq!(next(300, false, 18), (4863, false));
+        assert_eq!(next(-300, true, 19), (-5000, true));
+        assert_eq!(next(-300, true, 18), (-4864, false));
+        assert_eq!(next(-5001, true, 30), (-12544, false));
+
+        // a single word behaves like next_initialized_tick_within_one_word
+        assert_eq!(next(300, false, 1), (511, false));
+        assert_eq!(next(300, false, 0), (511, false));
+    }
+
+    #[test]
+    fn next_initialized_tick_stops_at_the_tick_range() {
+        let storage = bitmap(&[]);
+        for tick_spacing in [1, 10, 60, 200, 16384] {
+            let (min_word, max_word) = word_bounds(tick_spacing);
+            let (next, initialized) = BITMAP
+                .next_initialized_tick(&storage, 0, tick_spacing, true, u16::MAX)
+                .unwrap();
+            assert_eq!(
+                (next, initialized),
+                (min_word as i24 * 256 * tick_spacing, false)
+            );
+            assert!(next <= MIN_TICK);
+
+            let (next, initialized) = BITMAP
+                .next_initialized_tick(&storage, 0, tick_spacing, false, u16::MAX)
+                .unwrap();
+            assert_eq!(
+                (next, initialized),
+                ((max_word as i24 * 256 + 255) * tick_spacing, false)
+            );
+            assert!(next >= MAX_TICK);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use cosmwasm_std::{Int256, Uint256};
+
+use crate::bit_math::BitMath;
+use crate::fixed_point_128::FixedPoint128;
+use crate::safe_cast::to_i128;
+use crate::types::{i24, u160};
+
+/// The minimum tick that may be passed to `get_sqrt_ratio_at_tick` computed from log base 1.0001 of 2**-128
+pub const MIN_TICK: i24 = -887272;
+
+/// The maximum tick that may be passed to `get_sqrt_ratio_at_tick` computed from log base 1.0001 of 2**128
+pub const MAX_TICK: i24 = -MIN_TICK;
+
+/// The minimum value that can be returned from `get_sqrt_ratio_at_tick`.
+/// Equivalent to `get_sqrt_ratio_at_tick(MIN_TICK)`
+pub const MIN_SQRT_RATIO: u160 = Uint256::from_u128(4295128739);
+
+/// The maximum value that can be returned from `get_sqrt_ratio_at_tick`.
+/// Equivalent to `get_sqrt_ratio_at_tick(MAX_TICK)`, i.e. 1461446703485210103287273052203988822378723970342
+pub const MAX_SQRT_RATIO: u160 = Uint256::from_be_bytes([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+    0xff, 0xfd, 0x89, 0x63, 0xef, 0xd1, 0xfc, 0x6a, 0x50, 0x64, //
+    0x88, 0x49, 0x5d, 0x95, 0x1d, 0x52, 0x63, 0x98, 0x8d, 0x26,
+]);
+
+/// Multipliers applied for each set bit of |tick|, as Q128.128 numbers of 1/sqrt(1.0001)^(2^i)
+const RATIO_MULTIPLIERS: [(u32, u128); 19] = [
+    (0x2, 0xfff97272373d413259a46990580e213a),
+    (0x4, 0xfff2e50f5f656932ef12357cf3c7fdcc),
+    (0x8, 0xffe5caca7e10e4e61c3624eaa0941cd0),
+    (0x10, 0xffcb9843d60f6159c9db58835c926644),
+    (0x20, 0xff973b41fa98c081472e6896dfb254c0),
+    (0x40, 0xff2ea16466c96a3843ec78b326b52861),
+    (0x80, 0xfe5dee046a99a2a811c461f1969c3053),
+    (0x100, 0xfcbe86c7900a88aedcffc83b479aa3a4),
+    (0x200, 0xf987a7253ac413176f2b074cf7815e54),
+    (0x400, 0xf3392b0822b70005940c7a398e4b70f3),
+    (0x800, 0xe7159475a2c29b7443b29c7fa6e889d9),
+    (0x1000, 0xd097f3bdfd2022b8845ad8f792aa5825),
+    (0x2000, 0xa9f746462d870fdf8a65dc1f90e061e5),
+    (0x4000, 0x70d869a156d2a1b890bb3df62baf32f7),
+    (0x8000, 0x31be135f97d08fd981231505542fcfa6),
+    (0x10000, 0x9aa508b5b7a84e1c677de54f3e99bc9),
+    (0x20000, 0x5d6af8dedb81196699c329225ee604),
+    (0x40000, 0x2216e584f5fa1ea926041bedfe98),
+    (0x80000, 0x48a170391f7dc42444e8fa2),
+];
+
+/// Calculates sqrt(1.0001^tick) * 2^96
+/// Throws if |tick| > max tick
+/// @param tick The input tick for the above formula
+/// @return sqrt_price_x96 A Fixed point Q64.96 number representing the sqrt of the ratio of the two assets (token1/token0)
+/// at the given tick
+pub fn get_sqrt_ratio_at_tick(tick: i24) -> u160 {
+    let abs_tick = tick.unsigned_abs();
+    assert!(abs_tick <= MAX_TICK as u32, "T");
+
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        Uint256::from_u128(0xfffcb933bd6fad37aa2d162d1a594001)
+    } else {
+        FixedPoint128::Q128
+    };
+    for (bit, multiplier) in RATIO_MULTIPLIERS {
+        if abs_tick & bit != 0 {
+            ratio = (ratio * Uint256::from_u128(multiplier)) >> 128;
+        }
+    }
+
+    if tick > 0 {
+        ratio = Uint256::MAX / ratio;
+    }
+
+    // this divides by 1 << 32 rounding up to go from a Q128.128 to a Q128.96.
+    // we then downcast because we know the result always fits within 160 bits due to our tick input constraint
+    // we round up in the division so get_tick_at_sqrt_ratio of the output price is always consistent
+    let round_up = if (ratio % Uint256::from_u128(1 << 32)).is_zero() {
+        Uint256::zero()
+    } else {
+        Uint256::one()
+    };
+    (ratio >> 32) + round_up
+}
+
+/// Calculates the greatest tick value such that get_ratio_at_tick(tick) <= ratio
+/// Throws in case sqrt_price_x96 < MIN_SQRT_RATIO, as MIN_SQRT_RATIO is the lowest value get_ratio_at_tick may ever return.
+/// @param sqrt_price_x96 The sqrt ratio for which to compute the tick as a Q64.96
+/// @return tick The greatest tick for which the ratio is less than or equal to the input ratio
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x96: u160) -> i24 {
+    // second inequality must be < because the price can never reach the price at the max tick
+    assert!(
+        sqrt_price_x96 >= MIN_SQRT_RATIO && sqrt_price_x96 < MAX_SQRT_RATIO,
+        "R"
+    );
+
+    let ratio = sqrt_price_x96 << 32;
+    let msb = BitMath::most_significant_bit(ratio).expect("ratio is non-zero") as u32;
+
+    let mut r = if msb >= 128 {
+        ratio >> (msb - 127)
+    } else {
+        ratio << (127 - msb)
+    };
+
+    let mut log_2 = (Int256::from(msb) - Int256::from(128u8)) << 64;
+
+    // the low 64 bits of log_2 start out clear, so setting each fractional bit is a plain addition
+    for shift in (50..=63).rev() {
+        r = (r * r) >> 127;
+        let f = r >> 128;
+        log_2 += Int256::from_be_bytes(f.to_be_bytes()) << shift;
+        r >>= if f.is_zero() { 0 } else { 1 };
+    }
+
+    let log_sqrt10001 = log_2 * Int256::from(255738958999603826347141u128); // 128.128 number
+
+    let tick_low =
+        to_i128((log_sqrt10001 - Int256::from(3402992956809132418596140100660247210u128)) >> 128)
+            as i32;
+    let tick_hi =
+        to_i128((log_sqrt10001 + Int256::from(291339464771989622907027621153398088495u128)) >> 128)
+            as i32;
+
+    if tick_low == tick_hi || get_sqrt_ratio_at_tick(tick_hi) > sqrt_price_x96 {
+        tick_low
+    } else {
+        tick_hi
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::fixed_point_96::FixedPoint96;
+
+    /// Parses a fixture table of `input,output` rows, skipping `#` comments
+    fn fixture<A: FromStr, B: FromStr>(table: &str) -> Vec<(A, B)>
+    where
+        A::Err: std::fmt::Debug,
+        B::Err: std::fmt::Debug,
+    {
+        table
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (a, b) = line.split_once(',').unwrap();
+                (a.parse().unwrap(), b.parse().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bounds_match_the_reference_values() {
+        assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK), MIN_SQRT_RATIO);
+        assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK), MAX_SQRT_RATIO);
+        assert_eq!(get_sqrt_ratio_at_tick(0), FixedPoint96::Q96);
+        assert_eq!(
+            get_sqrt_ratio_at_tick(MIN_TICK + 1),
+            Uint256::from(4295343490u128)
+        );
+        assert_eq!(
+            get_sqrt_ratio_at_tick(MAX_TICK - 1),
+            Uint256::from_str("1461373636630004318706518188784493106690254656249").unwrap()
+        );
+
+        assert_eq!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO), MIN_TICK);
+        assert_eq!(
+            get_tick_at_sqrt_ratio(MIN_SQRT_RATIO + Uint256::one()),
+            MIN_TICK
+        );
+        assert_eq!(
+            get_tick_at_sqrt_ratio(MAX_SQRT_RATIO - Uint256::one()),
+            MAX_TICK - 1
+        );
+        assert_eq!(get_tick_at_sqrt_ratio(FixedPoint96::Q96), 0);
+        assert_eq!(
+            get_tick_at_sqrt_ratio(FixedPoint96::Q96 - Uint256::one()),
+            -1
+        );
+    }
+
+    #[test]
+    fn matches_the_fixture_tables() {
+        let ratios = fixture::<i32, Uint256>(include_str!("../fixtures/sqrt_ratio_at_tick.csv"));
+        assert!(!ratios.is_empty());
+        for (tick, sqrt_price_x96) in ratios {
+            assert_eq!(get_sqrt_ratio_at_tick(tick), sqrt_price_x96, "tick {tick}");
+        }
+
+        let ticks = fixture::<Uint256, i32>(include_str!("../fixtures/tick_at_sqrt_ratio.csv"));
+        assert!(!ticks.is_empty());
+        for (sqrt_price_x96, tick) in ticks {
+            assert_eq!(
+                get_tick_at_sqrt_ratio(sqrt_price_x96),
+                tick,
+                "sqrt price {sqrt_price_x96}"
+            );
+        }
+    }
+
+    /// Checks the tick's price maps back to the tick, and the price just below it to the tick below
+    fn assert_round_trip(tick: i32) {
+        let sqrt_price_x96 = get_sqrt_ratio_at_tick(tick);
+        if tick < MAX_TICK {
+            assert_eq!(get_tick_at_sqrt_ratio(sqrt_price_x96), tick);
+        }
+        if tick > MIN_TICK {
+            assert_eq!(
+                get_tick_at_sqrt_ratio(sqrt_price_x96 - Uint256::one()),
+                tick - 1
+            );
+            assert!(get_sqrt_ratio_at_tick(tick - 1) < sqrt_price_x96);
+        }
+    }
+
+    /// Spacings 60 and 200 are checked at every usable tick. Spacings 1 and 10 are sampled, not exhaustive: checking
+    /// all 1_774_545 ticks takes about 90s in a debug build, so they are only checked at every usable tick in the
+    /// 10_000 tick windows at each bound and around the starting price, where the bit math changes the most
+    #[test]
+    fn round_trips_at_every_tick_spacing_boundary() {
+        for tick_spacing in [60, 200] {
+            let max_tick = MAX_TICK / tick_spacing * tick_spacing;
+            for tick in (-max_tick..=max_tick).step_by(tick_spacing as usize) {
+                assert_round_trip(tick);
+            }
+        }
+        for tick_spacing in [1, 10] {
+            let max_tick = MAX_TICK / tick_spacing * tick_spacing;
+            for start in [-max_tick, -5_000, max_tick - 10_000] {
+                for tick in (start..=start + 10_000).step_by(tick_spacing as usize) {
+                    assert_round_trip(tick);
+                }
+            }
+        }
+    }
+
+    /// The message of the panic raised by `f`, which must panic
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let payload = std::panic::catch_unwind(f).unwrap_err();
+        match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => payload.downcast::<&str>().unwrap().to_string(),
+        }
+    }
+
+    #[test]
+    fn ticks_outside_the_bounds_panic() {
+        assert_eq!(
+            panic_message(|| {
+                get_sqrt_ratio_at_tick(MAX_TICK + 1);
+            }),
+            "T"
+        );
+        assert_eq!(
+            panic_message(|| {
+                get_sqrt_ratio_at_tick(MIN_TICK - 1);
+            }),
+            "T"
+        );
+    }
+
+    #[test]
+    fn sqrt_ratios_outside_the_bounds_panic() {
+        assert_eq!(
+            panic_message(|| {
+                get_tick_at_sqrt_ratio(MIN_SQRT_RATIO - Uint256::one());
+            }),
+            "R"
+        );
+        assert_eq!(
+            panic_message(|| {
+                get_tick_at_sqrt_ratio(MAX_SQRT_RATIO);
+            }),
+            "R"
+        );
+    }
+}
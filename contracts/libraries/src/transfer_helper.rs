@@ -0,0 +1,242 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Moves native and CW20 tokens behind one asset type
+//! Native tokens are sent with `BankMsg::Send`; CW20 tokens with `Transfer`, or `TransferFrom` when pulling an allowance.
+
+use std::fmt;
+
+use cosmwasm_std::{
+    coins, to_binary, Addr, BankMsg, CosmosMsg, QuerierWrapper, StdError, StdResult, Uint128,
+    WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A token: a native denom or a CW20 contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum AssetInfo {
+    Native { denom: String },
+    Cw20 { contract_addr: String },
+}
+
+impl AssetInfo {
+    pub fn native(denom: impl Into<String>) -> Self {
+        AssetInfo::Native {
+            denom: denom.into(),
+        }
+    }
+
+    pub fn cw20(contract_addr: impl Into<String>) -> Self {
+        AssetInfo::Cw20 {
+            contract_addr: contract_addr.into(),
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self, AssetInfo::Native { .. })
+    }
+
+    /// An amount of this token
+    pub fn with_amount(&self, amount: Uint128) -> Asset {
+        Asset {
+            info: self.clone(),
+            amount,
+        }
+    }
+
+    /// The balance `address` holds of this token
+    pub fn query_balance(&self, querier: &QuerierWrapper, address: &Addr) -> StdResult<Uint128> {
+        match self {
+            AssetInfo::Native { denom } => Ok(querier.query_balance(address, denom)?.amount),
+            AssetInfo::Cw20 { contract_addr } => {
+                let res: BalanceResponse = querier.query_wasm_smart(
+                    contract_addr,
+                    &Cw20QueryMsg::Balance {
+                        address: address.to_string(),
+                    },
+                )?;
+                Ok(res.balance)
+            }
+        }
+    }
+}
+
+/// Formats as the denom or contract address
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetInfo::Native { denom } => write!(f, "{denom}"),
+            AssetInfo::Cw20 { contract_addr } => write!(f, "{contract_addr}"),
+        }
+    }
+}
+
+/// An amount of a token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct Asset {
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+impl Asset {
+    /// Transfers the asset from the calling contract to `recipient`
+    pub fn transfer_msg(&self, recipient: &Addr) -> StdResult<CosmosMsg> {
+        safe_transfer(&self.info, recipient, self.amount)
+    }
+
+    /// Pulls the asset from `owner` to `recipient` using the calling contract's allowance
+    pub fn transfer_from_msg(&self, owner: &Addr, recipient: &Addr) -> StdResult<CosmosMsg> {
+        safe_transfer_from(&self.info, owner, recipient, self.amount)
+    }
+}
+
+impl fmt::Display for Asset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.amount, self.info)
+    }
+}
+
+/// Transfers tokens from the calling contract to a recipient
+/// @param token The token which will be transferred
+/// @param to The recipient of the transfer
+/// @param value The value of the transfer
+pub fn safe_transfer(token: &AssetInfo, to: &Addr, value: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match token {
+        AssetInfo::Native { denom } => BankMsg::Send {
+            to_address: to.to_string(),
+            amount: coins(value.u128(), denom),
+        }
+        .into(),
+        AssetInfo::Cw20 { contract_addr } => WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount: value,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+/// Transfers CW20 tokens from the targeted address to a recipient using the calling contract's allowance
+/// Native tokens can't be pulled; they have to be attached to the message as funds instead
+/// @param token The CW20 token which will be transferred
+/// @param from The originating address from which the tokens will be transferred
+/// @param to The destination address of the transfer
+/// @param value The amount to be transferred
+pub fn safe_transfer_from(
+    token: &AssetInfo,
+    from: &Addr,
+    to: &Addr,
+    value: Uint128,
+) -> StdResult<CosmosMsg> {
+    match token {
+        AssetInfo::Native { denom } => Err(StdError::generic_err(format!(
+            "native {denom} can't be transferred from another account, attach it as funds"
+        ))),
+        AssetInfo::Cw20 { contract_addr } => Ok(WasmMsg::Execute {
+            contract_addr: contract_addr.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: from.to_string(),
+                recipient: to.to_string(),
+                amount: value,
+            })?,
+            funds: vec![],
+        }
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(address: &str) -> Addr {
+        Addr::unchecked(address)
+    }
+
+    #[test]
+    fn safe_transfer_sends_native_tokens_with_the_bank() {
+        let msg =
+            safe_transfer(&AssetInfo::native("uxion"), &addr("alice"), Uint128::new(5)).unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "alice".to_string(),
+                amount: coins(5, "uxion"),
+            })
+        );
+        assert_eq!(
+            AssetInfo::native("uxion")
+                .with_amount(Uint128::new(5))
+                .transfer_msg(&addr("alice")),
+            Ok(msg)
+        );
+    }
+
+    #[test]
+    fn safe_transfer_sends_cw20_tokens_with_transfer() {
+        let msg =
+            safe_transfer(&AssetInfo::cw20("token"), &addr("alice"), Uint128::new(5)).unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "alice".to_string(),
+                    amount: Uint128::new(5),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn safe_transfer_from_pulls_cw20_allowances_only() {
+        let msg = safe_transfer_from(
+            &AssetInfo::cw20("token"),
+            &addr("alice"),
+            &addr("pool"),
+            Uint128::new(5),
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: "alice".to_string(),
+                    recipient: "pool".to_string(),
+                    amount: Uint128::new(5),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            AssetInfo::cw20("token")
+                .with_amount(Uint128::new(5))
+                .transfer_from_msg(&addr("alice"), &addr("pool")),
+            Ok(msg)
+        );
+
+        let err = safe_transfer_from(
+            &AssetInfo::native("uxion"),
+            &addr("alice"),
+            &addr("pool"),
+            Uint128::new(5),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err(
+                "native uxion can't be transferred from another account, attach it as funds"
+            )
+        );
+    }
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Fixed-width integer types shared by the libraries and the contracts built on them
+//! The pool math is specified in terms of Solidity's sized integers; these aliases keep those widths visible in
+//! signatures while mapping each one onto the smallest native or CosmWasm type that can hold it.
+//! Values of the narrower types are kept in range by the math that produces them (e.g. `SafeCast::to_u160`).
+
+#![allow(non_camel_case_types)]
+
+use cosmwasm_std::Uint256;
+
+/// An unsigned 160-bit integer, used for Q64.96 sqrt prices
+pub type u160 = Uint256;
+
+/// An unsigned 256-bit integer, used for fee growth accumulators and token amounts in intermediate math
+pub type u256 = Uint256;
+
+/// A signed 24-bit integer, used for ticks
+pub type i24 = i32;
+
+/// A signed 56-bit integer, used for tick accumulators
+pub type i56 = i64;
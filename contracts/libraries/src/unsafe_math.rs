@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+use cosmwasm_std::{DivideByZeroError, StdError, StdResult, Uint256};
+
+/// Computes the ceiling of the division x / y.
+/// @param x The dividend
+/// @param y The divisor
+/// @return The quotient, ceil(x / y)
+/// Panics if y == 0
+pub fn div_rounding_up(x: Uint256, y: Uint256) -> Uint256 {
+    checked_div_rounding_up(x, y).unwrap_or_else(|err| panic!("{err}"))
+}
+
+/// Computes the ceiling of the division x / y, failing if y == 0
+pub fn checked_div_rounding_up(x: Uint256, y: Uint256) -> StdResult<Uint256> {
+    if y.is_zero() {
+        return Err(StdError::divide_by_zero(DivideByZeroError::new(x)));
+    }
+    Ok(x / y
+        + if (x % y).is_zero() {
+            Uint256::zero()
+        } else {
+            Uint256::one()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn div_rounding_up_rounds_remainders_up() {
+        let uint = |value: u128| Uint256::from(value);
+        assert_eq!(div_rounding_up(uint(6), uint(3)), uint(2));
+        assert_eq!(div_rounding_up(uint(7), uint(3)), uint(3));
+        assert_eq!(div_rounding_up(Uint256::MAX, uint(1)), Uint256::MAX);
+        assert_eq!(
+            div_rounding_up(Uint256::MAX, uint(2)),
+            Uint256::MAX / uint(2) + uint(1)
+        );
+        assert!(matches!(
+            checked_div_rounding_up(uint(1), Uint256::zero()),
+            Err(StdError::DivideByZero { .. })
+        ));
+    }
+}